@@ -8,12 +8,12 @@ use std::{
   fmt::{Display, Formatter},
 };
 
-use marchrs_iterators::MultiRangeIter;
+use marchrs_iterators::{Grid, MultiRangeIter};
 use marchrs_rays::{
   CameraPlane, PerspectiveCamera3D, RayMarcher, RaySource, ScreenInfo, SphereMarcher,
 };
 use marchrs_sdf::{items::*, traits::*};
-use marchrs_vectors::Vector;
+use marchrs_vectors::{Scalar, Vector};
 
 #[derive(Clone, Copy)]
 struct Colour([u8; 3]);
@@ -36,17 +36,17 @@ impl Display for Colour {
 
 //
 
-fn get_model() -> DynModel<3, Colour> {
-  Sphere.scale([0.1; 3]).with(RED).wrap()
+fn get_model<S: Scalar>() -> DynModel<3, Colour, S> {
+  DynModel::new(Sphere.scale([0.1; 3]).with(RED))
 }
 
-fn get_camera() -> PerspectiveCamera3D {
-  let plane = CameraPlane::new([-0.5, 0.0, 0.0], [1.0, 0.0, 0.0]);
-  let upwards = Vector::from([0.0, 0.0, 1.0]);
-  PerspectiveCamera3D::new(plane, upwards, PI / 8.0)
+fn get_camera<S: Scalar>() -> PerspectiveCamera3D<S> {
+  let plane = CameraPlane::new(Vector::axis(0) * S::from_f64(-0.5), Vector::axis(0));
+  let upwards = Vector::axis(2);
+  PerspectiveCamera3D::new(plane, upwards, S::from_f64(PI / 8.0))
 }
 
-fn get_marcher() -> impl RayMarcher<3> {
+fn get_marcher<S: Scalar>() -> impl RayMarcher<3, S> {
   SphereMarcher {
     hit_error: 1e-4,
     miss_error: 1e2,
@@ -54,28 +54,35 @@ fn get_marcher() -> impl RayMarcher<3> {
   }
 }
 
-fn render() -> Vec<Vec<Colour>> {
-  let screen_info = ScreenInfo::new([0.3, 0.2], [150, 100]);
-  let model = get_model();
-  let camera = get_camera();
-  let marcher = get_marcher();
+fn render<S: Scalar>() -> Grid<2, Colour> {
+  let screen_info = ScreenInfo::new(Vector([0.3, 0.2]).cast::<S>(), [150, 100]);
+  let model = get_model::<S>();
+  let camera = get_camera::<S>();
+  let marcher = get_marcher::<S>();
 
   let rendered: Vec<_> = camera
     .rays(&screen_info)
     .map(|ray| marcher.march(&model, ray))
     .collect();
 
-  let mut screen = vec![vec![BLACK; screen_info.res[0]]; screen_info.res[1]];
-  for ([i, j], opt_colour) in MultiRangeIter::from(screen_info.res).zip(rendered) {
+  let mut screen = Grid::new(screen_info.res, BLACK);
+  for (idx, opt_colour) in MultiRangeIter::from(screen_info.res).zip(rendered) {
     let Ok(hit_pos) = opt_colour else { continue };
-    screen[j][i] = model.info(hit_pos)
+    screen[idx] = model.info(hit_pos)
   }
 
   screen
 }
 
+/// Renders in double precision by default,\
+/// pass `f32` as the first argument to render in single precision.
 pub fn main() {
+  let single = std::env::args().nth(1).is_some_and(|arg| arg == "f32");
   for _ in 0..500 {
-    render();
+    if single {
+      render::<f32>();
+    } else {
+      render::<f64>();
+    }
   }
 }
@@ -0,0 +1,353 @@
+use super::par_iter::{ParIter, Splittable};
+use rayon::iter::IntoParallelIterator;
+
+/// A function applied by `Map`.
+///
+/// This is implemented for all closures, but unlike the closure traits\
+/// it can also be implemented by named types, so that mapped iterators\
+/// can be named, e.g. as the associated type of a trait.
+pub trait MapFn<T> {
+  type Output;
+  fn call(&self, item: T) -> Self::Output;
+}
+
+impl<T, O, F: Fn(T) -> O> MapFn<T> for F {
+  type Output = O;
+  #[inline]
+  fn call(&self, item: T) -> Self::Output {
+    self(item)
+  }
+}
+
+/// Adapters that keep iterators `Splittable`, mirroring those on `Iterator`.
+///
+/// Each adapter can be turned into a `ParIter`, which is an\
+/// `IndexedParallelIterator` so long as the original iterators are.
+pub trait SplittableExt: Splittable + Iterator {
+  /// Applies `f` to each item, see `Iterator::map`.\
+  /// Use `Map::new` to map by a named `MapFn` instead.
+  fn mapped<O, F: Fn(Self::Item) -> O + Clone>(self, f: F) -> Map<Self, F> {
+    Map::new(self, f)
+  }
+
+  /// Pairs up the items of both iterators, see `Iterator::zip`.
+  fn zipped<B: Splittable + ExactSizeIterator>(self, other: B) -> Zip<Self, B>
+  where
+    Self: ExactSizeIterator,
+  {
+    // trimming to the same length keeps splits and `next_back` in step
+    let len = self.len().min(other.len());
+    Zip {
+      a: self.split_at(len).0,
+      b: other.split_at(len).0,
+    }
+  }
+
+  /// Pairs each item with its position, see `Iterator::enumerate`.
+  fn enumerated(self) -> Enumerate<Self> {
+    Enumerate {
+      iter: self,
+      offset: 0,
+    }
+  }
+
+  /// Iterates over `other` after this iterator, see `Iterator::chain`.
+  fn chained<B>(self, other: B) -> Chain<Self, B>
+  where
+    B: Splittable + Iterator<Item = Self::Item>,
+  {
+    Chain { a: self, b: other }
+  }
+}
+
+impl<I: Splittable + Iterator> SplittableExt for I {}
+
+/// Implements `IntoParallelIterator` via `ParIter`
+macro_rules! impl_into_par_iter {
+  ($([$($generics:tt)*] $adapter:ty),*) => {$(
+    impl<$($generics)*> IntoParallelIterator for $adapter
+    where
+      Self: ExactSizeIterator + DoubleEndedIterator + Splittable + Send,
+      <Self as Iterator>::Item: Send,
+    {
+      type Item = <Self as Iterator>::Item;
+      type Iter = ParIter<Self>;
+      fn into_par_iter(self) -> Self::Iter {
+        ParIter(self)
+      }
+    }
+  )*};
+}
+
+impl_into_par_iter!(
+  [I, F] Map<I, F>,
+  [A, B] Zip<A, B>,
+  [I] Enumerate<I>,
+  [A, B] Chain<A, B>
+);
+
+/// A `Splittable` iterator, mapped by `f`
+#[derive(Clone, Copy, Debug)]
+pub struct Map<I, F> {
+  iter: I,
+  f: F,
+}
+
+impl<I: Iterator, F: MapFn<I::Item>> Map<I, F> {
+  pub fn new(iter: I, f: F) -> Self {
+    Self { iter, f }
+  }
+}
+
+impl<I: Iterator, F: MapFn<I::Item>> Iterator for Map<I, F> {
+  type Item = F::Output;
+
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    self.iter.next().map(|item| self.f.call(item))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+}
+
+impl<I: ExactSizeIterator, F: MapFn<I::Item>> ExactSizeIterator for Map<I, F> {}
+
+impl<I: DoubleEndedIterator, F: MapFn<I::Item>> DoubleEndedIterator for Map<I, F> {
+  #[inline]
+  fn next_back(&mut self) -> Option<Self::Item> {
+    self.iter.next_back().map(|item| self.f.call(item))
+  }
+}
+
+impl<I: Splittable, F: Clone> Splittable for Map<I, F> {
+  fn split_at(self, index: usize) -> (Self, Self) {
+    let (iter0, iter1) = self.iter.split_at(index);
+    (
+      Self {
+        iter: iter0,
+        f: self.f.clone(),
+      },
+      Self {
+        iter: iter1,
+        f: self.f,
+      },
+    )
+  }
+}
+
+/// Two `Splittable` iterators of the same length, iterated in step
+#[derive(Clone, Copy, Debug)]
+pub struct Zip<A, B> {
+  a: A,
+  b: B,
+}
+
+impl<A: Iterator, B: Iterator> Iterator for Zip<A, B> {
+  type Item = (A::Item, B::Item);
+
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    Some((self.a.next()?, self.b.next()?))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.a.size_hint()
+  }
+}
+
+impl<A: ExactSizeIterator, B: ExactSizeIterator> ExactSizeIterator for Zip<A, B> {}
+
+impl<A: DoubleEndedIterator, B: DoubleEndedIterator> DoubleEndedIterator for Zip<A, B> {
+  #[inline]
+  fn next_back(&mut self) -> Option<Self::Item> {
+    Some((self.a.next_back()?, self.b.next_back()?))
+  }
+}
+
+impl<A: Splittable, B: Splittable> Splittable for Zip<A, B> {
+  fn split_at(self, index: usize) -> (Self, Self) {
+    let (a0, a1) = self.a.split_at(index);
+    let (b0, b1) = self.b.split_at(index);
+    (Self { a: a0, b: b0 }, Self { a: a1, b: b1 })
+  }
+}
+
+/// A `Splittable` iterator, with the position of each item
+#[derive(Clone, Copy, Debug)]
+pub struct Enumerate<I> {
+  iter: I,
+  /// The position of the first remaining item
+  offset: usize,
+}
+
+impl<I: Iterator> Iterator for Enumerate<I> {
+  type Item = (usize, I::Item);
+
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    let item = self.iter.next()?;
+    self.offset += 1;
+    Some((self.offset - 1, item))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for Enumerate<I> {}
+
+impl<I: ExactSizeIterator + DoubleEndedIterator> DoubleEndedIterator for Enumerate<I> {
+  #[inline]
+  fn next_back(&mut self) -> Option<Self::Item> {
+    let item = self.iter.next_back()?;
+    Some((self.offset + self.iter.len(), item))
+  }
+}
+
+impl<I: Splittable + ExactSizeIterator> Splittable for Enumerate<I> {
+  fn split_at(self, index: usize) -> (Self, Self) {
+    let index = index.min(self.iter.len());
+    let (iter0, iter1) = self.iter.split_at(index);
+    (
+      Self {
+        iter: iter0,
+        offset: self.offset,
+      },
+      Self {
+        iter: iter1,
+        offset: self.offset + index,
+      },
+    )
+  }
+}
+
+/// Two `Splittable` iterators, one after the other
+#[derive(Clone, Copy, Debug)]
+pub struct Chain<A, B> {
+  a: A,
+  b: B,
+}
+
+impl<A: Iterator, B: Iterator<Item = A::Item>> Iterator for Chain<A, B> {
+  type Item = A::Item;
+
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    self.a.next().or_else(|| self.b.next())
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let (lo_a, hi_a) = self.a.size_hint();
+    let (lo_b, hi_b) = self.b.size_hint();
+    let hi = hi_a.zip(hi_b).and_then(|(a, b)| a.checked_add(b));
+    (lo_a.saturating_add(lo_b), hi)
+  }
+}
+
+impl<A, B> ExactSizeIterator for Chain<A, B>
+where
+  A: ExactSizeIterator,
+  B: ExactSizeIterator<Item = A::Item>,
+{
+}
+
+impl<A, B> DoubleEndedIterator for Chain<A, B>
+where
+  A: DoubleEndedIterator,
+  B: DoubleEndedIterator<Item = A::Item>,
+{
+  #[inline]
+  fn next_back(&mut self) -> Option<Self::Item> {
+    self.b.next_back().or_else(|| self.a.next_back())
+  }
+}
+
+impl<A, B> Splittable for Chain<A, B>
+where
+  A: Splittable + ExactSizeIterator,
+  B: Splittable + ExactSizeIterator,
+{
+  fn split_at(self, index: usize) -> (Self, Self) {
+    let len = self.a.len();
+    if index <= len {
+      let (a0, a1) = self.a.split_at(index);
+      let (b0, b1) = self.b.split_at(0);
+      (Self { a: a0, b: b0 }, Self { a: a1, b: b1 })
+    } else {
+      let (b0, b1) = self.b.split_at(index - len);
+      let (a0, a1) = self.a.split_at(len);
+      (Self { a: a0, b: b0 }, Self { a: a1, b: b1 })
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::MultiRangeIter;
+  use proptest::{array, prelude::*};
+  use rayon::iter::IndexedParallelIterator;
+
+  fn multi_iter<const N: usize>(
+    range: impl Strategy<Value = usize>,
+  ) -> impl Strategy<Value = MultiRangeIter<N>> {
+    array::uniform(range).prop_map_into()
+  }
+
+  /// Checks splitting `iter` at every index, from both ends
+  fn split_iter_case<I>(iter: I) -> Result<(), TestCaseError>
+  where
+    I: Splittable + ExactSizeIterator + DoubleEndedIterator + Clone,
+    I::Item: Clone + PartialEq + std::fmt::Debug,
+  {
+    let items: Vec<_> = iter.clone().collect();
+    prop_assert_eq!(iter.len(), items.len());
+    prop_assert!(iter.clone().rev().eq(items.iter().rev().cloned()));
+    for idx in 0..=items.len() {
+      let (iter0, iter1) = iter.clone().split_at(idx);
+      prop_assert_eq!(iter0.len(), idx);
+      prop_assert_eq!(iter1.len(), items.len() - idx);
+      prop_assert!(iter0
+        .chain(iter1.rev().collect::<Vec<_>>().into_iter().rev())
+        .eq(items.iter().cloned()));
+    }
+    Ok(())
+  }
+
+  proptest! {
+    #[test]
+    fn split_map(iter in multi_iter::<2>(1usize..6)) {
+      split_iter_case(iter.mapped(|[i, j]| i * 10 + j))?;
+    }
+
+    #[test]
+    fn split_zip(a in multi_iter::<2>(1usize..6), b in multi_iter::<3>(1usize..4)) {
+      split_iter_case(a.zipped(b))?;
+    }
+
+    #[test]
+    fn split_enumerate(iter in multi_iter::<2>(1usize..6), skip in 0usize..5) {
+      let (_, iter) = iter.split_at(skip);
+      let iter = iter.enumerated();
+      prop_assert!(iter.map(|(i, _)| i).eq(0..iter.len()));
+      split_iter_case(iter)?;
+    }
+
+    #[test]
+    fn split_chain(a in multi_iter::<2>(1usize..6), b in multi_iter::<2>(1usize..6)) {
+      split_iter_case(a.chained(b))?;
+    }
+
+    #[test]
+    fn par_collect(a in multi_iter::<2>(1usize..10), b in multi_iter::<2>(1usize..10)) {
+      let iter = a.chained(b).enumerated().zipped(b).mapped(|((i, x), y)| (i, x[0] + y[1]));
+      let vec: Vec<_> = iter.collect();
+      let mut par_vec = vec![];
+      iter.into_par_iter().collect_into_vec(&mut par_vec);
+      prop_assert_eq!(vec, par_vec)
+    }
+  }
+}
@@ -0,0 +1,135 @@
+use std::{
+  fmt::{Display, Formatter},
+  sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc,
+  },
+};
+
+/// A shared flag for stopping a parallel iteration early.
+///
+/// Clones share the same flag, so one clone can be handed to\
+/// the workers while another is kept to cancel them, e.g.\
+/// when the camera moves mid-render.
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Signals all clones of this token to stop
+  pub fn cancel(&self) {
+    self.0.store(true, Ordering::Relaxed)
+  }
+
+  pub fn is_cancelled(&self) -> bool {
+    self.0.load(Ordering::Relaxed)
+  }
+
+  /// `Err(Cancelled)` if cancelled, for use with `?`
+  pub fn check(&self) -> Result<(), Cancelled> {
+    match self.is_cancelled() {
+      true => Err(Cancelled),
+      false => Ok(()),
+    }
+  }
+}
+
+/// An error indicating that work was stopped by a `CancelToken`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl Display for Cancelled {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Cancelled before finishing")
+  }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// A shared counter of the items finished out of a total.
+///
+/// Workers call `add` as they finish items, whilst any clone\
+/// can be polled from another thread to show a progress bar.
+#[derive(Clone, Debug, Default)]
+pub struct Progress {
+  done: Arc<AtomicUsize>,
+  total: Arc<AtomicUsize>,
+}
+
+impl Progress {
+  pub fn new(total: usize) -> Self {
+    let progress = Self::default();
+    progress.reset(total);
+    progress
+  }
+
+  /// Restarts the count, out of a new `total`
+  pub fn reset(&self, total: usize) {
+    self.done.store(0, Ordering::Relaxed);
+    self.total.store(total, Ordering::Relaxed);
+  }
+
+  /// Records that `count` more items have finished
+  #[inline]
+  pub fn add(&self, count: usize) {
+    self.done.fetch_add(count, Ordering::Relaxed);
+  }
+
+  pub fn done(&self) -> usize {
+    self.done.load(Ordering::Relaxed)
+  }
+
+  pub fn total(&self) -> usize {
+    self.total.load(Ordering::Relaxed)
+  }
+
+  /// The fraction of items finished, from `0.0` to `1.0`
+  pub fn fraction(&self) -> f64 {
+    match self.total() {
+      0 => 1.0,
+      total => (self.done() as f64 / total as f64).min(1.0),
+    }
+  }
+
+  pub fn is_complete(&self) -> bool {
+    self.done() >= self.total()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{MultiRangeIter, ParIter};
+  use rayon::iter::ParallelIterator;
+
+  #[test]
+  fn counts_in_parallel() {
+    let iter = MultiRangeIter::from([30, 40]);
+    let progress = Progress::new(iter.len());
+    ParIter(iter).for_each(|_| progress.add(1));
+    assert!(progress.is_complete());
+    assert_eq!(progress.fraction(), 1.0);
+    progress.reset(10);
+    assert_eq!(progress.fraction(), 0.0);
+  }
+
+  #[test]
+  fn cancels_clones() {
+    let token = CancelToken::new();
+    let progress = Progress::new(1000);
+    let (worker, count) = (token.clone(), progress.clone());
+    let result = ParIter(MultiRangeIter::from([1000])).try_for_each(|[i]| {
+      if i == 10 {
+        token.cancel();
+      }
+      worker.check()?;
+      count.add(1);
+      Ok(())
+    });
+    assert_eq!(result, Err(Cancelled));
+    assert!(!progress.is_complete());
+  }
+}
@@ -0,0 +1,382 @@
+use crate::MultiDims;
+
+use super::par_iter::{ParIter, Splittable};
+use rayon::iter::IntoParallelIterator;
+use std::{array, marker::PhantomData};
+
+/// A space filling curve, defined by how it recursively\
+/// orders the `2^N` child cubes of each cube it passes through.
+pub trait Curve<const N: usize> {
+  /// The orientation of the curve within a cube
+  type State: Copy;
+  /// The orientation of the curve within the root cube
+  const ROOT: Self::State;
+
+  /// The position of the `w`th child visited, as bit `j` for axis `j`,\
+  /// along with the orientation of the curve within that child.
+  fn child(state: Self::State, w: usize) -> (usize, Self::State);
+}
+
+/// The Morton (Z-order) curve, which interleaves the bits of each axis.
+#[derive(Clone, Copy, Debug)]
+pub struct Morton;
+
+impl<const N: usize> Curve<N> for Morton {
+  type State = ();
+  const ROOT: Self::State = ();
+
+  #[inline]
+  fn child(_: Self::State, w: usize) -> (usize, Self::State) {
+    (w, ())
+  }
+}
+
+/// The Hilbert curve, which only ever steps to neighbouring indexes\
+/// when the dimensions are all the same power of two. Otherwise skipping\
+/// the indexes outside of them can jump between parts of the curve.
+///
+/// This uses the state machine from Hamilton's\
+/// [Compact Hilbert Indices](https://www.cs.dal.ca/sites/default/files/technical_reports/CS-2006-07.pdf),\
+/// where the state is the entry corner and the exit axis of a cube.
+#[derive(Clone, Copy, Debug)]
+pub struct Hilbert;
+
+impl Hilbert {
+  /// Rotates the lowest `n` bits of `x` left by `r`
+  #[inline]
+  fn rotl(x: usize, r: usize, n: usize) -> usize {
+    let r = r % n;
+    let mask = (1 << n) - 1;
+    ((x << r) | (x >> (n - r))) & mask
+  }
+
+  #[inline]
+  fn gray(w: usize) -> usize {
+    w ^ (w >> 1)
+  }
+
+  /// The corner the curve enters the `w`th child from
+  #[inline]
+  fn entry(w: usize) -> usize {
+    if w == 0 {
+      0
+    } else {
+      Self::gray(2 * ((w - 1) / 2))
+    }
+  }
+
+  /// The axis the curve leaves the `w`th child along
+  #[inline]
+  fn direction(w: usize, n: usize) -> usize {
+    if w == 0 {
+      0
+    } else if w % 2 == 0 {
+      (w - 1).trailing_ones() as usize % n
+    } else {
+      w.trailing_ones() as usize % n
+    }
+  }
+}
+
+impl<const N: usize> Curve<N> for Hilbert {
+  /// The `(entry, direction)` of the curve in a cube
+  type State = (usize, usize);
+  const ROOT: Self::State = (0, 0);
+
+  #[inline]
+  fn child((entry, dir): Self::State, w: usize) -> (usize, Self::State) {
+    let bits = Self::rotl(Self::gray(w), dir + 1, N) ^ entry;
+    let entry = entry ^ Self::rotl(Self::entry(w), dir + 1, N);
+    let dir = (dir + Self::direction(w, N) + 1) % N;
+    (bits, (entry, dir))
+  }
+}
+
+/// Iterates over the multi-indexes of `dims` along a space filling curve.
+///
+/// Dimensions that aren't powers of two are handled by following\
+/// the curve over the smallest enclosing power of two cube,\
+/// skipping over any indexes outside of `dims`.
+///
+/// Rather than stepping along the curve, each index is found by\
+/// descending the tree of cubes, counting the indexes in each child.\
+/// This makes indexing, and so splitting, logarithmic in the size,\
+/// no matter how much of the enclosing cube is skipped.
+#[derive(Debug)]
+pub struct CurveIter<const N: usize, C> {
+  pub dims: MultiDims<N>,
+  /// The number of times the enclosing cube is halved
+  levels: u32,
+  /// The remaining positions along the curve, as `start..end`
+  range: (usize, usize),
+  curve: PhantomData<C>,
+}
+
+/// Iterates over the multi-indexes of `dims` in Morton (Z) order
+pub type MortonIter<const N: usize> = CurveIter<N, Morton>;
+/// Iterates over the multi-indexes of `dims` along a Hilbert curve
+pub type HilbertIter<const N: usize> = CurveIter<N, Hilbert>;
+
+// manual impls avoid requiring `C: Clone`
+impl<const N: usize, C> Clone for CurveIter<N, C> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<const N: usize, C> Copy for CurveIter<N, C> {}
+
+impl<const N: usize, C, D: Into<MultiDims<N>>> From<D> for CurveIter<N, C> {
+  fn from(value: D) -> Self {
+    let dims: MultiDims<N> = value.into();
+    let largest = dims.iter().copied().max().unwrap_or(1);
+    Self {
+      dims,
+      levels: largest.next_power_of_two().trailing_zeros(),
+      range: (0, dims.len()),
+      curve: PhantomData,
+    }
+  }
+}
+
+impl<const N: usize, C: Curve<N>> CurveIter<N, C> {
+  pub fn is_empty(&self) -> bool {
+    self.range.0 >= self.range.1
+  }
+
+  /// The number of indexes within `dims` in a cube of side `side`
+  #[inline]
+  fn count(&self, corner: [usize; N], side: usize) -> usize {
+    (0..N)
+      .map(|j| self.dims[j].saturating_sub(corner[j]).min(side))
+      .product()
+  }
+
+  /// The multi-index at position `flat` along the curve.
+  pub fn from_flat(&self, mut flat: usize) -> [usize; N] {
+    let mut corner = [0; N];
+    let mut state = C::ROOT;
+    for level in (0..self.levels).rev() {
+      let side = 1 << level;
+      for w in 0..1 << N {
+        let (bits, next) = C::child(state, w);
+        let child = array::from_fn(|j| corner[j] + (bits >> j & 1) * side);
+        let count = self.count(child, side);
+        if flat < count {
+          (corner, state) = (child, next);
+          break;
+        }
+        flat -= count;
+      }
+    }
+    corner
+  }
+
+  /// The position of multi-index `idx` along the curve.
+  pub fn into_flat(&self, idx: [usize; N]) -> usize {
+    let mut corner = [0; N];
+    let mut state = C::ROOT;
+    let mut flat = 0;
+    for level in (0..self.levels).rev() {
+      let side = 1 << level;
+      for w in 0..1 << N {
+        let (bits, next) = C::child(state, w);
+        let child: [usize; N] = array::from_fn(|j| corner[j] + (bits >> j & 1) * side);
+        if (0..N).all(|j| (idx[j] >> level & 1) == (bits >> j & 1)) {
+          (corner, state) = (child, next);
+          break;
+        }
+        flat += self.count(child, side);
+      }
+    }
+    flat
+  }
+}
+
+// Sequential Iterators
+
+impl<const N: usize, C: Curve<N>> Iterator for CurveIter<N, C> {
+  type Item = [usize; N];
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let (start, end) = self.range;
+    if start >= end {
+      return None;
+    }
+    self.range.0 += 1;
+    Some(self.from_flat(start))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let len = self.range.1.saturating_sub(self.range.0);
+    (len, Some(len))
+  }
+}
+
+impl<const N: usize, C: Curve<N>> ExactSizeIterator for CurveIter<N, C> {}
+
+impl<const N: usize, C: Curve<N>> DoubleEndedIterator for CurveIter<N, C> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    let (start, end) = self.range;
+    if start >= end {
+      return None;
+    }
+    self.range.1 -= 1;
+    Some(self.from_flat(end - 1))
+  }
+}
+
+impl<const N: usize, C: Curve<N>> Splittable for CurveIter<N, C> {
+  fn split_at(self, index: usize) -> (Self, Self) {
+    let (start, end) = self.range;
+    let mid = (start + index).min(end);
+    (
+      Self {
+        range: (start, mid),
+        ..self
+      },
+      Self {
+        range: (mid, end),
+        ..self
+      },
+    )
+  }
+}
+
+impl<const N: usize, C: Curve<N> + Send> IntoParallelIterator for CurveIter<N, C> {
+  type Item = <Self as Iterator>::Item;
+  type Iter = ParIter<Self>;
+  fn into_par_iter(self) -> Self::Iter {
+    ParIter(self)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::MultiRangeIter;
+  use proptest::{array, prelude::*};
+  use rayon::iter::ParallelIterator;
+  use std::fmt::Debug;
+
+  fn curve_iter<const N: usize, C: Debug>(
+    range: impl Strategy<Value = usize>,
+  ) -> impl Strategy<Value = CurveIter<N, C>> {
+    array::uniform(range).prop_map_into()
+  }
+
+  fn idx_dims<const N: usize, C: Curve<N> + Debug>(
+    range: impl Strategy<Value = usize>,
+  ) -> impl Strategy<Value = (usize, CurveIter<N, C>)> {
+    curve_iter(range).prop_flat_map(|iter: CurveIter<N, C>| (0..iter.dims.len(), Just(iter)))
+  }
+
+  fn split_iter_case<const N: usize, C: Curve<N>>(
+    idx: usize,
+    iter: CurveIter<N, C>,
+  ) -> Result<(), TestCaseError> {
+    let (iter0, iter1) = iter.split_at(idx);
+    let len = iter.len();
+    let len0 = iter0.len();
+    prop_assert_eq!(len0, idx);
+    let len1 = iter1.len();
+    prop_assert_eq!(len0 + len1, len);
+    prop_assert!(iter0.chain(iter1).eq(iter));
+    Ok(())
+  }
+
+  fn visits_all_case<const N: usize, C: Curve<N>>(
+    iter: CurveIter<N, C>,
+  ) -> Result<(), TestCaseError> {
+    let mut vec: Vec<_> = iter.collect();
+    prop_assert!(vec.iter().rev().eq(iter.rev().collect::<Vec<_>>().iter()));
+    for (flat, &idx) in vec.iter().enumerate() {
+      prop_assert_eq!(iter.into_flat(idx), flat);
+    }
+    vec.sort_by_key(|&idx| iter.dims.into_flat(idx));
+    prop_assert!(vec.into_iter().eq(MultiRangeIter::from(iter.dims)));
+    Ok(())
+  }
+
+  /// Whether every step along the curve is to a neighbouring index
+  fn is_continuous<const N: usize>(iter: HilbertIter<N>) -> bool {
+    let vec: Vec<_> = iter.collect();
+    vec.windows(2).all(|pair| {
+      let dist: usize = (0..N).map(|j| pair[0][j].abs_diff(pair[1][j])).sum();
+      dist == 1
+    })
+  }
+
+  proptest! {
+    #[test]
+    fn split_iter_morton((idx, iter) in idx_dims::<3, Morton>(1usize..40)) {
+      split_iter_case(idx, iter)?;
+    }
+
+    #[test]
+    fn split_iter_hilbert((idx, iter) in idx_dims::<2, Hilbert>(1usize..100)) {
+      split_iter_case(idx, iter)?;
+    }
+
+    #[test]
+    fn par_collect_morton(iter in curve_iter::<2, Morton>(1usize..20)) {
+      let vec: Vec<_> = iter.collect();
+      let par_vec: Vec<_> = ParIter(iter).collect();
+      prop_assert_eq!(vec, par_vec)
+    }
+
+    #[test]
+    fn par_collect_hilbert(iter in curve_iter::<3, Hilbert>(1usize..12)) {
+      let vec: Vec<_> = iter.collect();
+      let par_vec: Vec<_> = ParIter(iter).collect();
+      prop_assert_eq!(vec, par_vec)
+    }
+
+    #[test]
+    fn visits_all_morton(iter in curve_iter::<3, Morton>(1usize..12)) {
+      visits_all_case(iter)?;
+    }
+
+    #[test]
+    fn visits_all_hilbert(iter in curve_iter::<2, Hilbert>(1usize..30)) {
+      visits_all_case(iter)?;
+    }
+  }
+
+  #[test]
+  fn morton_order() {
+    let order: Vec<_> = MortonIter::from([4, 2]).collect();
+    assert_eq!(
+      order,
+      [
+        [0, 0],
+        [1, 0],
+        [0, 1],
+        [1, 1],
+        [2, 0],
+        [3, 0],
+        [2, 1],
+        [3, 1]
+      ]
+    );
+  }
+
+  #[test]
+  fn empty_dims() {
+    for dims in [[0, 6], [3, 0], [0, 0]] {
+      let iter = HilbertIter::from(dims);
+      assert!(iter.is_empty());
+      assert_eq!(iter.count(), 0);
+      assert_eq!(ParIter(MortonIter::from(dims)).count(), 0);
+    }
+  }
+
+  #[test]
+  fn hilbert_continuous() {
+    for side in [2, 4, 8, 16] {
+      assert!(is_continuous(HilbertIter::from([side; 2])));
+      assert!(is_continuous(HilbertIter::from([side; 3])));
+    }
+    assert!(is_continuous(HilbertIter::from([4; 4])));
+  }
+}
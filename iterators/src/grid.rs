@@ -0,0 +1,445 @@
+use crate::{MultiDims, MultiRangeIter};
+
+use super::par_iter::{ParIter, Splittable};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use std::{
+  ops::{Index, IndexMut},
+  slice,
+};
+
+/// How to look up indexes that fall outside of a `Grid`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdgeMode {
+  /// Use the nearest index on the edge of the grid
+  Clamp,
+  /// Wrap around to the opposite edge of the grid
+  Wrap,
+  /// Treat the outside of the grid as empty, giving `None`
+  Zero,
+}
+
+/// A dense `N` dimensional buffer, such as an image or volume,\
+/// storing a value for every multi-index in `dims`.
+///
+/// Values are stored in the same order as `MultiRangeIter`,\
+/// so axis `0` is contiguous and each row runs along it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid<const N: usize, T> {
+  dims: MultiDims<N>,
+  data: Vec<T>,
+}
+
+// Constructors
+
+impl<const N: usize, T> Grid<N, T> {
+  /// A grid of `dims`, with every value set to `fill`
+  pub fn new(dims: impl Into<MultiDims<N>>, fill: T) -> Self
+  where
+    T: Clone,
+  {
+    let dims: MultiDims<N> = dims.into();
+    Self {
+      dims,
+      data: vec![fill; dims.len()],
+    }
+  }
+
+  /// Wraps `data`, stored in `MultiRangeIter` order, as a grid of `dims`.
+  pub fn from_vec(dims: impl Into<MultiDims<N>>, data: Vec<T>) -> Self {
+    let dims: MultiDims<N> = dims.into();
+    assert_eq!(
+      dims.len(),
+      data.len(),
+      "data should have a value for every index\ndimensions = {dims:?}"
+    );
+    Self { dims, data }
+  }
+
+  /// A grid of `dims`, with each value given by calling `f` on its index
+  pub fn from_fn(dims: impl Into<MultiDims<N>>, f: impl FnMut([usize; N]) -> T) -> Self {
+    let dims: MultiDims<N> = dims.into();
+    let data = match dims.is_empty() {
+      true => vec![],
+      false => MultiRangeIter::from(dims).map(f).collect(),
+    };
+    Self { dims, data }
+  }
+
+  /// `from_fn`, calling `f` on each index in parallel
+  pub fn par_from_fn(
+    dims: impl Into<MultiDims<N>>,
+    f: impl Fn([usize; N]) -> T + Send + Sync,
+  ) -> Self
+  where
+    T: Send,
+  {
+    let dims: MultiDims<N> = dims.into();
+    let data = match dims.is_empty() {
+      true => vec![],
+      false => MultiRangeIter::from(dims).into_par_iter().map(f).collect(),
+    };
+    Self { dims, data }
+  }
+}
+
+// Properties
+
+impl<const N: usize, T> Grid<N, T> {
+  pub fn dims(&self) -> MultiDims<N> {
+    self.dims
+  }
+
+  pub fn len(&self) -> usize {
+    self.data.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.data.is_empty()
+  }
+
+  /// The values in `MultiRangeIter` order
+  pub fn as_slice(&self) -> &[T] {
+    &self.data
+  }
+
+  /// The values in `MultiRangeIter` order
+  pub fn as_mut_slice(&mut self) -> &mut [T] {
+    &mut self.data
+  }
+
+  pub fn into_vec(self) -> Vec<T> {
+    self.data
+  }
+
+  /// Whether `idx` is within the grid
+  pub fn contains(&self, idx: [usize; N]) -> bool {
+    (0..N).all(|i| idx[i] < self.dims[i])
+  }
+
+  pub fn get(&self, idx: [usize; N]) -> Option<&T> {
+    self
+      .contains(idx)
+      .then(|| &self.data[self.dims.into_flat(idx)])
+  }
+
+  pub fn get_mut(&mut self, idx: [usize; N]) -> Option<&mut T> {
+    let flat = self.dims.into_flat(idx);
+    self.contains(idx).then(|| &mut self.data[flat])
+  }
+
+  /// The value `offset` away from `idx`, handling\
+  /// indexes outside of the grid according to `edge`.\
+  /// Empty grids have no values to clamp or wrap to, so give `None`.
+  pub fn neighbour(&self, idx: [usize; N], offset: [isize; N], edge: EdgeMode) -> Option<&T> {
+    if self.dims.is_empty() {
+      return None;
+    }
+    let mut moved = [0; N];
+    for i in 0..N {
+      let dim = self.dims[i] as isize;
+      let pos = idx[i] as isize + offset[i];
+      moved[i] = match edge {
+        EdgeMode::Clamp => pos.clamp(0, dim - 1),
+        EdgeMode::Wrap => pos.rem_euclid(dim),
+        EdgeMode::Zero if (0..dim).contains(&pos) => pos,
+        EdgeMode::Zero => return None,
+      } as usize;
+    }
+    self.get(moved)
+  }
+}
+
+// Views
+
+impl<const N: usize, T> Grid<N, T> {
+  /// The rows running along axis `0`, in `MultiRangeIter` order
+  pub fn rows(&self) -> slice::ChunksExact<'_, T> {
+    self.data.chunks_exact(self.dims[0].max(1))
+  }
+
+  /// The rows running along axis `0`, in `MultiRangeIter` order
+  pub fn rows_mut(&mut self) -> slice::ChunksExactMut<'_, T> {
+    self.data.chunks_exact_mut(self.dims[0].max(1))
+  }
+
+  /// The `i`th slice across the last axis,\
+  /// i.e. all the values with `idx[N - 1] == i`.
+  pub fn slice(&self, i: usize) -> &[T] {
+    let len = self.len() / self.dims[N - 1].max(1);
+    &self.data[i * len..(i + 1) * len]
+  }
+
+  /// The `i`th slice across the last axis,\
+  /// i.e. all the values with `idx[N - 1] == i`.
+  pub fn slice_mut(&mut self, i: usize) -> &mut [T] {
+    let len = self.len() / self.dims[N - 1].max(1);
+    &mut self.data[i * len..(i + 1) * len]
+  }
+
+  /// Each value alongside its multi-index
+  pub fn cells(&self) -> Cells<'_, N, T> {
+    Cells {
+      iter: self.indexes(),
+      values: self.data.iter(),
+    }
+  }
+
+  /// Each value alongside its multi-index
+  pub fn cells_mut(&mut self) -> CellsMut<'_, N, T> {
+    CellsMut {
+      iter: self.indexes(),
+      values: self.data.iter_mut(),
+    }
+  }
+
+  /// `cells`, iterated in parallel
+  pub fn par_iter(&self) -> ParIter<Cells<'_, N, T>>
+  where
+    T: Sync,
+  {
+    ParIter(self.cells())
+  }
+
+  /// `cells_mut`, iterated in parallel
+  pub fn par_iter_mut(&mut self) -> ParIter<CellsMut<'_, N, T>>
+  where
+    T: Send,
+  {
+    ParIter(self.cells_mut())
+  }
+
+  /// The multi-indexes of the grid, even when it's empty
+  fn indexes(&self) -> MultiRangeIter<N> {
+    match self.dims.is_empty() {
+      true => MultiRangeIter {
+        dims: self.dims,
+        range: None,
+      },
+      false => self.dims.into(),
+    }
+  }
+}
+
+// Transformations
+
+impl<const N: usize, T> Grid<N, T> {
+  /// Applies `f` to every value in the grid
+  pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<N, U> {
+    Grid {
+      dims: self.dims,
+      data: self.data.iter().map(f).collect(),
+    }
+  }
+
+  /// Combines the values at each index of two grids with `f`.
+  pub fn zip<U, V>(&self, other: &Grid<N, U>, mut f: impl FnMut(&T, &U) -> V) -> Grid<N, V> {
+    assert_eq!(
+      self.dims, other.dims,
+      "zipped grids should have the same dimensions"
+    );
+    let data = self.data.iter().zip(&other.data);
+    Grid {
+      dims: self.dims,
+      data: data.map(|(lhs, rhs)| f(lhs, rhs)).collect(),
+    }
+  }
+
+  /// `map`, applying `f` in parallel
+  pub fn par_map<U: Send>(&self, f: impl Fn(&T) -> U + Send + Sync) -> Grid<N, U>
+  where
+    T: Sync,
+  {
+    Grid {
+      dims: self.dims,
+      data: ParIter(self.data.iter()).map(f).collect(),
+    }
+  }
+}
+
+impl<const N: usize, T> Index<[usize; N]> for Grid<N, T> {
+  type Output = T;
+  fn index(&self, idx: [usize; N]) -> &Self::Output {
+    self
+      .get(idx)
+      .unwrap_or_else(|| panic!("index {idx:?} should be within {:?}", self.dims))
+  }
+}
+
+impl<const N: usize, T> IndexMut<[usize; N]> for Grid<N, T> {
+  fn index_mut(&mut self, idx: [usize; N]) -> &mut Self::Output {
+    let dims = self.dims;
+    self
+      .get_mut(idx)
+      .unwrap_or_else(|| panic!("index {idx:?} should be within {dims:?}"))
+  }
+}
+
+impl<const N: usize, T> IntoIterator for Grid<N, T> {
+  type Item = T;
+  type IntoIter = std::vec::IntoIter<T>;
+  fn into_iter(self) -> Self::IntoIter {
+    self.data.into_iter()
+  }
+}
+
+/// Iterates over the values of a `Grid` alongside their multi-indexes
+#[derive(Clone, Debug)]
+pub struct Cells<'a, const N: usize, T> {
+  iter: MultiRangeIter<N>,
+  values: slice::Iter<'a, T>,
+}
+
+impl<'a, const N: usize, T> Iterator for Cells<'a, N, T> {
+  type Item = ([usize; N], &'a T);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    Some((self.iter.next()?, self.values.next()?))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.values.size_hint()
+  }
+}
+
+impl<const N: usize, T> ExactSizeIterator for Cells<'_, N, T> {}
+
+impl<const N: usize, T> DoubleEndedIterator for Cells<'_, N, T> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    Some((self.iter.next_back()?, self.values.next_back()?))
+  }
+}
+
+impl<const N: usize, T> Splittable for Cells<'_, N, T> {
+  fn split_at(self, index: usize) -> (Self, Self) {
+    let (iter0, iter1) = self.iter.split_at(index);
+    let (values0, values1) = self.values.split_at(index);
+    (
+      Self {
+        iter: iter0,
+        values: values0,
+      },
+      Self {
+        iter: iter1,
+        values: values1,
+      },
+    )
+  }
+}
+
+/// Iterates over the values of a `Grid` alongside their multi-indexes
+#[derive(Debug)]
+pub struct CellsMut<'a, const N: usize, T> {
+  iter: MultiRangeIter<N>,
+  values: slice::IterMut<'a, T>,
+}
+
+impl<'a, const N: usize, T> Iterator for CellsMut<'a, N, T> {
+  type Item = ([usize; N], &'a mut T);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    Some((self.iter.next()?, self.values.next()?))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.values.size_hint()
+  }
+}
+
+impl<const N: usize, T> ExactSizeIterator for CellsMut<'_, N, T> {}
+
+impl<const N: usize, T> DoubleEndedIterator for CellsMut<'_, N, T> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    Some((self.iter.next_back()?, self.values.next_back()?))
+  }
+}
+
+impl<const N: usize, T> Splittable for CellsMut<'_, N, T> {
+  fn split_at(self, index: usize) -> (Self, Self) {
+    let (iter0, iter1) = self.iter.split_at(index);
+    let (values0, values1) = self.values.into_slice().split_at_mut(index);
+    (
+      Self {
+        iter: iter0,
+        values: values0.iter_mut(),
+      },
+      Self {
+        iter: iter1,
+        values: values1.iter_mut(),
+      },
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use proptest::{array, prelude::*};
+
+  fn grid<const N: usize>(
+    range: impl Strategy<Value = usize>,
+  ) -> impl Strategy<Value = Grid<N, usize>> {
+    array::uniform(range).prop_map(|dims: [usize; N]| {
+      let dims = MultiDims(dims);
+      Grid::from_fn(dims, |idx| dims.into_flat(idx))
+    })
+  }
+
+  proptest! {
+    #[test]
+    fn par_iter_mut(mut grid in grid::<3>(1usize..10)) {
+      let dims = grid.dims();
+      grid.par_iter_mut().for_each(|(idx, value)| *value += dims.into_flat(idx));
+      let expected: Vec<_> = (0..grid.len()).map(|flat| 2 * flat).collect();
+      prop_assert_eq!(grid.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn par_collect(grid in grid::<2>(1usize..20)) {
+      let vec: Vec<_> = grid.cells().collect();
+      let par_vec: Vec<_> = grid.par_iter().collect();
+      prop_assert_eq!(vec, par_vec);
+      prop_assert_eq!(grid.par_map(|x| x + 1), grid.map(|x| x + 1));
+    }
+
+    #[test]
+    fn index_matches_flat(grid in grid::<3>(1usize..10)) {
+      for (idx, &value) in grid.cells() {
+        prop_assert_eq!(grid[idx], value);
+        prop_assert_eq!(grid.dims().into_flat(idx), value);
+      }
+    }
+  }
+
+  #[test]
+  fn edge_modes() {
+    let grid = Grid::from_fn([3, 2], |[i, j]| 10 * j + i);
+    let corner = [0, 1];
+    assert_eq!(grid.neighbour(corner, [-1, 0], EdgeMode::Clamp), Some(&10));
+    assert_eq!(grid.neighbour(corner, [-1, 0], EdgeMode::Wrap), Some(&12));
+    assert_eq!(grid.neighbour(corner, [-1, 0], EdgeMode::Zero), None);
+    assert_eq!(grid.neighbour(corner, [1, -1], EdgeMode::Zero), Some(&1));
+    assert_eq!(grid.neighbour(corner, [0, 3], EdgeMode::Wrap), Some(&0));
+  }
+
+  #[test]
+  fn views() {
+    let mut grid = Grid::from_fn([3, 2, 2], |[i, j, k]| 100 * k + 10 * j + i);
+    assert_eq!(grid.rows().nth(1), Some([10, 11, 12].as_slice()));
+    assert_eq!(grid.slice(1), [100, 101, 102, 110, 111, 112]);
+    grid.slice_mut(0).fill(0);
+    let sum = grid.zip(&grid.map(|&x| x), |a, b| a + b);
+    assert_eq!(sum.as_slice()[..6], [0; 6]);
+    assert_eq!(sum[[2, 1, 1]], 2 * 112);
+  }
+
+  #[test]
+  fn empty_views() {
+    let mut grid = Grid::new([2, 0], 0);
+    assert_eq!(grid.rows().count(), 0);
+    assert!(grid.slice(0).is_empty());
+    assert!(grid.slice_mut(0).is_empty());
+    for edge in [EdgeMode::Clamp, EdgeMode::Wrap, EdgeMode::Zero] {
+      assert_eq!(grid.neighbour([0, 0], [1, -1], edge), None);
+    }
+  }
+}
@@ -0,0 +1,314 @@
+use crate::MultiDims;
+
+use super::par_iter::{ParIter, Splittable};
+use rayon::iter::IntoParallelIterator;
+use std::array;
+
+/// A regular lattice of indexes, `offset + step * k` for `k` in `dims`
+#[derive(Clone, Copy, Debug)]
+struct Lattice<const N: usize> {
+  step: usize,
+  offset: [usize; N],
+  dims: MultiDims<N>,
+}
+
+impl<const N: usize> Lattice<N> {
+  fn new(step: usize, offset: [usize; N], within: &MultiDims<N>) -> Self {
+    let dims = array::from_fn(|j| within[j].saturating_sub(offset[j]).div_ceil(step));
+    Self {
+      step,
+      offset,
+      dims: MultiDims(dims),
+    }
+  }
+
+  /// The multi-index at position `flat` in the lattice
+  fn at(&self, flat: usize) -> [usize; N] {
+    let idx = self.dims.from_flat(flat);
+    array::from_fn(|j| self.offset[j] + self.step * idx[j])
+  }
+
+  /// The position of multi-index `idx` in the lattice
+  fn position(&self, idx: [usize; N]) -> usize {
+    let idx = array::from_fn(|j| (idx[j] - self.offset[j]) / self.step);
+    self.dims.into_flat(idx)
+  }
+}
+
+/// Iterates over the multi-indexes of `dims` in interlaced passes,\
+/// where each pass halves the stride between visited indexes.
+///
+/// The first pass visits every multiple of the coarsest stride,\
+/// and each later pass with stride `s` visits the multiples of `s`\
+/// that weren't visited by a previous pass. Within a pass, indexes are\
+/// visited lattice by lattice (as in Adam7), each in `MultiRangeIter` order.
+///
+/// After any pass, every index is close to one that's already been visited,\
+/// so the finished indexes can be used for a coarse preview.
+#[derive(Clone, Copy, Debug)]
+pub struct InterlacedIter<const N: usize> {
+  pub dims: MultiDims<N>,
+  /// The coarsest stride is `2^levels`, so there are `levels + 1` passes
+  levels: u32,
+  /// The remaining positions in the interlaced order, as `start..end`
+  range: (usize, usize),
+}
+
+impl<const N: usize, D: Into<MultiDims<N>>> From<D> for InterlacedIter<N> {
+  fn from(value: D) -> Self {
+    let dims: MultiDims<N> = value.into();
+    let largest = dims.iter().copied().max().unwrap_or(1);
+    Self {
+      dims,
+      levels: largest.max(1).ilog2(),
+      range: (0, dims.len()),
+    }
+  }
+}
+
+impl<const N: usize> InterlacedIter<N> {
+  pub fn is_empty(&self) -> bool {
+    self.range.0 >= self.range.1
+  }
+
+  /// The number of passes over `dims`, none if `dims` is empty
+  pub fn passes(&self) -> usize {
+    match self.dims.is_empty() {
+      true => 0,
+      false => self.levels as usize + 1,
+    }
+  }
+
+  /// The stride between the indexes visited in `pass`
+  pub fn stride(&self, pass: usize) -> usize {
+    1 << (self.levels as usize - pass)
+  }
+
+  /// The lattices visited in `pass`, in order
+  fn lattices(&self, pass: usize) -> impl Iterator<Item = Lattice<N>> + '_ {
+    let stride = self.stride(pass);
+    // the first pass has no coarser pass to skip over
+    let (step, offsets) = match pass {
+      0 => (stride, 0..1),
+      _ => (2 * stride, 1..1 << N),
+    };
+    offsets.map(move |bits: usize| {
+      let offset = array::from_fn(|j| (bits >> j & 1) * stride);
+      Lattice::new(step, offset, &self.dims)
+    })
+  }
+
+  /// The number of indexes visited in `pass`
+  fn pass_len(&self, pass: usize) -> usize {
+    self.lattices(pass).map(|lattice| lattice.dims.len()).sum()
+  }
+
+  /// The pass that visits multi-index `idx`
+  pub fn pass_of(&self, idx: [usize; N]) -> usize {
+    let zeros = idx.iter().map(|x| x.trailing_zeros()).min().unwrap_or(0);
+    (self.levels - zeros.min(self.levels)) as usize
+  }
+
+  /// The nearest multi-index to `idx` visited in passes up to `pass`.
+  pub fn nearest_visited(&self, pass: usize, idx: [usize; N]) -> [usize; N] {
+    let stride = self.stride(pass);
+    array::from_fn(|j| {
+      let nearest = (idx[j] + stride / 2) / stride * stride;
+      if nearest < self.dims[j] {
+        nearest
+      } else {
+        idx[j] / stride * stride
+      }
+    })
+  }
+
+  /// Restricts the iterator to the remaining indexes in `pass`.
+  pub fn pass(self, pass: usize) -> Self {
+    let start: usize = (0..pass).map(|p| self.pass_len(p)).sum();
+    let end = start + self.pass_len(pass);
+    Self {
+      range: (start.max(self.range.0), end.min(self.range.1)),
+      ..self
+    }
+  }
+
+  /// The multi-index at position `flat` in the interlaced order.
+  pub fn from_flat(&self, mut flat: usize) -> [usize; N] {
+    for pass in 0..self.passes() {
+      for lattice in self.lattices(pass) {
+        let len = lattice.dims.len();
+        if flat < len {
+          return lattice.at(flat);
+        }
+        flat -= len;
+      }
+    }
+    panic!("index should be within the dimensions {:?}", self.dims)
+  }
+
+  /// The position of multi-index `idx` in the interlaced order.
+  pub fn into_flat(&self, idx: [usize; N]) -> usize {
+    let pass = self.pass_of(idx);
+    let mut flat: usize = (0..pass).map(|p| self.pass_len(p)).sum();
+    for lattice in self.lattices(pass) {
+      if (0..N).all(|j| idx[j] % lattice.step == lattice.offset[j]) {
+        return flat + lattice.position(idx);
+      }
+      flat += lattice.dims.len();
+    }
+    unreachable!("every index should be in a lattice of its pass")
+  }
+}
+
+// Sequential Iterators
+
+impl<const N: usize> Iterator for InterlacedIter<N> {
+  type Item = [usize; N];
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let (start, end) = self.range;
+    if start >= end {
+      return None;
+    }
+    self.range.0 += 1;
+    Some(self.from_flat(start))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let len = self.range.1.saturating_sub(self.range.0);
+    (len, Some(len))
+  }
+}
+
+impl<const N: usize> ExactSizeIterator for InterlacedIter<N> {}
+
+impl<const N: usize> DoubleEndedIterator for InterlacedIter<N> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    let (start, end) = self.range;
+    if start >= end {
+      return None;
+    }
+    self.range.1 -= 1;
+    Some(self.from_flat(end - 1))
+  }
+}
+
+impl<const N: usize> Splittable for InterlacedIter<N> {
+  fn split_at(self, index: usize) -> (Self, Self) {
+    let (start, end) = self.range;
+    let mid = (start + index).min(end);
+    (
+      Self {
+        range: (start, mid),
+        ..self
+      },
+      Self {
+        range: (mid, end),
+        ..self
+      },
+    )
+  }
+}
+
+impl<const N: usize> IntoParallelIterator for InterlacedIter<N> {
+  type Item = <Self as Iterator>::Item;
+  type Iter = ParIter<Self>;
+  fn into_par_iter(self) -> Self::Iter {
+    ParIter(self)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::MultiRangeIter;
+  use proptest::{array, prelude::*};
+  use rayon::iter::ParallelIterator;
+
+  fn interlaced_iter<const N: usize>(
+    range: impl Strategy<Value = usize>,
+  ) -> impl Strategy<Value = InterlacedIter<N>> {
+    array::uniform(range).prop_map_into()
+  }
+
+  fn idx_dims<const N: usize>(
+    range: impl Strategy<Value = usize>,
+  ) -> impl Strategy<Value = (usize, InterlacedIter<N>)> {
+    interlaced_iter(range).prop_flat_map(|iter: InterlacedIter<N>| (0..iter.dims.len(), Just(iter)))
+  }
+
+  proptest! {
+    #[test]
+    fn split_iter((idx, iter) in idx_dims::<2>(1usize..60)) {
+      let (iter0, iter1) = iter.split_at(idx);
+      let len = iter.len();
+      let len0 = iter0.len();
+      prop_assert_eq!(len0, idx);
+      let len1 = iter1.len();
+      prop_assert_eq!(len0 + len1, len);
+      prop_assert!(iter0.chain(iter1).eq(iter));
+    }
+
+    #[test]
+    fn par_collect(iter in interlaced_iter::<3>(1usize..12)) {
+      let vec: Vec<_> = iter.collect();
+      let par_vec: Vec<_> = ParIter(iter).collect();
+      prop_assert_eq!(vec, par_vec)
+    }
+
+    #[test]
+    fn visits_all(iter in interlaced_iter::<2>(1usize..40)) {
+      let mut vec: Vec<_> = iter.collect();
+      for (flat, &idx) in vec.iter().enumerate() {
+        prop_assert_eq!(iter.into_flat(idx), flat);
+      }
+      vec.sort_by_key(|&idx| iter.dims.into_flat(idx));
+      prop_assert!(vec.into_iter().eq(MultiRangeIter::from(iter.dims)));
+    }
+
+    #[test]
+    fn passes_in_order(iter in interlaced_iter::<2>(1usize..40)) {
+      let passes = (0..iter.passes()).flat_map(|pass| iter.pass(pass));
+      prop_assert!(passes.eq(iter));
+      for pass in 0..iter.passes() {
+        for idx in iter.pass(pass) {
+          prop_assert_eq!(iter.pass_of(idx), pass);
+        }
+      }
+    }
+
+    #[test]
+    fn nearest_is_visited(iter in interlaced_iter::<2>(1usize..40)) {
+      for pass in 0..iter.passes() {
+        let stride = iter.stride(pass);
+        for idx in MultiRangeIter::from(iter.dims) {
+          let nearest = iter.nearest_visited(pass, idx);
+          prop_assert!(iter.pass_of(nearest) <= pass);
+          prop_assert!((0..2).all(|j| nearest[j] < iter.dims[j]));
+          prop_assert!((0..2).all(|j| nearest[j].abs_diff(idx[j]) < stride));
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn empty_dims() {
+    for dims in [[0, 5], [7, 0], [0, 0]] {
+      let iter = InterlacedIter::from(dims);
+      assert_eq!(iter.passes(), 0);
+      assert!(iter.is_empty());
+      assert_eq!(iter.count(), 0);
+      assert_eq!(ParIter(iter).count(), 0);
+    }
+  }
+
+  #[test]
+  fn coarse_to_fine() {
+    let iter = InterlacedIter::from([4, 3]);
+    assert_eq!(iter.passes(), 3);
+    let passes: Vec<Vec<_>> = (0..3).map(|pass| iter.pass(pass).collect()).collect();
+    assert_eq!(passes[0], [[0, 0]]);
+    assert_eq!(passes[1], [[2, 0], [0, 2], [2, 2]]);
+    assert_eq!(passes[2].len(), 8);
+  }
+}
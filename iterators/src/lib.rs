@@ -1,7 +1,21 @@
 /// Iterator utilities for ray marching
+mod adapters;
+pub use adapters::*;
 mod multi_range_iter;
 pub use multi_range_iter::*;
+mod control;
+pub use control::*;
+mod curve_iter;
+pub use curve_iter::*;
+mod grid;
+pub use grid::*;
+mod interlaced_iter;
+pub use interlaced_iter::*;
 mod multi_dims;
 pub use multi_dims::*;
+mod multi_region;
+pub use multi_region::*;
 mod par_iter;
 pub use par_iter::*;
+mod tiled_iter;
+pub use tiled_iter::*;
@@ -50,7 +50,7 @@ impl<const N: usize> MultiDims<N> {
   }
 
   pub fn is_empty(&self) -> bool {
-    self.iter().any(|&dim| dim == 0)
+    self.contains(&0)
   }
 
   /// The minimum possible value within this range
@@ -65,7 +65,7 @@ impl<const N: usize> MultiDims<N> {
 
   /// Increments the provided index
   pub fn increment(&self, mut idx: [usize; N]) -> [usize; N] {
-    for (x, dim) in idx.iter_mut().zip(self.into_iter()) {
+    for (x, dim) in idx.iter_mut().zip(self.0) {
       *x += 1;
       if *x < dim {
         break;
@@ -77,7 +77,7 @@ impl<const N: usize> MultiDims<N> {
 
   /// Decrements the provided index
   pub fn decrement(&self, mut idx: [usize; N]) -> [usize; N] {
-    for (x, dim) in idx.iter_mut().zip(self.into_iter()) {
+    for (x, dim) in idx.iter_mut().zip(self.0) {
       if *x > 0 {
         *x -= 1;
         break;
@@ -108,12 +108,13 @@ impl<const N: usize> MultiDims<N> {
       let (l, dim) = (lhs[i], self[i]);
       let r = rhs[i] + carry;
       if l >= r {
+        carry = 0;
         return l - r;
       }
 
       let diff = r - l;
-      carry = 1 + diff / dim;
-      dim - diff % dim
+      carry = diff.div_ceil(dim);
+      carry * dim - diff
     })
   }
 }
@@ -200,4 +201,13 @@ mod tests {
       prop_assert_eq!(multi_sub, multi0);
     }
   }
+
+  #[test]
+  fn sub_borrows() {
+    let dims = MultiDims::from([3, 3, 3]);
+    // A borrow shouldn't carry past digits that don't need one
+    assert_eq!(dims.sub([0, 2, 1], [1, 0, 0]), [2, 1, 1]);
+    // Borrowing a whole digit shouldn't leave it at `dim`
+    assert_eq!(dims.sub([0, 0, 2], [1, 2, 0]), [2, 0, 1]);
+  }
 }
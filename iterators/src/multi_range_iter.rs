@@ -70,11 +70,21 @@ impl<const N: usize> Splittable for MultiRangeIter<N> {
       // iterator is empty, return empty iterator twice
       return (self, self);
     };
+    let empty = Self {
+      range: None,
+      ..self
+    };
+    if index == 0 {
+      return (empty, self);
+    }
+    if index >= self.len() {
+      return (self, empty);
+    }
     let multi_index = self.dims.from_flat(index);
     let mid = self.dims.add(start, multi_index);
     (
       Self {
-        range: Some((start, mid)),
+        range: Some((start, self.dims.decrement(mid))),
         ..self
       },
       Self {
@@ -142,4 +152,11 @@ mod tests {
   fn split_iter_0() -> Result<(), TestCaseError> {
     split_iter_case(0, [1, 1, 1].into())
   }
+
+  #[test]
+  fn split_iter_mid() {
+    let (iter0, iter1) = MultiRangeIter::from([2, 3]).split_at(3);
+    assert_eq!(iter0.collect::<Vec<_>>(), [[0, 0], [1, 0], [0, 1]]);
+    assert_eq!(iter1.collect::<Vec<_>>(), [[1, 1], [0, 2], [1, 2]]);
+  }
 }
@@ -0,0 +1,287 @@
+use crate::{MultiDims, MultiRangeIter};
+
+use super::par_iter::{ParIter, Splittable};
+use rayon::iter::IntoParallelIterator;
+use std::array;
+
+/// A rectangular sub-view of a parent `MultiDims`,\
+/// covering `offset[i]..offset[i] + extent[i]` on each axis `i`.
+///
+/// With a `stride`, only every `stride[i]`th index along each axis\
+/// is included, starting from the `offset`. This can be used to\
+/// crop, tile or downsample a grid, whilst keeping parent coordinates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MultiRegion<const N: usize> {
+  pub parent: MultiDims<N>,
+  pub offset: [usize; N],
+  pub extent: MultiDims<N>,
+  pub stride: [usize; N],
+}
+
+impl<const N: usize> From<MultiDims<N>> for MultiRegion<N> {
+  fn from(value: MultiDims<N>) -> Self {
+    Self::new(value, [0; N], value)
+  }
+}
+
+impl<const N: usize> MultiRegion<N> {
+  /// The region of `extent` starting at `offset` within `parent`.
+  pub fn new(
+    parent: impl Into<MultiDims<N>>,
+    offset: [usize; N],
+    extent: impl Into<MultiDims<N>>,
+  ) -> Self {
+    let (parent, extent): (MultiDims<N>, MultiDims<N>) = (parent.into(), extent.into());
+    assert!(
+      (0..N).all(|i| offset[i] + extent[i] <= parent[i]),
+      "region should be within its parent\nparent = {parent:?}, offset = {offset:?}, extent = {extent:?}"
+    );
+    Self {
+      parent,
+      offset,
+      extent,
+      stride: [1; N],
+    }
+  }
+
+  /// Only includes every `stride[i]`th index along each axis `i`.
+  pub fn with_stride(self, stride: [usize; N]) -> Self {
+    assert!(
+      !stride.contains(&0),
+      "strides should be non zero\nstride = {stride:?}"
+    );
+    Self { stride, ..self }
+  }
+
+  /// The number of indexes along each axis of the region
+  pub fn dims(&self) -> MultiDims<N> {
+    MultiDims(array::from_fn(|i| self.extent[i].div_ceil(self.stride[i])))
+  }
+
+  pub fn len(&self) -> usize {
+    self.dims().len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.dims().is_empty()
+  }
+
+  /// Whether the parent index `idx` is included in the region
+  pub fn contains(&self, idx: [usize; N]) -> bool {
+    self.to_local(idx).is_some()
+  }
+
+  /// Converts an index within the region to a parent index
+  pub fn to_parent(&self, local: [usize; N]) -> [usize; N] {
+    array::from_fn(|i| self.offset[i] + self.stride[i] * local[i])
+  }
+
+  /// Converts a parent index to an index within the region,\
+  /// if it's included in the region.
+  pub fn to_local(&self, idx: [usize; N]) -> Option<[usize; N]> {
+    (0..N)
+      .all(|i| {
+        let rel = idx[i].wrapping_sub(self.offset[i]);
+        idx[i] >= self.offset[i] && rel < self.extent[i] && rel % self.stride[i] == 0
+      })
+      .then(|| array::from_fn(|i| (idx[i] - self.offset[i]) / self.stride[i]))
+  }
+
+  /// The overlap of two regions of the same parent, ignoring strides.
+  pub fn intersection(&self, other: &Self) -> Self {
+    let offset = array::from_fn(|i| self.offset[i].max(other.offset[i]));
+    let extent = array::from_fn(|i| {
+      let end = (self.offset[i] + self.extent[i]).min(other.offset[i] + other.extent[i]);
+      end.saturating_sub(offset[i])
+    });
+    Self::new(self.parent, offset, extent)
+  }
+
+  /// Splits the region into tiles of at most `tile` indexes along\
+  /// each axis, in the same order as `MultiRangeIter`.
+  ///
+  /// Each tile keeps the region's stride, so together they\
+  /// include exactly the same parent indexes as the region.
+  pub fn tiles(self, tile: impl Into<MultiDims<N>>) -> impl Iterator<Item = Self> {
+    let tile: MultiDims<N> = tile.into();
+    let dims = self.dims();
+    let counts: MultiDims<N> = MultiDims(array::from_fn(|i| dims[i].div_ceil(tile[i])));
+    let tiles = (!self.is_empty() && !tile.is_empty()).then(|| MultiRangeIter::from(counts));
+    tiles.into_iter().flatten().map(move |corner| {
+      let start = array::from_fn(|i| corner[i] * tile[i]);
+      let offset = self.to_parent(start);
+      let extent = array::from_fn(|i| {
+        let count = tile[i].min(dims[i] - start[i]);
+        (count - 1) * self.stride[i] + 1
+      });
+      Self::new(self.parent, offset, extent).with_stride(self.stride)
+    })
+  }
+}
+
+/// Iterates over the parent indexes in a `MultiRegion`,\
+/// in the same order as `MultiRangeIter` over the region.
+#[derive(Clone, Copy, Debug)]
+pub struct MultiRegionIter<const N: usize> {
+  pub region: MultiRegion<N>,
+  iter: MultiRangeIter<N>,
+}
+
+impl<const N: usize> From<MultiRegion<N>> for MultiRegionIter<N> {
+  fn from(region: MultiRegion<N>) -> Self {
+    let dims = region.dims();
+    let iter = if dims.is_empty() {
+      MultiRangeIter { dims, range: None }
+    } else {
+      dims.into()
+    };
+    Self { region, iter }
+  }
+}
+
+impl<const N: usize> IntoIterator for MultiRegion<N> {
+  type Item = [usize; N];
+  type IntoIter = MultiRegionIter<N>;
+  fn into_iter(self) -> Self::IntoIter {
+    self.into()
+  }
+}
+
+impl<const N: usize> MultiRegionIter<N> {
+  pub fn is_empty(&self) -> bool {
+    self.iter.is_empty()
+  }
+}
+
+// Sequential Iterators
+
+impl<const N: usize> Iterator for MultiRegionIter<N> {
+  type Item = [usize; N];
+
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    let local = self.iter.next()?;
+    Some(self.region.to_parent(local))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+}
+
+impl<const N: usize> ExactSizeIterator for MultiRegionIter<N> {}
+
+impl<const N: usize> DoubleEndedIterator for MultiRegionIter<N> {
+  #[inline]
+  fn next_back(&mut self) -> Option<Self::Item> {
+    let local = self.iter.next_back()?;
+    Some(self.region.to_parent(local))
+  }
+}
+
+impl<const N: usize> Splittable for MultiRegionIter<N> {
+  fn split_at(self, index: usize) -> (Self, Self) {
+    let (iter0, iter1) = self.iter.split_at(index);
+    (
+      Self {
+        iter: iter0,
+        ..self
+      },
+      Self {
+        iter: iter1,
+        ..self
+      },
+    )
+  }
+}
+
+impl<const N: usize> IntoParallelIterator for MultiRegionIter<N> {
+  type Item = <Self as Iterator>::Item;
+  type Iter = ParIter<Self>;
+  fn into_par_iter(self) -> Self::Iter {
+    ParIter(self)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use proptest::{array, prelude::*};
+  use rayon::iter::ParallelIterator;
+
+  /// A random strided region within a random parent
+  fn region<const N: usize>(
+    range: impl Strategy<Value = usize>,
+  ) -> impl Strategy<Value = MultiRegion<N>> {
+    array::uniform(range)
+      .prop_flat_map(|parent: [usize; N]| {
+        let offset = parent.map(|dim| 0..dim);
+        (Just(parent), offset)
+      })
+      .prop_flat_map(|(parent, offset)| {
+        let extent = std::array::from_fn::<_, N, _>(|i| 0..=parent[i] - offset[i]);
+        let stride = array::uniform(1usize..4);
+        (Just(parent), Just(offset), extent, stride)
+      })
+      .prop_map(|(parent, offset, extent, stride)| {
+        MultiRegion::new(parent, offset, extent).with_stride(stride)
+      })
+  }
+
+  fn idx_region<const N: usize>(
+    range: impl Strategy<Value = usize>,
+  ) -> impl Strategy<Value = (usize, MultiRegionIter<N>)> {
+    region(range)
+      .prop_flat_map(|region: MultiRegion<N>| (0..=region.len(), Just(region.into_iter())))
+  }
+
+  proptest! {
+    #[test]
+    fn split_iter((idx, iter) in idx_region::<3>(1usize..20)) {
+      let (iter0, iter1) = iter.split_at(idx);
+      prop_assert_eq!(iter0.len(), idx);
+      prop_assert_eq!(iter0.len() + iter1.len(), iter.len());
+      prop_assert!(iter0.chain(iter1).eq(iter));
+    }
+
+    #[test]
+    fn par_collect(region in region::<2>(1usize..20)) {
+      let vec: Vec<_> = region.into_iter().collect();
+      let par_vec: Vec<_> = ParIter(region.into_iter()).collect();
+      prop_assert_eq!(vec, par_vec)
+    }
+
+    #[test]
+    fn visits_contained(region in region::<2>(1usize..20)) {
+      let visited: Vec<_> = region.into_iter().collect();
+      let contained: Vec<_> = MultiRangeIter::from(region.parent)
+        .filter(|&idx| region.contains(idx))
+        .collect();
+      prop_assert_eq!(visited.len(), region.len());
+      let mut sorted = visited.clone();
+      sorted.sort_by_key(|&idx| region.parent.into_flat(idx));
+      prop_assert_eq!(sorted, contained);
+      for (flat, idx) in visited.into_iter().enumerate() {
+        prop_assert_eq!(region.to_local(idx), Some(region.dims().from_flat(flat)));
+      }
+    }
+
+    #[test]
+    fn tiles_cover(region in region::<2>(1usize..20), tile in array::uniform2(1usize..5)) {
+      let mut tiled: Vec<_> = region.tiles(tile).flatten().collect();
+      let mut visited: Vec<_> = region.into_iter().collect();
+      tiled.sort_by_key(|&idx| region.parent.into_flat(idx));
+      visited.sort_by_key(|&idx| region.parent.into_flat(idx));
+      prop_assert_eq!(tiled, visited);
+    }
+  }
+
+  #[test]
+  fn crop_and_stride() {
+    let region = MultiRegion::new([6, 4], [1, 1], [4, 2]).with_stride([2, 1]);
+    let visited: Vec<_> = region.into_iter().collect();
+    assert_eq!(visited, [[1, 1], [3, 1], [1, 2], [3, 2]]);
+    let empty = MultiRegion::new([6, 4], [2, 2], [0, 2]);
+    assert_eq!(empty.into_iter().count(), 0);
+  }
+}
@@ -2,12 +2,22 @@ use rayon::iter::{
   plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer},
   IndexedParallelIterator, ParallelIterator,
 };
-use std::ops::{Deref, DerefMut};
+use std::{
+  ops::{Deref, DerefMut},
+  slice,
+};
 
 pub trait Splittable: Sized {
   fn split_at(self, index: usize) -> (Self, Self);
 }
 
+impl<T> Splittable for slice::Iter<'_, T> {
+  fn split_at(self, index: usize) -> (Self, Self) {
+    let (lhs, rhs) = self.as_slice().split_at(index);
+    (lhs.iter(), rhs.iter())
+  }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct ParIter<I>(pub I);
 
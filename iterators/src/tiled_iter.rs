@@ -0,0 +1,233 @@
+use crate::MultiDims;
+
+use super::par_iter::{ParIter, Splittable};
+use rayon::iter::IntoParallelIterator;
+use std::array;
+
+/// Iterates over the multi-indexes of `dims` tile by tile,\
+/// visiting every index in one tile before moving onto the next.
+///
+/// Tiles are visited in the same order as `MultiRangeIter`,\
+/// as are the indexes within each tile. Tiles on the upper edges\
+/// are cut short when `tile` doesn't evenly divide `dims`.
+///
+/// This keeps neighbouring indexes together when splitting,\
+/// so `ParIter` hands out compact blocks rather than long strips.
+#[derive(Clone, Copy, Debug)]
+pub struct TiledIter<const N: usize> {
+  pub dims: MultiDims<N>,
+  pub tile: MultiDims<N>,
+  /// The remaining positions in the tiled order, as `start..end`
+  range: (usize, usize),
+}
+
+impl<const N: usize> TiledIter<N> {
+  /// Tiles `dims` with tiles of size `tile`, which can't be empty.\
+  /// Empty `dims` give an empty iterator, as with `MultiRangeIter`.
+  pub fn new(dims: impl Into<MultiDims<N>>, tile: impl Into<MultiDims<N>>) -> Self {
+    let (dims, tile): (MultiDims<N>, MultiDims<N>) = (dims.into(), tile.into());
+    assert!(
+      !tile.is_empty(),
+      "all tile dimensions should be non zero\ntile = {tile:?}"
+    );
+    Self {
+      dims,
+      tile,
+      range: (0, dims.len()),
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.range.0 >= self.range.1
+  }
+
+  /// The multi-index at position `flat` in the tiled order.
+  ///
+  /// Working down from the most significant axis, every tile before\
+  /// the current one along an axis holds `tile[i]` times the extent\
+  /// of the block on every other axis. These extents are cut down to\
+  /// the current tile for the axes already visited.
+  pub fn from_flat(&self, mut flat: usize) -> [usize; N] {
+    let mut extents = self.dims.0;
+    let mut corner = [0; N];
+    for i in (0..N).rev() {
+      let others: usize = (0..N).filter(|&j| j != i).map(|j| extents[j]).product();
+      let step = self.tile[i] * others;
+      let tile = flat / step;
+      flat -= tile * step;
+      corner[i] = tile * self.tile[i];
+      extents[i] = self.tile[i].min(self.dims[i] - corner[i]);
+    }
+    let within = MultiDims(extents).from_flat(flat);
+    array::from_fn(|i| corner[i] + within[i])
+  }
+
+  /// The position of multi-index `idx` in the tiled order.
+  pub fn into_flat(&self, idx: [usize; N]) -> usize {
+    let mut extents = self.dims.0;
+    let mut flat = 0;
+    for i in (0..N).rev() {
+      let others: usize = (0..N).filter(|&j| j != i).map(|j| extents[j]).product();
+      let tile = idx[i] / self.tile[i];
+      flat += tile * self.tile[i] * others;
+      let corner = tile * self.tile[i];
+      extents[i] = self.tile[i].min(self.dims[i] - corner);
+    }
+    let within = array::from_fn(|i| idx[i] % self.tile[i]);
+    flat + MultiDims(extents).into_flat(within)
+  }
+}
+
+// Sequential Iterators
+
+impl<const N: usize> Iterator for TiledIter<N> {
+  type Item = [usize; N];
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let (start, end) = self.range;
+    if start >= end {
+      return None;
+    }
+    self.range.0 += 1;
+    Some(self.from_flat(self.range.0 - 1))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let len = self.range.1.saturating_sub(self.range.0);
+    (len, Some(len))
+  }
+}
+
+impl<const N: usize> ExactSizeIterator for TiledIter<N> {}
+
+impl<const N: usize> DoubleEndedIterator for TiledIter<N> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    let (start, end) = self.range;
+    if start >= end {
+      return None;
+    }
+    self.range.1 -= 1;
+    Some(self.from_flat(self.range.1))
+  }
+}
+
+impl<const N: usize> Splittable for TiledIter<N> {
+  fn split_at(self, index: usize) -> (Self, Self) {
+    let (start, end) = self.range;
+    let mid = (start + index).min(end);
+    (
+      Self {
+        range: (start, mid),
+        ..self
+      },
+      Self {
+        range: (mid, end),
+        ..self
+      },
+    )
+  }
+}
+
+impl<const N: usize> IntoParallelIterator for TiledIter<N> {
+  type Item = <Self as Iterator>::Item;
+  type Iter = ParIter<Self>;
+  fn into_par_iter(self) -> Self::Iter {
+    ParIter(self)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::MultiRangeIter;
+  use proptest::{array, prelude::*};
+  use rayon::iter::ParallelIterator;
+
+  fn tiled_iter<const N: usize>(
+    dims: impl Strategy<Value = usize>,
+    tile: impl Strategy<Value = usize>,
+  ) -> impl Strategy<Value = TiledIter<N>> {
+    (array::uniform(dims), array::uniform(tile)).prop_map(|(dims, tile)| TiledIter::new(dims, tile))
+  }
+
+  fn idx_dims<const N: usize>(
+    dims: impl Strategy<Value = usize>,
+    tile: impl Strategy<Value = usize>,
+  ) -> impl Strategy<Value = (usize, TiledIter<N>)> {
+    tiled_iter(dims, tile).prop_flat_map(|iter| (0..iter.dims.len(), Just(iter)))
+  }
+
+  fn split_iter_case<const N: usize>(idx: usize, iter: TiledIter<N>) -> Result<(), TestCaseError> {
+    let (iter0, iter1) = iter.split_at(idx);
+    prop_assert_eq!(iter0.len(), idx);
+    prop_assert_eq!(iter0.len() + iter1.len(), iter.len());
+    prop_assert_eq!(
+      iter0.chain(iter1).collect::<Vec<_>>(),
+      iter.collect::<Vec<_>>()
+    );
+    Ok(())
+  }
+
+  proptest! {
+    #[test]
+    fn split_iter((idx, iter) in idx_dims::<3>(1usize..20, 1usize..8)) {
+      split_iter_case(idx, iter)?;
+    }
+
+    #[test]
+    fn par_collect(iter in tiled_iter::<2>(1usize..20, 1usize..8)) {
+      let vec: Vec<_> = iter.collect();
+      let par_vec: Vec<_> = ParIter(iter).collect();
+      prop_assert_eq!(vec, par_vec)
+    }
+
+    #[test]
+    fn visits_all(iter in tiled_iter::<3>(1usize..12, 1usize..6)) {
+      let mut vec: Vec<_> = iter.collect();
+      let rev: Vec<_> = iter.rev().collect();
+      prop_assert!(vec.iter().eq(rev.iter().rev()));
+      for (flat, &idx) in vec.iter().enumerate() {
+        prop_assert_eq!(iter.into_flat(idx), flat);
+      }
+
+      vec.sort_by_key(|&idx| iter.dims.into_flat(idx));
+      let all: Vec<_> = MultiRangeIter::from(iter.dims).collect();
+      prop_assert_eq!(vec, all);
+    }
+  }
+
+  #[test]
+  fn empty_dims() {
+    for dims in [[0, 4], [5, 0], [0, 0]] {
+      let iter = TiledIter::new(dims, [2, 2]);
+      assert!(iter.is_empty());
+      assert_eq!(iter.count(), 0);
+      assert_eq!(ParIter(iter).count(), 0);
+    }
+  }
+
+  #[test]
+  #[should_panic(expected = "tile dimensions should be non zero")]
+  fn empty_tile() {
+    TiledIter::new([4, 4], [2, 0]);
+  }
+
+  #[test]
+  fn tile_by_tile() {
+    let order: Vec<_> = TiledIter::new([3, 3], [2, 2]).collect();
+    assert_eq!(
+      order,
+      [
+        [0, 0],
+        [1, 0],
+        [0, 1],
+        [1, 1],
+        [2, 0],
+        [2, 1],
+        [0, 2],
+        [1, 2],
+        [2, 2]
+      ]
+    );
+  }
+}
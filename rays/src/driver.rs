@@ -0,0 +1,52 @@
+use marchrs_iterators::{CancelToken, Cancelled, Progress};
+use rayon::iter::*;
+
+/// Renders each ray into the matching pixel of `pixels` in parallel,\
+/// stopping early once `cancel` is cancelled.\
+/// Any indexed items can stand in for the rays, e.g. pixel indexes.
+///
+/// `progress` is reset to the number of pixels and counts each one\
+/// as it's rendered, so it can be polled for a percent-complete bar.\
+/// When cancelled, each pixel holds either its previous value\
+/// or its newly rendered one, so the buffer is always valid to show, e.g.
+/// ```
+/// # use marchrs_iterators::{CancelToken, Cancelled, Progress};
+/// # use marchrs_rays::*;
+/// let camera = PerspectiveCamera3D::default();
+/// let screen = ScreenInfo::new([0.3, 0.2], [30, 20]);
+/// let mut pixels = vec![0.0; 600];
+/// let (cancel, progress) = (CancelToken::new(), Progress::default());
+/// let render = |ray: Ray<3>| ray.dir.normal()[2];
+/// render_into(camera.rays(&screen), &mut pixels, render, &cancel, &progress).unwrap();
+/// assert!(progress.is_complete());
+///
+/// cancel.cancel();
+/// let result = render_into(camera.rays(&screen), &mut pixels, render, &cancel, &progress);
+/// assert_eq!(result, Err(Cancelled));
+/// ```
+pub fn render_into<I, R, T: Send>(
+  rays: R,
+  pixels: &mut [T],
+  render: impl Fn(I) -> T + Send + Sync,
+  cancel: &CancelToken,
+  progress: &Progress,
+) -> Result<(), Cancelled>
+where
+  R: IntoParallelIterator<Item = I, Iter: IndexedParallelIterator>,
+{
+  let rays = rays.into_par_iter();
+  assert_eq!(
+    rays.len(),
+    pixels.len(),
+    "there should be a pixel for every ray"
+  );
+  progress.reset(pixels.len());
+  rays
+    .zip(pixels.par_iter_mut())
+    .try_for_each(|(ray, pixel)| {
+      cancel.check()?;
+      *pixel = render(ray);
+      progress.add(1);
+      Ok(())
+    })
+}
@@ -0,0 +1,206 @@
+use crate::render_into;
+use marchrs_iterators::{CancelToken, Cancelled, Progress};
+use rayon::{
+  iter::{IndexedParallelIterator, IntoParallelIterator},
+  ThreadPool, ThreadPoolBuildError, ThreadPoolBuilder,
+};
+use std::sync::Arc;
+
+/// Where rendering work is run, chosen at runtime.
+///
+/// Every pixel is rendered independently, so all executors\
+/// give identical output, only differing in how the work is shared.
+#[derive(Clone, Debug, Default)]
+pub enum Executor {
+  /// Runs in parallel on the global rayon pool
+  #[default]
+  Global,
+  /// Runs in parallel on the given rayon pool
+  Pool(Arc<ThreadPool>),
+  /// Runs on the calling thread only, for targets without threads (i.e. wasm)
+  Sequential,
+}
+
+impl PartialEq for Executor {
+  fn eq(&self, other: &Self) -> bool {
+    match (self, other) {
+      (Self::Global, Self::Global) | (Self::Sequential, Self::Sequential) => true,
+      (Self::Pool(pool), Self::Pool(other)) => Arc::ptr_eq(pool, other),
+      _ => false,
+    }
+  }
+}
+
+impl Executor {
+  /// Runs in parallel on a new pool of `threads` threads.
+  pub fn threads(threads: usize) -> Result<Self, ThreadPoolBuildError> {
+    let pool = ThreadPoolBuilder::new().num_threads(threads).build()?;
+    Ok(Self::Pool(Arc::new(pool)))
+  }
+
+  /// The number of threads work will be shared between
+  pub fn num_threads(&self) -> usize {
+    match self {
+      Self::Global => rayon::current_num_threads(),
+      Self::Pool(pool) => pool.current_num_threads(),
+      Self::Sequential => 1,
+    }
+  }
+
+  /// `render_into`, run by this executor.
+  ///
+  /// `cancel` and `progress` can be left as `None`\
+  /// for renders that always run to the end, e.g.
+  /// ```
+  /// # use marchrs_rays::*;
+  /// let camera = PerspectiveCamera3D::default();
+  /// let screen = ScreenInfo::new([0.3, 0.2], [30, 20]);
+  /// let render = |ray: Ray<3>| ray.dir.normal()[1];
+  ///
+  /// let executors = [Executor::Global, Executor::threads(2).unwrap(), Executor::Sequential];
+  /// let images = executors.map(|executor| {
+  ///   let mut pixels = vec![0.0; 600];
+  ///   executor.render_into(camera.rays(&screen), &mut pixels, render, None, None).unwrap();
+  ///   pixels
+  /// });
+  /// assert!(images.iter().all(|pixels| pixels == &images[0]));
+  /// ```
+  pub fn render_into<I, R, T: Send>(
+    &self,
+    rays: R,
+    pixels: &mut [T],
+    render: impl Fn(I) -> T + Send + Sync,
+    cancel: Option<&CancelToken>,
+    progress: Option<&Progress>,
+  ) -> Result<(), Cancelled>
+  where
+    R: ExactSizeIterator<Item = I>
+      + IntoParallelIterator<Item = I, Iter: IndexedParallelIterator>
+      + Send,
+  {
+    let (never, untracked) = (CancelToken::new(), Progress::default());
+    let (cancel, progress) = (cancel.unwrap_or(&never), progress.unwrap_or(&untracked));
+    match self {
+      Self::Global => render_into(rays, pixels, render, cancel, progress),
+      Self::Pool(pool) => pool.install(|| render_into(rays, pixels, render, cancel, progress)),
+      Self::Sequential => {
+        // Checked up front, as `render_into` does for the parallel executors
+        assert_eq!(
+          rays.len(),
+          pixels.len(),
+          "there should be a pixel for every ray"
+        );
+        progress.reset(pixels.len());
+        for (pixel, ray) in pixels.iter_mut().zip(rays) {
+          cancel.check()?;
+          *pixel = render(ray);
+          progress.add(1);
+        }
+        Ok(())
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{PerspectiveCamera3D, Ray, RaySource, ScreenInfo};
+  use std::sync::atomic::{AtomicUsize, Ordering};
+
+  const LEN: usize = 600;
+
+  fn screen() -> ScreenInfo<2> {
+    ScreenInfo::new([0.3, 0.2], [30, 20])
+  }
+
+  /// Renders `1.0` per pixel, cancelling from within the `stop`th render
+  fn render_until(executor: &Executor, stop: usize) -> (Result<(), Cancelled>, Vec<f64>, Progress) {
+    let camera = PerspectiveCamera3D::default();
+    let (cancel, progress) = (CancelToken::new(), Progress::default());
+    let started = AtomicUsize::new(0);
+    let mut pixels = vec![0.0; LEN];
+    let render = |_: Ray<3>| {
+      if started.fetch_add(1, Ordering::Relaxed) + 1 == stop {
+        cancel.cancel();
+      }
+      1.0
+    };
+    let result = executor.render_into(
+      camera.rays(&screen()),
+      &mut pixels,
+      render,
+      Some(&cancel),
+      Some(&progress),
+    );
+    (result, pixels, progress)
+  }
+
+  #[test]
+  fn cancels_sequential() {
+    let (result, pixels, progress) = render_until(&Executor::Sequential, 10);
+    assert_eq!(result, Err(Cancelled));
+    assert_eq!(progress.done(), 10);
+    assert_eq!(pixels.iter().filter(|&&pixel| pixel == 1.0).count(), 10);
+  }
+
+  #[test]
+  fn cancels_pool() {
+    let executor = Executor::threads(2).unwrap();
+    let (result, pixels, progress) = render_until(&executor, 10);
+    assert_eq!(result, Err(Cancelled));
+    // Other workers finish the pixel they're on, but don't start any more
+    let rendered = pixels.iter().filter(|&&pixel| pixel == 1.0).count();
+    assert_eq!(progress.done(), rendered);
+    let most = 10 + executor.num_threads() - 1;
+    assert!(
+      (10..=most).contains(&rendered),
+      "rendered {rendered} pixels"
+    );
+  }
+
+  #[test]
+  fn cancelled_before_starting() {
+    let camera = PerspectiveCamera3D::default();
+    let (cancel, progress) = (CancelToken::new(), Progress::default());
+    cancel.cancel();
+    for executor in [Executor::threads(2).unwrap(), Executor::Sequential] {
+      let mut pixels = vec![0.0; LEN];
+      let render = |_: Ray<3>| 1.0;
+      let result = executor.render_into(
+        camera.rays(&screen()),
+        &mut pixels,
+        render,
+        Some(&cancel),
+        Some(&progress),
+      );
+      assert_eq!(result, Err(Cancelled));
+      assert!(pixels.iter().all(|&pixel| pixel == 0.0));
+    }
+  }
+
+  #[test]
+  fn finishes_uncancelled() {
+    for executor in [Executor::threads(2).unwrap(), Executor::Sequential] {
+      let (result, pixels, progress) = render_until(&executor, usize::MAX);
+      assert_eq!(result, Ok(()));
+      assert!(progress.is_complete());
+      assert!(pixels.iter().all(|&pixel| pixel == 1.0));
+    }
+  }
+
+  #[test]
+  fn executors_check_count_before_rendering() {
+    let camera = PerspectiveCamera3D::default();
+    for executor in [Executor::threads(2).unwrap(), Executor::Sequential] {
+      let rendered = AtomicUsize::new(0);
+      let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let mut pixels = vec![0.0; LEN - 1];
+        let render = |_: Ray<3>| rendered.fetch_add(1, Ordering::Relaxed) as f64;
+        let _ = executor.render_into(camera.rays(&screen()), &mut pixels, render, None, None);
+      }));
+      assert!(result.is_err(), "{executor:?} accepted too few pixels");
+      assert_eq!(rendered.into_inner(), 0, "{executor:?} rendered first");
+    }
+  }
+}
@@ -1,6 +1,12 @@
 /// Ray marching algorithms
+mod driver;
+pub use driver::*;
+mod executor;
+pub use executor::*;
 mod marcher;
 pub use marcher::*;
+mod progressive;
+pub use progressive::*;
 mod renderer;
 pub use renderer::*;
 mod sources;
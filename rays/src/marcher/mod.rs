@@ -4,7 +4,7 @@ use crate::sources::Ray;
 use marchrs_sdf::traits::Sdf;
 
 mod sphere_march;
-use marchrs_vectors::Vector;
+use marchrs_vectors::{Scalar, Vector};
 pub use sphere_march::*;
 
 /// An error indicating the ray marcher didn't hit an object
@@ -26,6 +26,6 @@ impl Display for MarchError {
   }
 }
 
-pub trait RayMarcher<const N: usize> {
-  fn march<S: Sdf<N>>(&self, sdf: &S, ray: Ray<N>) -> Result<Vector<N>, MarchError>;
+pub trait RayMarcher<const N: usize, S: Scalar = f64> {
+  fn march<M: Sdf<N, S>>(&self, sdf: &M, ray: Ray<N, S>) -> Result<Vector<N, S>, MarchError>;
 }
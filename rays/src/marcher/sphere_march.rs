@@ -1,6 +1,6 @@
 use super::{MarchError, Ray, RayMarcher};
 use marchrs_sdf::traits::Sdf;
-use marchrs_vectors::Vector;
+use marchrs_vectors::{Scalar, Vector};
 
 #[derive(Clone, Copy, PartialEq)]
 pub struct SphereMarcher {
@@ -19,23 +19,75 @@ impl Default for SphereMarcher {
   }
 }
 
-impl<const N: usize> RayMarcher<N> for SphereMarcher {
-  fn march<S: Sdf<N>>(
+impl<const N: usize, S: Scalar> RayMarcher<N, S> for SphereMarcher {
+  fn march<M: Sdf<N, S>>(
     &self,
-    sdf: &S,
-    Ray { mut pos, dir }: Ray<N>,
-  ) -> Result<Vector<N>, MarchError> {
+    sdf: &M,
+    Ray { mut pos, dir }: Ray<N, S>,
+  ) -> Result<Vector<N, S>, MarchError> {
+    let hit_error = S::from_f64(self.hit_error);
+    let miss_error = S::from_f64(self.miss_error);
     for _ in 0..self.max_iter {
       let dist = sdf.call(pos);
-      if dist > self.miss_error {
-        return Err(MarchError::Diverges(dist));
+      if dist > miss_error {
+        return Err(MarchError::Diverges(dist.to_f64()));
       }
-      if dist < self.hit_error {
+      if dist < hit_error {
         return Ok(pos);
       }
-      pos = pos + dist * dir;
+      pos = pos + dir * dist;
     }
 
     Err(MarchError::MaxIter(self.max_iter))
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{PerspectiveCamera3D, RaySource, ScreenInfo};
+  use marchrs_sdf::items::{Cuboid, Sphere, Torus};
+  use marchrs_sdf::traits::{SdfRotate3D, SdfScale, SdfTranslate, SdfUnion};
+
+  /// Marches a few items in `S`, with each hit in `f64`
+  fn march<S: Scalar>() -> Vec<Result<Vector<3>, MarchError>> {
+    let model = Sphere
+      .scale([0.12; 3])
+      .translate([0.0, -0.07, 0.0])
+      .or(
+        Cuboid::<3>::default()
+          .scale([0.08; 3])
+          .translate([0.05, 0.06, 0.04]),
+      )
+      .or(
+        Torus::<3>::default()
+          .scale([0.2; 3])
+          .rot([1.0, 1.0, 0.0], 1.0),
+      );
+    let screen = ScreenInfo::new(Vector([0.3, 0.2]).cast::<S>(), [60, 40]);
+    let marcher = SphereMarcher {
+      max_iter: 200,
+      ..Default::default()
+    };
+    let rays = PerspectiveCamera3D::<S>::default().rays(&screen);
+    rays
+      .map(|ray| marcher.march(&model, ray).map(Vector::cast))
+      .collect()
+  }
+
+  #[test]
+  fn f32_matches_f64() {
+    let (single, double) = (march::<f32>(), march::<f64>());
+    let hits = double.iter().filter(|hit| hit.is_ok()).count();
+    assert!(hits > 100, "only {hits} rays hit the scene");
+    for (single, double) in single.iter().zip(&double) {
+      match (single, double) {
+        (Ok(single), Ok(double)) => {
+          assert!((*single - *double).mag() < 1e-3, "{single:?} != {double:?}")
+        }
+        (Err(_), Err(_)) => {}
+        _ => panic!("{single:?} != {double:?}"),
+      }
+    }
+  }
+}
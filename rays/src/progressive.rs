@@ -0,0 +1,196 @@
+use crate::Executor;
+use marchrs_iterators::{CancelToken, Cancelled, InterlacedIter, MultiDims};
+
+/// A pixel buffer rendered over several interlaced passes,\
+/// sharpening from a blocky preview down to the full resolution.
+///
+/// After each pass, any pixels that haven't been rendered yet\
+/// are filled from the nearest pixel that has, so the buffer\
+/// can be shown as a complete (if coarse) image at any point, e.g.
+/// ```
+/// # use marchrs_rays::{Executor, Progressive};
+/// let (mut image, executor) = (Progressive::new([4, 3], 0), Executor::Sequential);
+/// let render = |[i, j]: [usize; 2]| i + 10 * j;
+/// image.render_pass(&executor, render, None).unwrap();
+/// assert_eq!(image.pixels, [0; 12]);
+/// while image.render_pass(&executor, render, None).unwrap() {}
+/// assert_eq!(image.pixels, [0, 1, 2, 3, 10, 11, 12, 13, 20, 21, 22, 23]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Progressive<const N: usize, T> {
+  iter: InterlacedIter<N>,
+  /// The number of passes rendered so far
+  done: usize,
+  /// The pixels, in the same order as `MultiRangeIter`
+  pub pixels: Vec<T>,
+}
+
+impl<const N: usize, T: Clone + Send> Progressive<N, T> {
+  /// An unrendered buffer of `res` pixels, all set to `fill`
+  pub fn new(res: impl Into<MultiDims<N>>, fill: T) -> Self {
+    let iter = InterlacedIter::from(res);
+    Self {
+      iter,
+      done: 0,
+      pixels: vec![fill; iter.dims.len()],
+    }
+  }
+
+  /// The resolution of the buffer
+  pub fn res(&self) -> MultiDims<N> {
+    self.iter.dims
+  }
+
+  /// The number of passes needed to render every pixel
+  pub fn passes(&self) -> usize {
+    self.iter.passes()
+  }
+
+  /// The number of passes rendered so far
+  pub fn passes_done(&self) -> usize {
+    self.done
+  }
+
+  /// Whether every pass has been rendered
+  pub fn is_done(&self) -> bool {
+    self.done >= self.passes()
+  }
+
+  /// Renders the next pass with `executor`, calling `render` for each new pixel,\
+  /// then fills the unrendered pixels from their nearest rendered pixel.
+  ///
+  /// Returns whether there are any passes left to render, or `Err(Cancelled)`\
+  /// if `cancel` stops the pass early, in which case it's rendered again\
+  /// from the start by the next call.
+  pub fn render_pass(
+    &mut self,
+    executor: &Executor,
+    render: impl Fn([usize; N]) -> T + Send + Sync,
+    cancel: Option<&CancelToken>,
+  ) -> Result<bool, Cancelled> {
+    if self.is_done() {
+      return Ok(false);
+    }
+    let (dims, pass) = (self.res(), self.done);
+    let indexes = self.iter.pass(pass);
+    let mut rendered = vec![None; indexes.len()];
+    let render = |idx| Some(render(idx));
+    let result = executor.render_into(indexes, &mut rendered, render, cancel, None);
+    // keep any pixels finished before cancelling, as they're still valid
+    for (idx, pixel) in indexes.zip(rendered) {
+      if let Some(pixel) = pixel {
+        self.pixels[dims.into_flat(idx)] = pixel;
+      }
+    }
+    result?;
+
+    self.done += 1;
+    if self.is_done() {
+      return Ok(false);
+    }
+    // only later passes are filled, so this reads only finished pixels
+    for later in pass + 1..self.passes() {
+      for idx in self.iter.pass(later) {
+        let nearest = dims.into_flat(self.iter.nearest_visited(pass, idx));
+        self.pixels[dims.into_flat(idx)] = self.pixels[nearest].clone();
+      }
+    }
+    Ok(true)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use marchrs_iterators::MultiRangeIter;
+  use std::sync::atomic::{AtomicUsize, Ordering};
+
+  const RES: [usize; 2] = [13, 6];
+
+  fn render([i, j]: [usize; 2]) -> usize {
+    i + 100 * j
+  }
+
+  #[test]
+  fn renders_each_pixel_once() {
+    let mut image = Progressive::new(RES, 0);
+    assert_eq!(image.passes(), 4);
+    let calls = AtomicUsize::new(0);
+    let counted = |idx| {
+      calls.fetch_add(1, Ordering::Relaxed);
+      render(idx)
+    };
+    let executor = Executor::threads(2).unwrap();
+    for pass in 1..=image.passes() {
+      assert!(!image.is_done());
+      let more = image.render_pass(&executor, counted, None).unwrap();
+      assert_eq!(more, pass < image.passes());
+      assert_eq!(image.passes_done(), pass);
+    }
+    assert!(image.is_done());
+    assert_eq!(image.render_pass(&executor, counted, None), Ok(false));
+    assert_eq!(calls.into_inner(), RES[0] * RES[1]);
+    assert_eq!(
+      image.pixels,
+      Vec::from_iter(MultiRangeIter::from(RES).map(render))
+    );
+  }
+
+  #[test]
+  fn empty_res() {
+    let mut image = Progressive::new([13, 0], 0);
+    assert_eq!(image.passes(), 0);
+    assert!(image.is_done());
+    assert!(image.pixels.is_empty());
+    let result = image.render_pass(&Executor::Sequential, render, None);
+    assert_eq!(result, Ok(false));
+  }
+
+  #[test]
+  fn fills_from_nearest() {
+    let mut image = Progressive::new(RES, usize::MAX);
+    let iter = InterlacedIter::from(RES);
+    for pass in 0..image.passes() - 1 {
+      image
+        .render_pass(&Executor::Sequential, render, None)
+        .unwrap();
+      for (flat, idx) in MultiRangeIter::from(RES).enumerate() {
+        let expected = render(iter.nearest_visited(pass, idx));
+        assert_eq!(image.pixels[flat], expected, "{idx:?} after pass {pass}");
+      }
+    }
+  }
+
+  #[test]
+  fn executors_match() {
+    let images = [Executor::Sequential, Executor::threads(3).unwrap()].map(|executor| {
+      let mut image = Progressive::new(RES, 0);
+      image.render_pass(&executor, render, None).unwrap();
+      image.render_pass(&executor, render, None).unwrap();
+      image.pixels
+    });
+    assert_eq!(images[0], images[1]);
+  }
+
+  #[test]
+  fn cancels_pass() {
+    let mut image = Progressive::new(RES, 0);
+    let cancel = CancelToken::new();
+    image
+      .render_pass(&Executor::Sequential, render, Some(&cancel))
+      .unwrap();
+    cancel.cancel();
+    let result = image.render_pass(&Executor::Sequential, render, Some(&cancel));
+    assert_eq!(result, Err(Cancelled));
+    assert_eq!(image.passes_done(), 1);
+    // a cancelled pass is rendered again in full
+    while image
+      .render_pass(&Executor::Sequential, render, None)
+      .unwrap()
+    {}
+    assert_eq!(
+      image.pixels,
+      Vec::from_iter(MultiRangeIter::from(RES).map(render))
+    );
+  }
+}
@@ -1,6 +1,6 @@
 use crate::MarchError;
 use marchrs_sdf::traits::SdfFull;
-use marchrs_vectors::Vector;
+use marchrs_vectors::{Scalar, Vector};
 
 mod colour;
 pub use colour::*;
@@ -14,12 +14,12 @@ mod solid;
 pub use solid::*;
 
 /// A method of rendering the information for a single ray hit
-pub trait Renderer<const N: usize, I> {
+pub trait Renderer<const N: usize, I, S: Scalar = f64> {
   /// Renders a given `SDF` item within a scene,\
   /// Returning the render information for the closest item.
-  fn render<S: SdfFull<N, Info = I>>(
+  fn render<M: SdfFull<N, S, Info = I>>(
     &self,
-    model: &S,
-    hit: Result<Vector<N>, MarchError>,
-  ) -> S::Info;
+    model: &M,
+    hit: Result<Vector<N, S>, MarchError>,
+  ) -> M::Info;
 }
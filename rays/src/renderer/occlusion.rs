@@ -1,5 +1,5 @@
 use marchrs_sdf::traits::SdfFull;
-use marchrs_vectors::Vector;
+use marchrs_vectors::{Scalar, Vector};
 
 use crate::MarchError;
 
@@ -10,20 +10,20 @@ pub struct Occlusion {
   pub step_size: f64,
 }
 
-impl<const N: usize> Renderer<N, f64> for Occlusion {
-  fn render<S: SdfFull<N, Info = f64>>(
+impl<const N: usize, S: Scalar> Renderer<N, f64, S> for Occlusion {
+  fn render<M: SdfFull<N, S, Info = f64>>(
     &self,
-    model: &S,
-    hit: Result<Vector<N>, MarchError>,
-  ) -> S::Info {
+    model: &M,
+    hit: Result<Vector<N, S>, MarchError>,
+  ) -> M::Info {
     let Ok(pos) = hit else { return 0.0 };
     let normal = model.grad(pos);
     (1..=self.num_iters)
       .map(|i| i as f64)
       .map(|i| {
-        let step_dist = i * self.step_size;
-        let dist = step_dist - model.call(pos + step_dist * normal);
-        (dist * dist) / i
+        let step_dist = S::from_f64(i * self.step_size);
+        let dist = step_dist - model.call(pos + normal * step_dist);
+        (dist * dist).to_f64() / i
       })
       .sum()
   }
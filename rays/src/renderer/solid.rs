@@ -1,19 +1,19 @@
 use super::Renderer;
 use crate::MarchError;
 use marchrs_sdf::traits::SdfFull;
-use marchrs_vectors::Vector;
+use marchrs_vectors::{Scalar, Vector};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Solid<C> {
   pub background: C,
 }
 
-impl<const N: usize, C: Copy> Renderer<N, C> for Solid<C> {
-  fn render<S: SdfFull<N, Info = C>>(
+impl<const N: usize, C: Copy, S: Scalar> Renderer<N, C, S> for Solid<C> {
+  fn render<M: SdfFull<N, S, Info = C>>(
     &self,
-    model: &S,
-    hit: Result<Vector<N>, MarchError>,
-  ) -> S::Info {
+    model: &M,
+    hit: Result<Vector<N, S>, MarchError>,
+  ) -> M::Info {
     let Ok(pos) = hit else { return self.background };
     model.info(pos)
   }
@@ -1,28 +1,28 @@
 use std::f64::consts::PI;
 
 use super::{screens::ScreenInfo, CameraPlane, Ray, RaySource};
-use marchrs_vectors::{GridIter, Vector};
-use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use marchrs_iterators::{Map, MapFn};
+use marchrs_vectors::{GridIter, Scalar, Vector};
 
 #[derive(Clone, Copy, PartialEq)]
-pub struct PerspectiveCamera3D {
-  pub plane: CameraPlane<3>,
-  pub upwards: Vector<3>,
-  pub fov: f64,
+pub struct PerspectiveCamera3D<S = f64> {
+  pub plane: CameraPlane<3, S>,
+  pub upwards: Vector<3, S>,
+  pub fov: S,
 }
 
-impl Default for PerspectiveCamera3D {
+impl<S: Scalar> Default for PerspectiveCamera3D<S> {
   fn default() -> Self {
     Self {
-      plane: CameraPlane::new([-1.0, 0.0, 0.0], [1.0, 0.0, 0.0]),
-      upwards: Vector([0.0, 0.0, 1.0]),
-      fov: PI / 8.0,
+      plane: CameraPlane::new(Vector::axis(0) * -S::ONE, Vector::axis(0)),
+      upwards: Vector::axis(2),
+      fov: S::from_f64(PI / 8.0),
     }
   }
 }
 
-impl PerspectiveCamera3D {
-  pub fn new(plane: CameraPlane<3>, upwards: Vector<3>, fov: f64) -> Self {
+impl<S: Scalar> PerspectiveCamera3D<S> {
+  pub fn new(plane: CameraPlane<3, S>, upwards: Vector<3, S>, fov: S) -> Self {
     PerspectiveCamera3D {
       plane,
       upwards: upwards.normal(),
@@ -32,71 +32,64 @@ impl PerspectiveCamera3D {
 
   /// Calculates the distance the camera position.\
   /// We choose this such that the FOV angle is over the largest dimension.
-  fn camera_pos(&self, screen: &ScreenInfo<2>) -> Vector<3> {
+  fn camera_pos(&self, screen: &ScreenInfo<2, S>) -> Vector<3, S> {
     let max_dim = screen.dims.max();
-    let cam_dist = max_dim / (2.0 * (self.fov / 2.0).tan());
-    self.plane.pos - cam_dist * self.plane.normal
+    let cam_dist = max_dim / (S::from_f64(2.0) * (self.fov / S::from_f64(2.0)).tan());
+    self.plane.pos - self.plane.normal * cam_dist
   }
 
   /// Calculates a rightwards vector based on the camera normal and up vector.
-  fn right(&self) -> Vector<3> {
+  fn right(&self) -> Vector<3, S> {
     self.plane.normal.cross(self.upwards)
   }
+
+  /// The ray through the pixel at `idx`, matching the ray from `rays`.\
+  /// This allows pixels to be rendered in any order.
+  pub fn ray(&self, screen: &ScreenInfo<2, S>, idx: [usize; 2]) -> Ray<3, S> {
+    let Vector([i, j]) = screen.position(idx);
+    let pos = self.right() * i + self.upwards * j + self.plane.pos;
+    Ray {
+      pos,
+      dir: pos - self.camera_pos(screen),
+    }
+  }
 }
 
-impl RaySource<3> for PerspectiveCamera3D {
-  type Screen = ScreenInfo<2>;
-  type RayIter = Perspective3DIter;
+impl<S: Scalar> RaySource<3, S> for PerspectiveCamera3D<S> {
+  type Screen = ScreenInfo<2, S>;
+  type RayIter = Perspective3DIter<S>;
   fn rays(&self, screen: &Self::Screen) -> Self::RayIter {
-    let iter = screen.positions();
-    Perspective3DIter {
+    let rays = PerspectiveRays {
       camera_pos: self.camera_pos(screen),
       screen_pos: self.plane.pos,
       basis: [self.right(), self.upwards],
-      iter,
-    }
+    };
+    Map::new(screen.positions(), rays)
   }
 }
 
-pub struct Perspective3DIter {
-  camera_pos: Vector<3>,
-  screen_pos: Vector<3>,
-  basis: [Vector<3>; 2],
-  iter: GridIter<2>,
+/// The rays through each position on the screen
+pub type Perspective3DIter<S = f64> = Map<GridIter<2, S>, PerspectiveRays<S>>;
+
+/// Maps positions on the screen to rays from the camera
+#[derive(Clone, Copy)]
+pub struct PerspectiveRays<S = f64> {
+  camera_pos: Vector<3, S>,
+  screen_pos: Vector<3, S>,
+  basis: [Vector<3, S>; 2],
 }
 
-impl Iterator for Perspective3DIter {
-  type Item = Ray<3>;
-  fn next(&mut self) -> Option<Self::Item> {
-    let Vector([i, j]) = self.iter.next()?;
+impl<S: Scalar> MapFn<Vector<2, S>> for PerspectiveRays<S> {
+  type Output = Ray<3, S>;
+  #[inline]
+  fn call(&self, Vector([i, j]): Vector<2, S>) -> Self::Output {
     let [u, v] = self.basis;
 
     // position in world space
-    let pos = i * u + j * v + self.screen_pos;
-    Some(Ray {
+    let pos = u * i + v * j + self.screen_pos;
+    Ray {
       pos,
       dir: pos - self.camera_pos,
-    })
-  }
-}
-
-impl ParallelIterator for Perspective3DIter {
-  type Item = Ray<3>;
-  fn drive_unindexed<C>(self, consumer: C) -> C::Result
-  where
-    C: rayon::iter::plumbing::UnindexedConsumer<Self::Item>,
-  {
-    self
-      .iter
-      .into_par_iter()
-      .map(|Vector([i, j])| {
-        let [u, v] = self.basis;
-        let pos = i * u + j * v + self.screen_pos;
-        Ray {
-          pos,
-          dir: pos - self.camera_pos,
-        }
-      })
-      .drive_unindexed(consumer)
+    }
   }
 }
@@ -1,20 +1,20 @@
-use marchrs_vectors::Vector;
-use rayon::iter::IntoParallelIterator;
+use marchrs_vectors::{Aabb, Scalar, Vector};
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator};
 
 mod cameras;
-pub use cameras::PerspectiveCamera3D;
+pub use cameras::{Perspective3DIter, PerspectiveCamera3D, PerspectiveRays};
 mod screens;
 pub use screens::ScreenInfo;
 
 /// A simple definition of a plane
 #[derive(Clone, Copy, PartialEq)]
-pub struct CameraPlane<const N: usize> {
-  pub pos: Vector<N>,
-  pub normal: Vector<N>,
+pub struct CameraPlane<const N: usize, S = f64> {
+  pub pos: Vector<N, S>,
+  pub normal: Vector<N, S>,
 }
 
-impl<const N: usize> CameraPlane<N> {
-  pub fn new(pos: impl Into<Vector<N>>, normal: impl Into<Vector<N>>) -> Self {
+impl<const N: usize, S: Scalar> CameraPlane<N, S> {
+  pub fn new(pos: impl Into<Vector<N, S>>, normal: impl Into<Vector<N, S>>) -> Self {
     Self {
       pos: pos.into(),
       normal: normal.into().normal(),
@@ -24,14 +24,30 @@ impl<const N: usize> CameraPlane<N> {
 
 /// A Ray to be cast into a scene
 #[derive(Clone, Copy)]
-pub struct Ray<const N: usize> {
-  pub pos: Vector<N>,
-  pub dir: Vector<N>,
+pub struct Ray<const N: usize, S = f64> {
+  pub pos: Vector<N, S>,
+  pub dir: Vector<N, S>,
+}
+
+impl<const N: usize, S: Scalar> Ray<N, S> {
+  /// The position `t` units of `dir` along the ray
+  #[inline]
+  pub fn at(&self, t: S) -> Vector<N, S> {
+    self.pos + self.dir * t
+  }
+
+  /// The entry and exit `t` of the ray through `aabb`, if it hits.\
+  /// See [`Aabb::ray_intersect`] for the details.
+  #[inline]
+  pub fn intersect(&self, aabb: &Aabb<N, S>) -> Option<(S, S)> {
+    aabb.ray_intersect(self.pos, self.dir)
+  }
 }
 
 /// Any object that can emit rays in a scene
-pub trait RaySource<const N: usize> {
+pub trait RaySource<const N: usize, S: Scalar = f64> {
   type Screen;
-  type RayIter: Iterator<Item = Ray<N>> + IntoParallelIterator<Item = Ray<N>>;
+  type RayIter: Iterator<Item = Ray<N, S>>
+    + IntoParallelIterator<Item = Ray<N, S>, Iter: IndexedParallelIterator>;
   fn rays(&self, screen: &Self::Screen) -> Self::RayIter;
 }
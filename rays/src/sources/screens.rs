@@ -1,22 +1,35 @@
-use marchrs_iterators::MultiDims;
-use marchrs_vectors::{GridIter, Vector};
+use marchrs_iterators::{MultiDims, MultiRegion};
+use marchrs_vectors::{GridIter, Scalar, Vector};
 
 #[derive(Clone, Copy, PartialEq)]
-pub struct ScreenInfo<const N: usize> {
-  pub dims: Vector<N>,
+pub struct ScreenInfo<const N: usize, S = f64> {
+  pub dims: Vector<N, S>,
   pub res: MultiDims<N>,
 }
 
-impl<const N: usize> ScreenInfo<N> {
-  pub fn new(dims: impl Into<Vector<N>>, res: impl Into<MultiDims<N>>) -> Self {
+impl<const N: usize, S: Scalar> ScreenInfo<N, S> {
+  pub fn new(dims: impl Into<Vector<N, S>>, res: impl Into<MultiDims<N>>) -> Self {
     Self {
       dims: dims.into(),
       res: res.into(),
     }
   }
 
-  pub fn positions(&self) -> GridIter<N> {
-    let last = self.dims * 0.5;
+  pub fn positions(&self) -> GridIter<N, S> {
+    let last = self.dims * S::from_f64(0.5);
     GridIter::new(-last..=last, self.res)
   }
+
+  /// The positions of only the pixels within `region` of `res`
+  pub fn region_positions(&self, region: MultiRegion<N>) -> GridIter<N, S> {
+    let last = self.dims * S::from_f64(0.5);
+    GridIter::in_region(-last..=last, region)
+  }
+
+  /// The position of the pixel at `idx`, matching `positions`
+  pub fn position(&self, idx: [usize; N]) -> Vector<N, S> {
+    let last = self.dims * S::from_f64(0.5);
+    let steps = self.dims / (Vector::from(self.res.0) - S::ONE);
+    steps * Vector::from(idx) - last
+  }
 }
@@ -1,4 +1,4 @@
-use marchrs_vectors::Vector;
+use marchrs_vectors::{Quaternion, Scalar, Vector};
 
 pub trait Interpolate {
   fn lerp(self, rhs: Self, f: f64) -> Self;
@@ -11,6 +11,14 @@ impl Interpolate for f64 {
   }
 }
 
+impl Interpolate for f32 {
+  #[inline]
+  fn lerp(self, rhs: Self, f: f64) -> Self {
+    let f = f as f32;
+    (1.0 - f) * self + f * rhs
+  }
+}
+
 impl Interpolate for bool {
   #[inline]
   fn lerp(self, rhs: Self, f: f64) -> Self {
@@ -46,9 +54,17 @@ impl<const N: usize, T: Interpolate> Interpolate for [T; N] {
   }
 }
 
-impl<const N: usize> Interpolate for Vector<N> {
+impl<const N: usize, S: Scalar + Interpolate> Interpolate for Vector<N, S> {
   #[inline]
   fn lerp(self, rhs: Self, f: f64) -> Self {
     Self(self.0.lerp(rhs.0, f))
   }
 }
+
+impl Interpolate for Quaternion {
+  /// Uses spherical interpolation, for a constant angular velocity
+  #[inline]
+  fn lerp(self, rhs: Self, f: f64) -> Self {
+    self.slerp(rhs, f)
+  }
+}
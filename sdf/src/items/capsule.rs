@@ -0,0 +1,218 @@
+use crate::traits::{Sdf, SdfBounds, SdfGrad, SdfNoInfo};
+use marchrs_vectors::{Aabb, Scalar, Vector};
+use std::array;
+
+/// A line `Segment(start, end)`, with no thickness.
+///
+/// This is mostly useful for building other items, as it's only\
+/// hit exactly on the line, i.e. `Segment::capsule` or `.round`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Segment<const N: usize> {
+  start: Vector<N>,
+  end: Vector<N>,
+}
+
+impl<const N: usize> Default for Segment<N> {
+  fn default() -> Self {
+    let half = Vector::axis(N - 1) * 0.5;
+    Self::new(-half, half)
+  }
+}
+
+impl<const N: usize> Segment<N> {
+  pub fn new(start: impl Into<Vector<N>>, end: impl Into<Vector<N>>) -> Self {
+    Self {
+      start: start.into(),
+      end: end.into(),
+    }
+  }
+
+  /// The segment, thickened into a `Capsule` of the given `radius`
+  pub fn capsule(self, radius: f64) -> Capsule<N> {
+    Capsule {
+      segment: self,
+      radius: radius.abs(),
+    }
+  }
+
+  /// The offset from the closest point on the segment to `pos`
+  #[inline]
+  pub(super) fn offset<S: Scalar>(&self, pos: Vector<N, S>) -> Vector<N, S> {
+    let start: Vector<N, S> = self.start.cast();
+    let dir = self.end.cast() - start;
+    let rel = pos - start;
+    // branch on the parameters, as intervals can't always compare
+    let len2 = (self.end - self.start).mag2();
+    let t = match len2 > 0.0 {
+      true => (rel.dot(dir) / S::from_f64(len2)).max(S::ZERO).min(S::ONE),
+      false => S::ZERO,
+    };
+    rel - dir * t
+  }
+
+  /// The gradient of the distance, given the `offset` to `pos`.
+  ///
+  /// On the segment every direction away from it is equally steep,\
+  /// so we fall back to the one along the axis it's least aligned with.
+  #[inline]
+  fn offset_grad<S: Scalar>(&self, offset: Vector<N, S>) -> Vector<N, S> {
+    let dir = self.end - self.start;
+    let axis = (0..N)
+      .min_by(|&i, &j| dir[i].abs().total_cmp(&dir[j].abs()))
+      .map_or(Vector::zeros(), Vector::axis);
+    let perp = match dir.mag2() > 0.0 {
+      true => axis - dir * (dir.dot(axis) / dir.mag2()),
+      false => axis,
+    };
+    let fallback: Vector<N, S> = match perp.mag2() > 0.0 {
+      true => perp.normal().cast(),
+      false => axis.cast(),
+    };
+    let dist = offset.mag();
+    let scale = dist.recip();
+    Vector(array::from_fn(|i| {
+      dist.if_positive(offset[i] * scale, fallback[i])
+    }))
+  }
+}
+
+impl<const N: usize, S: Scalar> Sdf<N, S> for Segment<N> {
+  #[inline]
+  fn call(&self, pos: Vector<N, S>) -> S {
+    self.offset(pos).mag()
+  }
+}
+
+impl<const N: usize> SdfNoInfo<N> for Segment<N> {}
+
+impl<const N: usize, S: Scalar> SdfGrad<N, S> for Segment<N> {
+  #[inline]
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
+    let offset = self.offset(pos);
+    (offset.mag(), self.offset_grad(offset))
+  }
+}
+
+impl<const N: usize> SdfBounds<N> for Segment<N> {
+  fn bounds(&self) -> Aabb<N> {
+    Aabb::from_points([self.start, self.end])
+  }
+}
+
+/// A `Capsule(start, end, radius)`:
+/// - all points within `radius` of the segment from `start` to `end`
+///
+/// The default lies along the last axis, within `[-0.5, 0.5]`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Capsule<const N: usize> {
+  segment: Segment<N>,
+  radius: f64,
+}
+
+impl<const N: usize> Default for Capsule<N> {
+  fn default() -> Self {
+    let half = Vector::axis(N - 1) * 0.25;
+    Self::new(-half, half, 0.25)
+  }
+}
+
+impl<const N: usize> Capsule<N> {
+  pub fn new(start: impl Into<Vector<N>>, end: impl Into<Vector<N>>, radius: f64) -> Self {
+    Segment::new(start, end).capsule(radius)
+  }
+}
+
+impl<const N: usize, S: Scalar> Sdf<N, S> for Capsule<N> {
+  #[inline]
+  fn call(&self, pos: Vector<N, S>) -> S {
+    self.segment.offset(pos).mag() - S::from_f64(self.radius)
+  }
+
+  #[inline]
+  fn hits(&self, pos: Vector<N, S>) -> bool {
+    self.segment.offset(pos).mag2() <= S::from_f64(self.radius * self.radius)
+  }
+}
+
+impl<const N: usize> SdfNoInfo<N> for Capsule<N> {}
+
+impl<const N: usize, S: Scalar> SdfGrad<N, S> for Capsule<N> {
+  #[inline]
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
+    let offset = self.segment.offset(pos);
+    let dist = offset.mag() - S::from_f64(self.radius);
+    (dist, self.segment.offset_grad(offset))
+  }
+}
+
+impl<const N: usize> SdfBounds<N> for Capsule<N> {
+  fn bounds(&self) -> Aabb<N> {
+    self.segment.bounds().expand(self.radius)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::items::checks::{check_bounds, check_grad, check_intervals};
+  use marchrs_vectors::GridIter;
+
+  #[test]
+  fn distances() {
+    let capsule = Capsule::<4>::new([0.0; 4], [1.0, 1.0, 0.0, 0.0], 0.5);
+    assert_eq!(capsule.call(Vector([0.0, 0.0, 0.0, 2.0])), 1.5);
+    assert_eq!(capsule.call(Vector([-1.0, 0.0, 0.0, 0.0])), 0.5);
+    assert_eq!(
+      capsule.call(Vector([1.0, 0.0, 0.0, 0.0])),
+      0.5f64.sqrt() - 0.5
+    );
+    let point = Capsule::<2>::new([1.0, 1.0], [1.0, 1.0], 0.5);
+    assert_eq!(point.call(Vector([1.0, 3.0])), 1.5);
+    for pos in GridIter::<4>::new(Vector([-1.0; 4])..=Vector([2.0; 4]), [7; 4]) {
+      assert_eq!(capsule.hits(pos), capsule.call(pos) <= 0.0);
+    }
+  }
+
+  #[test]
+  fn negative_radius() {
+    let capsule = Capsule::<3>::new([0.0; 3], [1.0, 0.0, 0.0], -0.5);
+    assert!(capsule == Capsule::new([0.0; 3], [1.0, 0.0, 0.0], 0.5));
+    assert!(capsule.hits(Vector([0.5, 0.2, 0.0])));
+    assert_eq!(capsule.call(Vector([0.5, 0.0, 1.0])), 0.5);
+  }
+
+  #[test]
+  fn grads_match_dual_and_numeric() {
+    check_grad(&Capsule::<3>::default());
+    check_grad(&Capsule::new([0.1, -0.2, 0.3], [-0.4, 0.5, 0.0], 0.2));
+    check_grad(&Segment::new([0.1, -0.2, 0.3], [-0.4, 0.5, 0.0]));
+  }
+
+  #[test]
+  fn grads_on_segment() {
+    let segment = Segment::<3>::new([0.0; 3], [1.0, 1.0, 0.0]);
+    let capsule = segment.capsule(0.2);
+    for pos in [Vector([0.5, 0.5, 0.0]), Vector([0.0; 3])] {
+      for grad in [segment.grad(pos), capsule.grad(pos)] {
+        assert!((grad.mag() - 1.0).abs() < 1e-12, "{grad:?} at {pos:?}");
+        assert_eq!(grad.dot(Vector([1.0, 1.0, 0.0])), 0.0);
+      }
+    }
+    let point = Segment::<2>::new([1.0, 1.0], [1.0, 1.0]);
+    assert_eq!(point.grad(Vector([1.0, 1.0])), Vector([1.0, 0.0]));
+  }
+
+  #[test]
+  fn bounds_contain_hits() {
+    let capsule = Capsule::<3>::new([0.0, 1.0, 1.0], [1.0, 1.5, 0.5], 0.3);
+    check_bounds(
+      &capsule,
+      GridIter::<3>::new(Vector([-1.0; 3])..=Vector([2.0; 3]), [31; 3]),
+    );
+  }
+
+  #[test]
+  fn intervals_contain_samples() {
+    check_intervals(&Capsule::<3>::new([-0.5, 0.2, 0.0], [0.4, -0.3, 0.6], 0.3));
+  }
+}
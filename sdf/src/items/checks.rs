@@ -0,0 +1,63 @@
+//! Checks shared by the tests of each item
+use crate::traits::{call_dual, Sdf, SdfBounds, SdfGrad, SdfInterval};
+use marchrs_vectors::{Aabb, Dual, GridIter, Vector};
+use std::array;
+
+/// Checks `call_grad` against both dual numbers and the numeric\
+/// default of `SdfGrad`, at points spread around the origin.
+///
+/// The points are offset from the axes and each other,\
+/// so they don't land on the creases of symmetric items.
+pub(crate) fn check_grad<const N: usize>(
+  item: &(impl SdfGrad<N> + Sdf<N, Dual<N, f64>> + Sync),
+) {
+  let numeric = |pos: [f64; N]| item.call(Vector(pos));
+  let (min, max) = ([-0.83, -0.71, -0.97], [0.89, 0.77, 0.91]);
+  let range = Vector(array::from_fn(|i| min[i % 3]))..=Vector(array::from_fn(|i| max[i % 3]));
+  for pos in GridIter::<N>::new(range, [7; N]) {
+    let (dist, grad) = item.call_grad(pos);
+    let (auto_dist, auto_grad) = call_dual(item, pos);
+    assert!((dist - auto_dist).abs() < 1e-12, "{dist} != {auto_dist}");
+    assert!(
+      (grad - auto_grad).mag() < 1e-9,
+      "{grad:?} != {auto_grad:?} at {pos:?}"
+    );
+    let expected = numeric.grad(pos);
+    assert!(
+      (grad - expected).mag() < 1e-5,
+      "{grad:?} != numeric {expected:?} at {pos:?}"
+    );
+  }
+}
+
+/// Checks every point in `points` that `item` hits is within its bounds,\
+/// returning how many it hits.
+pub(crate) fn check_bounds(
+  item: &impl SdfBounds<3>,
+  points: impl IntoIterator<Item = Vector<3>>,
+) -> usize {
+  let bounds = item.bounds().expand(1e-9);
+  let hits = points
+    .into_iter()
+    .filter(|&pos| item.hits(pos))
+    .inspect(|&pos| assert!(bounds.contains(pos), "{pos:?} outside {bounds:?}"))
+    .count();
+  assert!(hits > 0, "no points hit within {bounds:?}");
+  hits
+}
+
+/// Checks `call_interval` contains the distances sampled\
+/// within each of the regions tiling `[-1, 1]^N`.
+pub(crate) fn check_intervals<const N: usize>(item: &impl SdfInterval<N>) {
+  for min in GridIter::<N>::new(Vector([-1.0; N])..=Vector([0.5; N]), [4; N]) {
+    let region = Aabb::new(min, min + 0.5);
+    let bounds = item.call_interval(region);
+    for pos in GridIter::<N>::new(region.min..=region.max, [5; N]) {
+      let dist: f64 = item.call(pos);
+      assert!(
+        bounds.lo - 1e-9 <= dist && dist <= bounds.hi + 1e-9,
+        "{dist} outside {bounds:?} at {pos:?}"
+      );
+    }
+  }
+}
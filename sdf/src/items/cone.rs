@@ -0,0 +1,329 @@
+use crate::traits::{Sdf, SdfBounds, SdfGrad, SdfNoInfo};
+use marchrs_vectors::{Aabb, Scalar, Vector};
+
+/// Splits `pos` into its distance from the last axis and its height along it
+#[inline]
+fn profile<const N: usize, S: Scalar>(pos: Vector<N, S>) -> [S; 2] {
+  let height = pos[N - 1];
+  [(pos.mag2() - height * height).max(S::ZERO).sqrt(), height]
+}
+
+/// Turns a gradient in the profile plane back into a gradient at `pos`
+#[inline]
+fn unprofile<const N: usize, S: Scalar>(pos: Vector<N, S>, [dr, dh]: [S; 2]) -> Vector<N, S> {
+  let mut radial = pos;
+  radial[N - 1] = S::ZERO;
+  let radius = radial.mag();
+  // all directions are equivalent on the axis, so drop the radial part
+  let mut grad = radial * radius.if_positive(dr / radius, S::ZERO);
+  grad[N - 1] = dh;
+  grad
+}
+
+/// The offset from the closest point on the segment `a..b` to `q`
+///
+/// This branches on the ends, which are parameters of the item,\
+/// as intervals can't always compare.
+#[inline]
+fn segment_offset<S: Scalar>(q: [S; 2], a: [f64; 2], b: [f64; 2]) -> [S; 2] {
+  let ab = [b[0] - a[0], b[1] - a[1]];
+  let aq = [q[0] - S::from_f64(a[0]), q[1] - S::from_f64(a[1])];
+  let len2 = ab[0] * ab[0] + ab[1] * ab[1];
+  let ab = ab.map(S::from_f64);
+  let t = match len2 > 0.0 {
+    true => ((aq[0] * ab[0] + aq[1] * ab[1]) / S::from_f64(len2))
+      .max(S::ZERO)
+      .min(S::ONE),
+    false => S::ZERO,
+  };
+  [aq[0] - ab[0] * t, aq[1] - ab[1] * t]
+}
+
+/// A `CappedCone(bottom, top)`, also known as a frustum:
+/// - aligned with the last axis
+/// - radius `bottom` at `-0.5` on the axis
+/// - radius `top` at `0.5` on the axis
+#[derive(Clone, Copy, PartialEq)]
+pub struct CappedCone<const N: usize> {
+  bottom: f64,
+  top: f64,
+}
+
+impl<const N: usize> Default for CappedCone<N> {
+  fn default() -> Self {
+    Self::new(0.5, 0.25)
+  }
+}
+
+impl<const N: usize> CappedCone<N> {
+  pub fn new(bottom: f64, top: f64) -> Self {
+    Self {
+      bottom: bottom.abs(),
+      top: top.abs(),
+    }
+  }
+
+  /// The signed distance in the profile plane, and the offset\
+  /// from the closest point on the surface in that plane.
+  ///
+  /// The sign comes from the edge planes, as the distance to the\
+  /// closest edge is exact inside a convex shape. This avoids\
+  /// branching, so that it stays conservative with intervals.
+  #[inline]
+  fn profile_dist<S: Scalar>(&self, [r, h]: [S; 2]) -> (S, [S; 2]) {
+    let (bottom, top) = (self.bottom, self.top);
+    let offsets = [
+      segment_offset([r, h], [0.0, -0.5], [bottom, -0.5]),
+      segment_offset([r, h], [bottom, -0.5], [top, 0.5]),
+      segment_offset([r, h], [top, 0.5], [0.0, 0.5]),
+    ];
+    let dist2 = offsets.map(|[x, y]| x * x + y * y);
+
+    let (bottom, half) = (S::from_f64(bottom), S::from_f64(0.5));
+    let slope = self.bottom - self.top;
+    let scale = S::from_f64((1.0 + slope * slope).sqrt().recip());
+    let side = ((r - bottom) + (h + half) * S::from_f64(slope)) * scale;
+    let plane = (-h - half).max(h - half).max(side);
+
+    let dist = dist2[0].min(dist2[1]).min(dist2[2]).sqrt() * plane.signum();
+    let closest = (0..3).fold(0, |i, j| if dist2[j] < dist2[i] { j } else { i });
+    (dist, offsets[closest])
+  }
+}
+
+impl<const N: usize, S: Scalar> Sdf<N, S> for CappedCone<N> {
+  #[inline]
+  fn call(&self, pos: Vector<N, S>) -> S {
+    self.profile_dist(profile(pos)).0
+  }
+}
+
+impl<const N: usize> SdfNoInfo<N> for CappedCone<N> {}
+
+impl<const N: usize, S: Scalar> SdfGrad<N, S> for CappedCone<N> {
+  #[inline]
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
+    let (dist, [dr, dh]) = self.profile_dist(profile(pos));
+    // offsets point away from the surface, which is inwards when inside
+    let sign = dist.signum();
+    (dist, unprofile(pos, [dr * sign, dh * sign]).normal())
+  }
+}
+
+impl<const N: usize> SdfBounds<N> for CappedCone<N> {
+  fn bounds(&self) -> Aabb<N> {
+    let mut half = Vector::from(self.bottom.max(self.top));
+    half[N - 1] = 0.5;
+    Aabb::new(-half, half)
+  }
+}
+
+/// A `Cone(radius)`:
+/// - aligned with the last axis
+/// - radius `radius` at `-0.5` on the axis
+/// - a point at `0.5` on the axis
+#[derive(Clone, Copy, PartialEq)]
+pub struct Cone<const N: usize>(CappedCone<N>);
+
+impl<const N: usize> Default for Cone<N> {
+  fn default() -> Self {
+    Self::new(0.5)
+  }
+}
+
+impl<const N: usize> Cone<N> {
+  pub fn new(radius: f64) -> Self {
+    Self(CappedCone::new(radius, 0.0))
+  }
+}
+
+impl<const N: usize, S: Scalar> Sdf<N, S> for Cone<N> {
+  #[inline]
+  fn call(&self, pos: Vector<N, S>) -> S {
+    self.0.call(pos)
+  }
+}
+
+impl<const N: usize> SdfNoInfo<N> for Cone<N> {}
+
+impl<const N: usize, S: Scalar> SdfGrad<N, S> for Cone<N> {
+  #[inline]
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
+    self.0.call_grad(pos)
+  }
+}
+
+impl<const N: usize> SdfBounds<N> for Cone<N> {
+  fn bounds(&self) -> Aabb<N> {
+    self.0.bounds()
+  }
+}
+
+/// A `RoundCone(bottom, top)`, the hull of two spheres:
+/// - aligned with the last axis
+/// - a sphere of radius `bottom`, touching `-0.5` on the axis
+/// - a sphere of radius `top`, touching `0.5` on the axis
+///
+/// When `bottom + top > 1` the spheres overlap and the hull\
+/// reaches past `-0.5..0.5`, and when one sphere contains\
+/// the other this is just the larger sphere.
+#[derive(Clone, Copy, PartialEq)]
+pub struct RoundCone<const N: usize> {
+  bottom: f64,
+  top: f64,
+}
+
+impl<const N: usize> Default for RoundCone<N> {
+  fn default() -> Self {
+    Self::new(0.3, 0.15)
+  }
+}
+
+impl<const N: usize> RoundCone<N> {
+  pub fn new(bottom: f64, top: f64) -> Self {
+    Self {
+      bottom: bottom.abs(),
+      top: top.abs(),
+    }
+  }
+
+  /// The height along the axis of the center of the closest sphere\
+  /// in the hull, and its radius.
+  ///
+  /// The hull is the union of spheres with linearly varying radii\
+  /// between the end spheres, and the distance to these is convex\
+  /// in the height, so clamping the unconstrained minimum is exact.
+  #[inline]
+  fn closest_sphere<S: Scalar>(&self, [r, h]: [S; 2]) -> (S, S) {
+    let (start, end) = (-0.5 + self.bottom, 0.5 - self.top);
+    // the centers swap over when the spheres overlap enough
+    let dir = match end < start {
+      true => -1.0,
+      false => 1.0,
+    };
+    let length = (end - start).abs();
+    // a sphere containing the other is the whole hull, and the\
+    // slope would reach 1, so that the ratio below is infinite
+    if length <= (self.bottom - self.top).abs() {
+      let (center, radius) = match self.bottom < self.top {
+        true => (end, self.top),
+        false => (start, self.bottom),
+      };
+      return (S::from_f64(center), S::from_f64(radius));
+    }
+    let slope = (self.bottom - self.top) / length;
+    // the ratio between the offsets along and across the axis
+    let ratio = S::from_f64(slope / (1.0 - slope * slope).sqrt());
+    let along = ((h - S::from_f64(start)) * S::from_f64(dir) - r * ratio)
+      .max(S::ZERO)
+      .min(S::from_f64(length));
+    (
+      S::from_f64(start) + along * S::from_f64(dir),
+      S::from_f64(self.bottom) - along * S::from_f64(slope),
+    )
+  }
+}
+
+impl<const N: usize, S: Scalar> Sdf<N, S> for RoundCone<N> {
+  #[inline]
+  fn call(&self, pos: Vector<N, S>) -> S {
+    let [r, h] = profile(pos);
+    let (center, radius) = self.closest_sphere([r, h]);
+    let dh = h - center;
+    (r * r + dh * dh).sqrt() - radius
+  }
+}
+
+impl<const N: usize> SdfNoInfo<N> for RoundCone<N> {}
+
+impl<const N: usize, S: Scalar> SdfGrad<N, S> for RoundCone<N> {
+  #[inline]
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
+    let (center, radius) = self.closest_sphere(profile(pos));
+    let offset = pos - Vector::axis(N - 1) * center;
+    (offset.mag() - radius, offset.normal())
+  }
+}
+
+impl<const N: usize> SdfBounds<N> for RoundCone<N> {
+  fn bounds(&self) -> Aabb<N> {
+    let radius = self.bottom.max(self.top);
+    let (mut min, mut max) = (Vector::from(-radius), Vector::from(radius));
+    // each sphere reaches twice its radius from the end it touches
+    min[N - 1] = (-0.5f64).min(0.5 - 2.0 * self.top);
+    max[N - 1] = 0.5f64.max(-0.5 + 2.0 * self.bottom);
+    Aabb::new(min, max)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::items::checks::{check_bounds, check_grad, check_intervals};
+  use marchrs_vectors::GridIter;
+
+  fn grid() -> GridIter<3> {
+    GridIter::new(Vector([-1.0; 3])..=Vector([1.0; 3]), [21; 3])
+  }
+
+  #[test]
+  fn distances() {
+    let cone = CappedCone::<3>::new(0.5, 0.25);
+    let side = -0.375 / 1.0625f64.sqrt();
+    assert!((cone.call(Vector([0.0, 0.0, 0.0])) - side).abs() < 1e-12);
+    assert_eq!(cone.call(Vector([0.0, 0.0, 1.0])), 0.5);
+    assert_eq!(cone.call(Vector([0.0, 1.0, -0.5])), 0.5);
+    let round = RoundCone::<3>::new(0.3, 0.15);
+    assert!((round.call(Vector([0.0, 0.0, -1.0])) - 0.5f64).abs() < 1e-12);
+    assert!((round.call(Vector([0.0, 0.0, 1.0])) - 0.5f64).abs() < 1e-12);
+    assert!((round.call(Vector([0.0, 0.0, -0.2])) + 0.3f64).abs() < 1e-12);
+  }
+
+  #[test]
+  fn overlapping_spheres() {
+    // The top sphere's centre is below the bottom one's
+    let round = RoundCone::<3>::new(0.6, 0.7);
+    assert!((round.call(Vector([0.0, 0.0, 1.0])) - 0.3f64).abs() < 1e-12);
+    assert!((round.call(Vector([0.0, 0.0, -1.0])) - 0.1f64).abs() < 1e-12);
+    // The bottom sphere contains the top one
+    let round = RoundCone::<3>::new(0.8, 0.1);
+    assert!((round.call(Vector([0.0, 0.0, 1.0])) + 0.1f64).abs() < 1e-12);
+    assert!((round.call(Vector([1.0, 0.0, 0.3])) - 0.2f64).abs() < 1e-12);
+    assert_eq!(
+      round.bounds(),
+      Aabb::new([-0.8, -0.8, -0.5], [0.8, 0.8, 0.5 + 0.6])
+    );
+  }
+
+  #[test]
+  fn grads_match_dual_and_numeric() {
+    check_grad(&Cone::<3>::default());
+    check_grad(&CappedCone::<3>::default());
+    check_grad(&CappedCone::<3>::new(0.1, 0.4));
+    check_grad(&RoundCone::<3>::default());
+    check_grad(&RoundCone::<3>::new(0.1, 0.4));
+    check_grad(&RoundCone::<3>::new(0.6, 0.7));
+    check_grad(&RoundCone::<3>::new(0.8, 0.1));
+  }
+
+  #[test]
+  fn bounds_contain_hits() {
+    let cones = [
+      RoundCone::<3>::new(0.5, 0.2),
+      RoundCone::new(0.6, 0.7),
+      RoundCone::new(0.8, 0.1),
+      RoundCone::new(0.1, 0.9),
+    ];
+    for cone in cones {
+      check_bounds(&cone, grid().map(|pos| pos * 1.5));
+    }
+    check_bounds(&CappedCone::<3>::new(0.2, 0.6), grid());
+  }
+
+  #[test]
+  fn intervals_contain_samples() {
+    check_intervals(&CappedCone::<3>::new(0.6, 0.1));
+    check_intervals(&RoundCone::<3>::new(0.4, 0.2));
+    check_intervals(&RoundCone::<3>::new(0.6, 0.7));
+  }
+}
@@ -1,7 +1,6 @@
 use super::Middle;
-use crate::traits::{Sdf, SdfGrad, SdfNoInfo};
-use core::f64;
-use marchrs_vectors::Vector;
+use crate::traits::{call_dual, Sdf, SdfBounds, SdfGrad, SdfNoInfo};
+use marchrs_vectors::{Aabb, Scalar, Vector};
 use std::array;
 
 /// A simple Cube:
@@ -16,23 +15,34 @@ impl<const N: usize> Default for Cube<N> {
   }
 }
 
-impl<const N: usize> Sdf<N> for Cube<N> {
+impl<const N: usize, S: Scalar> Sdf<N, S> for Cube<N> {
   #[inline]
-  fn call(&self, pos: Vector<N>) -> f64 {
+  fn call(&self, pos: Vector<N, S>) -> S {
     self
       .0
       .iter()
       .map(|mid| mid.call(pos))
-      .reduce(f64::max)
-      .unwrap_or(f64::MIN)
+      .reduce(S::max)
+      .unwrap_or(S::MIN)
   }
 
   #[inline]
-  fn hits(&self, pos: Vector<N>) -> bool {
-    self.0.iter().all(|mid| mid.call(pos) <= 0.0)
+  fn hits(&self, pos: Vector<N, S>) -> bool {
+    self.0.iter().all(|mid| mid.call(pos) <= S::ZERO)
   }
 }
 
 impl<const N: usize> SdfNoInfo<N> for Cube<N> {}
 
-impl<const N: usize> SdfGrad<N> for Cube<N> {}
+impl<const N: usize, S: Scalar> SdfGrad<N, S> for Cube<N> {
+  #[inline]
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
+    call_dual(self, pos)
+  }
+}
+
+impl<const N: usize> SdfBounds<N> for Cube<N> {
+  fn bounds(&self) -> Aabb<N> {
+    Aabb::new(-0.5, 0.5)
+  }
+}
@@ -1,6 +1,5 @@
-use crate::traits::{Sdf, SdfGrad, SdfNoInfo};
-use core::f64;
-use marchrs_vectors::Vector;
+use crate::traits::{call_dual, Sdf, SdfBounds, SdfGrad, SdfNoInfo};
+use marchrs_vectors::{Aabb, Scalar, Vector};
 
 /// A simple Cube:
 /// - centered at `(0, 0, ...)`
@@ -14,19 +13,48 @@ impl<const N: usize> Default for Cuboid<N> {
   }
 }
 
-impl<const N: usize> Sdf<N> for Cuboid<N> {
+impl<const N: usize, S: Scalar> Sdf<N, S> for Cuboid<N> {
   #[inline]
-  fn call(&self, pos: Vector<N>) -> f64 {
-    let offset = pos.abs() - self.0 * 0.5;
-    offset.el_max(0.0).mag() + offset.max().min(0.0)
+  fn call(&self, pos: Vector<N, S>) -> S {
+    let offset = pos.abs() - self.0.cast() * S::from_f64(0.5);
+    offset.el_max(S::ZERO).mag() + offset.max().min(S::ZERO)
   }
 
   #[inline]
-  fn hits(&self, pos: Vector<N>) -> bool {
-    self.0.iter().zip(pos).all(|(dim, x)| x <= dim * 0.5)
+  fn hits(&self, pos: Vector<N, S>) -> bool {
+    self
+      .0
+      .iter()
+      .zip(pos)
+      .all(|(&dim, x)| x.abs() <= S::from_f64(dim * 0.5))
   }
 }
 
 impl<const N: usize> SdfNoInfo<N> for Cuboid<N> {}
 
-impl<const N: usize> SdfGrad<N> for Cuboid<N> {}
+impl<const N: usize, S: Scalar> SdfGrad<N, S> for Cuboid<N> {
+  #[inline]
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
+    call_dual(self, pos)
+  }
+}
+
+impl<const N: usize> SdfBounds<N> for Cuboid<N> {
+  fn bounds(&self) -> Aabb<N> {
+    let half = self.0.abs() * 0.5;
+    Aabb::new(-half, half)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn hits_both_sides() {
+    let cuboid = Cuboid::<3>::default();
+    assert!(cuboid.hits(Vector([-0.4, 0.2, -0.3])));
+    assert!(!cuboid.hits(Vector([-0.6, 0.0, 0.0])));
+    assert!(!cuboid.hits(Vector([0.0, -2.0, 0.0])));
+  }
+}
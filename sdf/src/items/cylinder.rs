@@ -1,5 +1,6 @@
-use crate::traits::{Sdf, SdfNoInfo};
-use marchrs_vectors::Vector;
+use crate::traits::{call_dual, Sdf, SdfBounds, SdfGrad, SdfNoInfo};
+use marchrs_vectors::{Aabb, Scalar, Vector};
+use std::array;
 
 /// An infinite `Cylinder(axis)`
 /// - aligned with `axis`
@@ -15,19 +16,27 @@ impl<const N: usize> Default for Cylinder<N> {
 
 impl<const N: usize> Cylinder<N> {
   pub fn new(axis: impl Into<Vector<N>>) -> Self {
-    Self(axis.into())
+    Self(axis.into().normal())
   }
 }
 
-impl<const N: usize> Sdf<N> for Cylinder<N> {
+impl<const N: usize, S: Scalar> Sdf<N, S> for Cylinder<N> {
   #[inline]
-  fn call(&self, pos: Vector<N>) -> f64 {
-    (pos - (pos.dot(self.0)) * self.0).mag() - 0.5
+  fn call(&self, pos: Vector<N, S>) -> S {
+    let axis = self.0.cast();
+    (pos - axis * pos.dot(axis)).mag() - S::from_f64(0.5)
   }
 }
 
 impl<const N: usize> SdfNoInfo<N> for Cylinder<N> {}
 
+impl<const N: usize, S: Scalar> SdfGrad<N, S> for Cylinder<N> {
+  #[inline]
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
+    call_dual(self, pos)
+  }
+}
+
 /// A capped `Cylinder(axis)`
 /// - aligned with `axis`
 /// - radius `0.5`
@@ -43,17 +52,67 @@ impl<const N: usize> Default for CapCylinder<N> {
 
 impl<const N: usize> CapCylinder<N> {
   pub fn new(axis: impl Into<Vector<N>>) -> Self {
-    Self(axis.into())
+    Self(axis.into().normal())
   }
 }
 
-impl<const N: usize> Sdf<N> for CapCylinder<N> {
+impl<const N: usize, S: Scalar> Sdf<N, S> for CapCylinder<N> {
   #[inline]
-  fn call(&self, pos: Vector<N>) -> f64 {
-    let proj = pos.dot(self.0) * self.0;
+  fn call(&self, pos: Vector<N, S>) -> S {
+    let axis = self.0.cast();
+    let proj = axis * pos.dot(axis);
     let perp = pos - proj;
-    proj.mag().max(perp.mag()) - 0.5
+    proj.mag().max(perp.mag()) - S::from_f64(0.5)
   }
 }
 
 impl<const N: usize> SdfNoInfo<N> for CapCylinder<N> {}
+
+impl<const N: usize, S: Scalar> SdfGrad<N, S> for CapCylinder<N> {
+  #[inline]
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
+    call_dual(self, pos)
+  }
+}
+
+/// The cylinder is only bounded on axes perpendicular to its own.
+impl<const N: usize> SdfBounds<N> for Cylinder<N> {
+  fn bounds(&self) -> Aabb<N> {
+    let axis = self.0;
+    let half = Vector(array::from_fn(|i| {
+      if axis[i] == 0.0 {
+        0.5
+      } else {
+        f64::INFINITY
+      }
+    }));
+    Aabb::new(-half, half)
+  }
+}
+
+/// Each axis `i` is bounded by the ends of the cylinder, at `0.5 * axis[i]`,\
+/// plus the extent of the end caps, `0.5 * sqrt(1 - axis[i]^2)`.
+impl<const N: usize> SdfBounds<N> for CapCylinder<N> {
+  fn bounds(&self) -> Aabb<N> {
+    let axis = self.0;
+    let half = Vector(array::from_fn(|i| {
+      0.5 * (axis[i].abs() + (1.0 - axis[i] * axis[i]).max(0.0).sqrt())
+    }));
+    Aabb::new(-half, half)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn scaled_axes_are_normalised() {
+    let pos = Vector([0.3, -0.4, 0.2]);
+    let (unit, scaled) = (Cylinder::new([0.6, 0.0, 0.8]), Cylinder::new([1.5, 0.0, 2.0]));
+    assert!((Sdf::<3>::call(&unit, pos) - scaled.call(pos)).abs() < 1e-12);
+    let (unit, scaled) = (CapCylinder::new([0.6, 0.0, 0.8]), CapCylinder::new([1.5, 0.0, 2.0]));
+    assert!((Sdf::<3>::call(&unit, pos) - scaled.call(pos)).abs() < 1e-12);
+    assert_eq!(unit.bounds(), scaled.bounds());
+  }
+}
@@ -0,0 +1,510 @@
+use super::Triangle;
+use crate::traits::{Sdf, SdfBounds, SdfGrad, SdfNoInfo};
+use marchrs_vectors::{Aabb, Scalar, Vector};
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// The most faces stored in a single leaf of the hierarchy
+const LEAF_SIZE: usize = 4;
+
+/// A closed triangle `Mesh`, with signed distances.
+///
+/// Faces are stored in a bounding volume hierarchy, so each call only\
+/// visits the faces near `pos`. The sign comes from the angle weighted\
+/// pseudo-normal of the closest face, edge or vertex, so is exact for\
+/// closed meshes wound anticlockwise when viewed from outside.
+///
+/// The closest face is found for the `f64` value of each position,\
+/// then the distance to it is found in the scalar type itself,\
+/// so dual numbers give exact gradients. Intervals also visit every\
+/// face that could be closer within their region, see `signed`.
+#[derive(Clone)]
+pub struct Mesh {
+  faces: Vec<Face>,
+  nodes: Vec<Node>,
+}
+
+/// A face, along with the pseudo-normals of its features
+#[derive(Clone, Copy)]
+struct Face {
+  triangle: Triangle<3>,
+  normal: Vector<3>,
+  /// The normals of the edges `ab, bc, ca`
+  edges: [Vector<3>; 3],
+  /// The normals of the vertices `a, b, c`
+  vertices: [Vector<3>; 3],
+}
+
+/// The part of a face that a point is closest to
+#[derive(Clone, Copy)]
+enum Feature {
+  Vertex(usize),
+  Edge(usize),
+  Face,
+}
+
+#[derive(Clone)]
+enum Node {
+  Leaf(Aabb<3>, Range<usize>),
+  Branch(Aabb<3>, [usize; 2]),
+}
+
+impl Mesh {
+  /// Builds a mesh from a triangle soup,\
+  /// joining any vertices at exactly the same position.
+  pub fn new(triangles: impl IntoIterator<Item = [Vector<3>; 3]>) -> Self {
+    let (vertices, faces) = weld(triangles);
+    Self::from_indexed(&vertices, faces)
+  }
+
+  /// Builds a mesh from shared `vertices`, and `faces` indexing into them.
+  ///
+  /// Degenerate faces are skipped, as they have no normal.
+  pub fn from_indexed(vertices: &[Vector<3>], faces: impl IntoIterator<Item = [usize; 3]>) -> Self {
+    let faces: Vec<_> = faces
+      .into_iter()
+      .map(|face| {
+        (
+          face,
+          Triangle::new(vertices[face[0]], vertices[face[1]], vertices[face[2]]),
+        )
+      })
+      .filter(|(_, triangle)| triangle.normal().is_finite())
+      .collect();
+
+    let mut edge_normals = HashMap::new();
+    let mut vertex_normals = vec![Vector::zeros(); vertices.len()];
+    for (face, triangle) in &faces {
+      let normal = triangle.normal();
+      let points = triangle.points();
+      for i in 0..3 {
+        let (j, k) = ((i + 1) % 3, (i + 2) % 3);
+        let key = (face[i].min(face[j]), face[i].max(face[j]));
+        let edge = edge_normals.entry(key).or_insert(Vector::zeros());
+        *edge = *edge + normal;
+        let angle = (points[j] - points[i]).angle_between(points[k] - points[i]);
+        vertex_normals[face[i]] = vertex_normals[face[i]] + normal * angle;
+      }
+    }
+
+    let mut faces: Vec<_> = faces
+      .into_iter()
+      .map(|(face, triangle)| Face {
+        triangle,
+        normal: triangle.normal(),
+        edges: [0, 1, 2].map(|i| {
+          let (a, b) = (face[i], face[(i + 1) % 3]);
+          edge_normals[&(a.min(b), a.max(b))]
+        }),
+        vertices: face.map(|i| vertex_normals[i]),
+      })
+      .collect();
+
+    let mut nodes = vec![];
+    if !faces.is_empty() {
+      build(&mut faces, 0, &mut nodes);
+    }
+    Self { faces, nodes }
+  }
+
+  /// The number of faces in the mesh
+  pub fn len(&self) -> usize {
+    self.faces.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.faces.is_empty()
+  }
+
+  /// The faces of the mesh, in the order stored in the hierarchy
+  pub fn triangles(&self) -> impl Iterator<Item = Triangle<3>> + '_ {
+    self.faces.iter().map(|face| face.triangle)
+  }
+
+  /// Finds the closest face and point on the mesh to `pos`,\
+  /// along with the pseudo-normal at that point.
+  fn closest(&self, pos: Vector<3>) -> Option<(&Face, Vector<3>, Vector<3>)> {
+    let mut best = None;
+    let mut best_dist = f64::INFINITY;
+    let mut stack = match self.nodes.is_empty() {
+      true => vec![],
+      false => vec![0],
+    };
+    while let Some(node) = stack.pop() {
+      if box_dist2(self.nodes[node].bounds(), pos) >= best_dist {
+        continue;
+      }
+      match &self.nodes[node] {
+        Node::Leaf(_, range) => {
+          for face in &self.faces[range.clone()] {
+            let (point, feature) = face.closest(pos);
+            let dist = (pos - point).mag2();
+            if dist < best_dist {
+              best_dist = dist;
+              best = Some((face, point, face.pseudo_normal(feature)));
+            }
+          }
+        }
+        Node::Branch(_, [left, right]) => {
+          // Visit the nearer child first, so the further can be culled
+          let near = |child: &usize| box_dist2(self.nodes[*child].bounds(), pos);
+          match near(left) <= near(right) {
+            true => stack.extend([*right, *left]),
+            false => stack.extend([*left, *right]),
+          }
+        }
+      }
+    }
+    best
+  }
+
+  /// The signed distance at `pos`.
+  ///
+  /// The sign comes from the closest face to the `f64` value of `pos`.\
+  /// Intervals cover a whole region, so every face that could be closer\
+  /// within it is visited too, and the sign only holds throughout\
+  /// when the surface can't cross the region.
+  fn signed<S: Scalar>(&self, pos: Vector<3, S>) -> S {
+    let centre = pos.cast();
+    let Some((face, point, normal)) = self.closest(centre) else {
+      return S::INFINITY;
+    };
+    let sign = match (centre - point).dot(normal) < 0.0 {
+      true => -S::ONE,
+      false => S::ONE,
+    };
+    // Only regions can be closer to faces other than the closest
+    let dist2 = match pos.into_iter().all(S::is_point) {
+      true => (pos - face.closest(pos).0).mag2(),
+      false => self.region_dist2(pos),
+    };
+    let dist = dist2.sqrt();
+    dist.if_positive(dist * sign, -dist * sign)
+  }
+
+  /// The smallest squared distance from `pos` to any face,\
+  /// skipping nodes whose box is certainly further than the closest.
+  ///
+  /// The distance to each face is also bounded below by the distance\
+  /// to its box, as intervals lose precision through the weights.
+  fn region_dist2<S: Scalar>(&self, pos: Vector<3, S>) -> S {
+    let mut best = S::INFINITY;
+    let mut stack = match self.nodes.is_empty() {
+      true => vec![],
+      false => vec![0],
+    };
+    while let Some(node) = stack.pop() {
+      if box_dist2(self.nodes[node].bounds(), pos) >= best {
+        continue;
+      }
+      match &self.nodes[node] {
+        Node::Leaf(_, range) => {
+          for face in &self.faces[range.clone()] {
+            let bounds = Aabb::from_points(face.triangle.points());
+            best = best.min(face.triangle.dist2(pos).max(box_dist2(bounds, pos)));
+          }
+        }
+        Node::Branch(_, children) => stack.extend(children),
+      }
+    }
+    best
+  }
+}
+
+/// Joins the vertices of a triangle soup at exactly the same position,\
+/// returning the shared vertices and faces indexing into them.
+pub(crate) fn weld(
+  triangles: impl IntoIterator<Item = [Vector<3>; 3]>,
+) -> (Vec<Vector<3>>, Vec<[usize; 3]>) {
+  let mut vertices = vec![];
+  let mut indices = HashMap::new();
+  let faces = triangles
+    .into_iter()
+    .map(|triangle| {
+      triangle.map(|vertex| {
+        // `+ 0.0` so that `-0.0` and `0.0` are joined
+        let key = vertex.0.map(|x| (x + 0.0).to_bits());
+        *indices.entry(key).or_insert_with(|| {
+          vertices.push(vertex);
+          vertices.len() - 1
+        })
+      })
+    })
+    .collect();
+  (vertices, faces)
+}
+
+/// Splits `faces` in half along the longest axis of their centres,\
+/// until each leaf holds at most `LEAF_SIZE` faces.
+///
+/// `start` is the index of `faces[0]` within the mesh,\
+/// and the index of the new node is returned.
+fn build(faces: &mut [Face], start: usize, nodes: &mut Vec<Node>) -> usize {
+  let bounds = Aabb::from_points(faces.iter().flat_map(|face| face.triangle.points()));
+  let index = nodes.len();
+  nodes.push(Node::Leaf(bounds, start..start + faces.len()));
+  if faces.len() <= LEAF_SIZE {
+    return index;
+  }
+
+  let size = Aabb::from_points(faces.iter().map(Face::centre)).size();
+  let axis = (0..3).fold(0, |best, i| if size[i] > size[best] { i } else { best });
+  let mid = faces.len() / 2;
+  faces.select_nth_unstable_by(mid, |l, r| l.centre()[axis].total_cmp(&r.centre()[axis]));
+  let (left, right) = faces.split_at_mut(mid);
+  let children = [build(left, start, nodes), build(right, start + mid, nodes)];
+  nodes[index] = Node::Branch(bounds, children);
+  index
+}
+
+/// The squared distance from `pos` to the closest point in `bounds`
+#[inline]
+fn box_dist2<S: Scalar>(bounds: Aabb<3>, pos: Vector<3, S>) -> S {
+  let below = (bounds.min.cast() - pos).el_max(S::ZERO);
+  let above = (pos - bounds.max.cast()).el_max(S::ZERO);
+  (below + above).mag2()
+}
+
+impl Node {
+  fn bounds(&self) -> Aabb<3> {
+    match self {
+      Node::Leaf(bounds, _) | Node::Branch(bounds, _) => *bounds,
+    }
+  }
+}
+
+impl Face {
+  fn centre(&self) -> Vector<3> {
+    let [a, b, c] = self.triangle.points();
+    (a + b + c) * (1.0 / 3.0)
+  }
+
+  fn pseudo_normal(&self, feature: Feature) -> Vector<3> {
+    match feature {
+      Feature::Vertex(i) => self.vertices[i],
+      Feature::Edge(i) => self.edges[i],
+      Feature::Face => self.normal,
+    }
+  }
+
+  /// The closest point on the face to `pos`, and the feature it lies on.
+  ///
+  /// See Ericson's Real-Time Collision Detection, section 5.1.5.
+  fn closest<S: Scalar>(&self, pos: Vector<3, S>) -> (Vector<3, S>, Feature) {
+    let [a, b, c] = self.triangle.points().map(Vector::cast::<S>);
+    let (ab, ac) = (b - a, c - a);
+    let zero = S::ZERO;
+
+    let (d1, d2) = (ab.dot(pos - a), ac.dot(pos - a));
+    if d1 <= zero && d2 <= zero {
+      return (a, Feature::Vertex(0));
+    }
+    let (d3, d4) = (ab.dot(pos - b), ac.dot(pos - b));
+    if d3 >= zero && d4 <= d3 {
+      return (b, Feature::Vertex(1));
+    }
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= zero && d1 >= zero && d3 <= zero {
+      return (a + ab * (d1 / (d1 - d3)), Feature::Edge(0));
+    }
+    let (d5, d6) = (ab.dot(pos - c), ac.dot(pos - c));
+    if d6 >= zero && d5 <= d6 {
+      return (c, Feature::Vertex(2));
+    }
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= zero && d2 >= zero && d6 <= zero {
+      return (a + ac * (d2 / (d2 - d6)), Feature::Edge(2));
+    }
+    let va = d3 * d6 - d5 * d4;
+    if va <= zero && d4 >= d3 && d5 >= d6 {
+      let t = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+      return (b + (c - b) * t, Feature::Edge(1));
+    }
+    let scale = (va + vb + vc).recip();
+    (a + ab * (vb * scale) + ac * (vc * scale), Feature::Face)
+  }
+}
+
+impl<S: Scalar> Sdf<3, S> for Mesh {
+  #[inline]
+  fn call(&self, pos: Vector<3, S>) -> S {
+    self.signed(pos)
+  }
+
+  #[inline]
+  fn hits(&self, pos: Vector<3, S>) -> bool {
+    self.bounds().contains(pos.cast()) && self.call(pos) <= S::ZERO
+  }
+}
+
+impl<S: Scalar> SdfGrad<3, S> for Mesh {
+  #[inline]
+  fn call_grad(&self, pos: Vector<3, S>) -> (S, Vector<3, S>) {
+    let Some((_, point, normal)) = self.closest(pos.cast()) else {
+      return (S::INFINITY, Vector::zeros());
+    };
+    let offset = pos - point.cast();
+    let sign = match offset.cast().dot(normal) < 0.0 {
+      true => -S::ONE,
+      false => S::ONE,
+    };
+    let dist = offset.mag();
+    match dist > S::ZERO {
+      true => (sign * dist, offset * (sign / dist)),
+      false => (S::ZERO, normal.normal().cast()),
+    }
+  }
+}
+
+impl SdfNoInfo<3> for Mesh {}
+
+impl SdfBounds<3> for Mesh {
+  fn bounds(&self) -> Aabb<3> {
+    self.nodes.first().map_or(Aabb::empty(), Node::bounds)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::items::checks::check_grad;
+  use crate::items::{Cuboid, Quad, Sphere};
+  use crate::traits::{
+    call_dual, AutoGrad, Remove, SdfInterval, SdfScale, SdfTranslate, SdfUnion,
+  };
+  use marchrs_vectors::GridIter;
+  use std::f64::consts::PI;
+
+  /// The faces of a unit cube centred on the origin
+  fn cube_mesh() -> Mesh {
+    Mesh::new((0..3).flat_map(|k| {
+      [-1.0, 1.0].into_iter().flat_map(move |sign| {
+        let centre = Vector::<3>::axis(k) * (0.5 * sign);
+        let (mut u, mut v) = (Vector::axis((k + 1) % 3), Vector::axis((k + 2) % 3));
+        if sign < 0.0 {
+          (u, v) = (v, u);
+        }
+        let (u, v) = (u * 0.5, v * 0.5);
+        let quad = Quad::new(
+          centre - u - v,
+          centre + u - v,
+          centre + u + v,
+          centre - u + v,
+        );
+        quad.triangles().map(|triangle| triangle.points())
+      })
+    }))
+  }
+
+  /// A sphere of the given `radius`, approximated by latitude and longitude
+  fn sphere_mesh(radius: f64, rings: usize, segments: usize) -> Mesh {
+    let point = |i: usize, j: usize| {
+      let polar = PI * i as f64 / rings as f64;
+      let azimuth = 2.0 * PI * j as f64 / segments as f64;
+      Vector::from_spherical(radius, polar, azimuth)
+    };
+    Mesh::new((0..rings).flat_map(|i| {
+      (0..segments).flat_map(move |j| {
+        let quad = Quad::new(
+          point(i, j),
+          point(i + 1, j),
+          point(i + 1, j + 1),
+          point(i, j + 1),
+        );
+        quad.triangles().map(|triangle| triangle.points())
+      })
+    }))
+  }
+
+  #[test]
+  fn cube() {
+    let mesh = cube_mesh();
+    let cuboid = Cuboid::<3>::default();
+    assert_eq!(mesh.len(), 12);
+    for pos in GridIter::<3>::new(Vector([-1.0; 3])..=Vector([1.0; 3]), [9; 3]) {
+      let (dist, expected) = (mesh.call(pos), cuboid.call(pos));
+      assert!(
+        (dist - expected).abs() < 1e-12,
+        "{dist} != {expected} at {pos:?}"
+      );
+      assert_eq!(mesh.hits(pos), cuboid.hits(pos), "at {pos:?}");
+    }
+    assert!((mesh.bounds().min - Vector([-0.5; 3])).mag() < 1e-12);
+    assert!((mesh.bounds().max - Vector([0.5; 3])).mag() < 1e-12);
+  }
+
+  #[test]
+  fn sphere() {
+    let mesh = sphere_mesh(0.8, 12, 16);
+    let triangles: Vec<_> = mesh.triangles().collect();
+    let sphere = Sphere.scale([1.6; 3]);
+    let range = Vector([-0.97, -1.03, -0.91])..=Vector([1.01, 0.93, 0.99]);
+    for pos in GridIter::<3>::new(range, [11; 3]) {
+      let dist = mesh.call(pos);
+      let unsigned = triangles
+        .iter()
+        .map(|triangle| triangle.call(pos))
+        .fold(f64::INFINITY, f64::min);
+      assert!(
+        (dist.abs() - unsigned).abs() < 1e-12,
+        "{dist} != {unsigned} at {pos:?}"
+      );
+      let approx = sphere.call(pos);
+      if approx.abs() > 0.05 {
+        assert_eq!(dist < 0.0, approx < 0.0, "{dist} at {pos:?}");
+      }
+      if dist > 0.05 {
+        let numeric = |pos: [f64; 3]| mesh.call(Vector(pos));
+        let (grad, expected) = (mesh.grad(pos), numeric.grad(pos));
+        assert!(
+          (grad - expected).mag() < 1e-5,
+          "{grad:?} != {expected:?} at {pos:?}"
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn grads_match_dual_and_numeric() {
+    check_grad(&cube_mesh().translate([0.0, 0.15, 0.06]));
+    check_grad(&sphere_mesh(0.45, 6, 8).translate([0.07, -0.05, 0.03]));
+    // Differentiates through the mesh as a child of other operators
+    let scene = AutoGrad(cube_mesh().translate([0.2, -0.1, 0.1]).or(Sphere));
+    let pos = Vector([0.9, 0.3, -0.6]);
+    let (dist, grad) = scene.call_grad(pos);
+    let (expected, expected_grad) = call_dual(&cube_mesh(), pos - Vector([0.2, -0.1, 0.1]));
+    assert!((dist - expected).abs() < 1e-12);
+    assert!((grad - expected_grad).mag() < 1e-12);
+    let f32_dist: f32 = cube_mesh().call(Vector([0.9f32, 0.3, -0.6]));
+    assert!((f32_dist as f64 - cube_mesh().call(pos)).abs() < 1e-6);
+  }
+
+  #[test]
+  fn combines() {
+    let scene = cube_mesh().or(Sphere.translate([1.0, 0.0, 0.0]));
+    assert_eq!(scene.call(Vector([1.0, 0.0, 0.0])), -0.5);
+    assert!((scene.call(Vector([-1.0, 0.0, 0.0])) - 0.5f64).abs() < 1e-12);
+    let hollow = Remove(cube_mesh(), Sphere.scale([0.25; 3]));
+    assert!(!hollow.hits(Vector([0.0; 3])));
+    assert!(hollow.hits(Vector([0.4, 0.0, 0.0])));
+  }
+
+  #[test]
+  fn intervals_contain_samples() {
+    let mesh = sphere_mesh(0.8, 12, 16);
+    for min in GridIter::<3>::new(Vector([-1.2; 3])..=Vector([0.9; 3]), [6; 3]) {
+      let region = Aabb::new(min, min + 0.3);
+      let bounds = mesh.call_interval(region);
+      for pos in GridIter::<3>::new(region.min..=region.max, [4; 3]) {
+        let dist: f64 = mesh.call(pos);
+        assert!(
+          bounds.lo - 1e-9 <= dist && dist <= bounds.hi + 1e-9,
+          "{dist} outside {bounds:?} at {pos:?}"
+        );
+      }
+    }
+    assert!(!mesh.may_contain_surface(Aabb::new([-0.2; 3], [0.2; 3])));
+    assert!(!mesh.may_contain_surface(Aabb::new([1.0; 3], [1.5; 3])));
+    assert!(mesh.may_contain_surface(Aabb::new([0.7, -0.1, -0.1], [0.9, 0.1, 0.1])));
+  }
+}
@@ -1,5 +1,5 @@
 use crate::traits::{Sdf, SdfGrad, SdfNoInfo};
-use marchrs_vectors::Vector;
+use marchrs_vectors::{Scalar, Vector};
 
 /// A set of two planes `Middle(axis)`:
 /// - centered on `(0, 0, ...)`
@@ -20,18 +20,19 @@ impl<const N: usize> Middle<N> {
   }
 }
 
-impl<const N: usize> Sdf<N> for Middle<N> {
+impl<const N: usize, S: Scalar> Sdf<N, S> for Middle<N> {
   #[inline]
-  fn call(&self, pos: Vector<N>) -> f64 {
-    self.0.dot(pos).abs() - 0.5
+  fn call(&self, pos: Vector<N, S>) -> S {
+    self.0.cast().dot(pos).abs() - S::from_f64(0.5)
   }
 }
 
 impl<const N: usize> SdfNoInfo<N> for Middle<N> {}
 
-impl<const N: usize> SdfGrad<N> for Middle<N> {
-  fn call_grad(&self, pos: Vector<N>) -> (f64, Vector<N>) {
-    let d = self.0.dot(pos);
-    (d.abs() - 0.5, d.signum() * self.0)
+impl<const N: usize, S: Scalar> SdfGrad<N, S> for Middle<N> {
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
+    let axis = self.0.cast();
+    let d = axis.dot(pos);
+    (d.abs() - S::from_f64(0.5), axis * d.signum())
   }
 }
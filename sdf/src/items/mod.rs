@@ -11,3 +11,15 @@ mod middle;
 use middle::*;
 mod cylinder;
 pub use cylinder::*;
+mod torus;
+pub use torus::*;
+mod cone;
+pub use cone::*;
+mod capsule;
+pub use capsule::*;
+mod triangle;
+pub use triangle::*;
+mod mesh;
+pub use mesh::*;
+#[cfg(test)]
+pub(crate) mod checks;
@@ -1,5 +1,5 @@
-use crate::traits::{Sdf, SdfGrad, SdfNoInfo};
-use marchrs_vectors::Vector;
+use crate::traits::{Sdf, SdfBounds, SdfGrad, SdfNoInfo};
+use marchrs_vectors::{Aabb, Scalar, Vector};
 
 /// A simple Plane:
 /// - located at `(0, 0, ...)`
@@ -7,17 +7,17 @@ use marchrs_vectors::Vector;
 #[derive(Clone, Copy, Default, PartialEq)]
 pub struct Plane<const N: usize>;
 
-impl<const N: usize> Sdf<N> for Plane<N> {
+impl<const N: usize, S: Scalar> Sdf<N, S> for Plane<N> {
   #[inline]
-  fn call(&self, pos: Vector<N>) -> f64 {
+  fn call(&self, pos: Vector<N, S>) -> S {
     pos[N - 1]
   }
 }
 
 impl<const N: usize> SdfNoInfo<N> for Plane<N> {}
 
-impl<const N: usize> SdfGrad<N> for Plane<N> {
-  fn call_grad(&self, pos: Vector<N>) -> (f64, Vector<N>) {
+impl<const N: usize, S: Scalar> SdfGrad<N, S> for Plane<N> {
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
     (self.call(pos), Vector::axis(N - 1))
   }
 }
@@ -41,17 +41,32 @@ impl<const N: usize> GenericPlane<N> {
   }
 }
 
-impl<const N: usize> Sdf<N> for GenericPlane<N> {
+impl<const N: usize, S: Scalar> Sdf<N, S> for GenericPlane<N> {
   #[inline]
-  fn call(&self, pos: Vector<N>) -> f64 {
-    self.0.dot(pos) - self.1
+  fn call(&self, pos: Vector<N, S>) -> S {
+    self.0.cast().dot(pos) - S::from_f64(self.1)
   }
 }
 
 impl<const N: usize> SdfNoInfo<N> for GenericPlane<N> {}
 
-impl<const N: usize> SdfGrad<N> for GenericPlane<N> {
-  fn call_grad(&self, pos: Vector<N>) -> (f64, Vector<N>) {
-    (self.call(pos), self.0)
+impl<const N: usize, S: Scalar> SdfGrad<N, S> for GenericPlane<N> {
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
+    (self.call(pos), self.0.cast())
+  }
+}
+
+/// Only the last axis is bounded, from above.
+impl<const N: usize> SdfBounds<N> for Plane<N> {
+  fn bounds(&self) -> Aabb<N> {
+    let mut bounds = Aabb::infinite();
+    bounds.max[N - 1] = 0.0;
+    bounds
+  }
+}
+
+impl<const N: usize> SdfBounds<N> for GenericPlane<N> {
+  fn bounds(&self) -> Aabb<N> {
+    Aabb::infinite()
   }
 }
@@ -1,5 +1,5 @@
-use crate::traits::{Sdf, SdfGrad, SdfNoInfo};
-use marchrs_vectors::Vector;
+use crate::traits::{Sdf, SdfBounds, SdfGrad, SdfNoInfo};
+use marchrs_vectors::{Aabb, Scalar, Vector};
 
 /// A simple Sphere:
 /// - located at `(0, 0, ...)`
@@ -7,22 +7,28 @@ use marchrs_vectors::Vector;
 #[derive(Clone, Copy, Default, PartialEq)]
 pub struct Sphere<const N: usize>;
 
-impl<const N: usize> Sdf<N> for Sphere<N> {
+impl<const N: usize, S: Scalar> Sdf<N, S> for Sphere<N> {
   #[inline]
-  fn call(&self, pos: Vector<N>) -> f64 {
-    pos.mag() - 0.5
+  fn call(&self, pos: Vector<N, S>) -> S {
+    pos.mag() - S::from_f64(0.5)
   }
 
   #[inline]
-  fn hits(&self, pos: Vector<N>) -> bool {
-    pos.mag2() <= 0.25
+  fn hits(&self, pos: Vector<N, S>) -> bool {
+    pos.mag2() <= S::from_f64(0.25)
   }
 }
 
 impl<const N: usize> SdfNoInfo<N> for Sphere<N> {}
 
-impl<const N: usize> SdfGrad<N> for Sphere<N> {
-  fn call_grad(&self, pos: Vector<N>) -> (f64, Vector<N>) {
+impl<const N: usize, S: Scalar> SdfGrad<N, S> for Sphere<N> {
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
     (self.call(pos), pos.normal())
   }
 }
+
+impl<const N: usize> SdfBounds<N> for Sphere<N> {
+  fn bounds(&self) -> Aabb<N> {
+    Aabb::new(-0.5, 0.5)
+  }
+}
@@ -0,0 +1,257 @@
+use crate::traits::{Sdf, SdfBounds, SdfGrad, SdfNoInfo};
+use marchrs_vectors::{Aabb, Scalar, Vector};
+use std::array;
+use std::f64::consts::{FRAC_PI_2, PI};
+
+/// A `Torus(major, minor)`:
+/// - centered at `(0, 0, ...)`
+/// - a ring of radius `major` in the plane of the first 2 axes
+/// - a tube of radius `minor` around the ring
+///
+/// So in 2D this is an annulus, and in 3D the ring is perpendicular\
+/// to the last axis. This matches a `CappedTorus` with an `angle` of `PI`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Torus<const N: usize> {
+  major: f64,
+  minor: f64,
+}
+
+impl<const N: usize> Default for Torus<N> {
+  fn default() -> Self {
+    Self::new(0.375, 0.125)
+  }
+}
+
+impl<const N: usize> Torus<N> {
+  pub fn new(major: f64, minor: f64) -> Self {
+    Self { major, minor }
+  }
+
+  /// The closest point on the ring to `pos`
+  fn ring_point<S: Scalar>(&self, pos: Vector<N, S>) -> Vector<N, S> {
+    let mut planar = Vector::<N, S>::zeros();
+    (planar[0], planar[1]) = (pos[0], pos[1]);
+    let radius = planar.mag();
+    let major = S::from_f64(self.major);
+    // every point on the ring is equally close to the axis
+    let fallback: Vector<N, S> = Vector::axis(0) * major;
+    let scale = major / radius;
+    Vector(array::from_fn(|i| {
+      radius.if_positive(planar[i] * scale, fallback[i])
+    }))
+  }
+}
+
+impl<const N: usize> Torus<N> {
+  /// The squared distance from `pos` to the ring
+  #[inline]
+  fn ring_dist2<S: Scalar>(&self, pos: Vector<N, S>) -> S {
+    let planar = pos[0] * pos[0] + pos[1] * pos[1];
+    // the squared distance out of the plane of the ring
+    let height = (pos.mag2() - planar).max(S::ZERO);
+    let offset = planar.sqrt() - S::from_f64(self.major);
+    offset * offset + height
+  }
+}
+
+impl<const N: usize, S: Scalar> Sdf<N, S> for Torus<N> {
+  #[inline]
+  fn call(&self, pos: Vector<N, S>) -> S {
+    self.ring_dist2(pos).sqrt() - S::from_f64(self.minor)
+  }
+
+  #[inline]
+  fn hits(&self, pos: Vector<N, S>) -> bool {
+    self.ring_dist2(pos) <= S::from_f64(self.minor * self.minor)
+  }
+}
+
+impl<const N: usize> SdfNoInfo<N> for Torus<N> {}
+
+impl<const N: usize, S: Scalar> SdfGrad<N, S> for Torus<N> {
+  #[inline]
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
+    let offset = pos - self.ring_point(pos);
+    (offset.mag() - S::from_f64(self.minor), offset.normal())
+  }
+}
+
+impl<const N: usize> SdfBounds<N> for Torus<N> {
+  fn bounds(&self) -> Aabb<N> {
+    let mut half = Vector::from(self.minor.abs());
+    let outer = self.major.abs() + self.minor.abs();
+    (half[0], half[1]) = (outer, outer);
+    Aabb::new(-half, half)
+  }
+}
+
+/// A `CappedTorus(major, minor, angle)`, i.e. an arc of a `Torus`:
+/// - centered at `(0, 0, ...)`
+/// - a ring of radius `major` in the plane of the first 2 axes
+/// - a tube of radius `minor` around the ring
+/// - only the ring within `angle` radians either side of axis `1`
+///
+/// An `angle` of `PI` gives the full `Torus`, in any dimension.
+#[derive(Clone, Copy, PartialEq)]
+pub struct CappedTorus<const N: usize> {
+  major: f64,
+  minor: f64,
+  angle: f64,
+}
+
+impl<const N: usize> Default for CappedTorus<N> {
+  fn default() -> Self {
+    Self::new(0.375, 0.125, FRAC_PI_2)
+  }
+}
+
+impl<const N: usize> CappedTorus<N> {
+  pub fn new(major: f64, minor: f64, angle: f64) -> Self {
+    Self {
+      major,
+      minor,
+      angle: angle.clamp(0.0, PI),
+    }
+  }
+
+  /// The angle from axis `1` to the closest point on the arc to `pos`.
+  ///
+  /// This avoids branching on which part of the arc is closest,\
+  /// so that it stays conservative when called with intervals.
+  #[inline]
+  fn arc_angle<S: Scalar>(&self, pos: Vector<N, S>) -> S {
+    pos[0].abs().atan2(pos[1]).min(S::from_f64(self.angle))
+  }
+
+  /// The squared distance from `pos` to the closest point on the arc
+  #[inline]
+  fn arc_dist2<S: Scalar>(&self, pos: Vector<N, S>) -> S {
+    let [x, y] = [pos[0].abs(), pos[1]];
+    let major = S::from_f64(self.major);
+    // the projection onto the direction of the closest point on the arc
+    let excess = (x.atan2(y) - S::from_f64(self.angle)).max(S::ZERO);
+    let proj = (x * x + y * y).sqrt() * excess.cos();
+    (pos.mag2() + major * major - S::from_f64(2.0) * major * proj).max(S::ZERO)
+  }
+}
+
+impl<const N: usize, S: Scalar> Sdf<N, S> for CappedTorus<N> {
+  #[inline]
+  fn call(&self, pos: Vector<N, S>) -> S {
+    self.arc_dist2(pos).sqrt() - S::from_f64(self.minor)
+  }
+
+  #[inline]
+  fn hits(&self, pos: Vector<N, S>) -> bool {
+    self.arc_dist2(pos) <= S::from_f64(self.minor * self.minor)
+  }
+}
+
+impl<const N: usize> SdfNoInfo<N> for CappedTorus<N> {}
+
+impl<const N: usize, S: Scalar> SdfGrad<N, S> for CappedTorus<N> {
+  #[inline]
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
+    let angle = self.arc_angle(pos);
+    let major = S::from_f64(self.major);
+    let mut closest = Vector::zeros();
+    closest[0] = major * angle.sin() * pos[0].signum();
+    closest[1] = major * angle.cos();
+    let offset = pos - closest;
+    (offset.mag() - S::from_f64(self.minor), offset.normal())
+  }
+}
+
+/// The arc spans `major * sin(angle)` either side of axis `1`,\
+/// and from `major * cos(angle)` up to `major` along it.
+impl<const N: usize> SdfBounds<N> for CappedTorus<N> {
+  fn bounds(&self) -> Aabb<N> {
+    let (major, minor) = (self.major.abs(), self.minor.abs());
+    let mut min = Vector::from(-minor);
+    let mut max = Vector::from(minor);
+    let width = major * self.angle.min(FRAC_PI_2).sin();
+    (min[0], max[0]) = (-width - minor, width + minor);
+    (min[1], max[1]) = (major * self.angle.cos() - minor, major + minor);
+    Aabb::new(min, max)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::items::checks::{check_bounds, check_grad, check_intervals};
+  use marchrs_vectors::GridIter;
+
+  fn grid() -> GridIter<3> {
+    GridIter::new(Vector([-1.0; 3])..=Vector([1.0; 3]), [11; 3])
+  }
+
+  #[test]
+  fn distances() {
+    let torus = Torus::<3>::new(0.6, 0.2);
+    assert!((torus.call(Vector([0.0, 0.0, 0.0])) - 0.4f64).abs() < 1e-12);
+    assert!((torus.call(Vector([0.6, 0.0, 0.5])) - 0.3f64).abs() < 1e-12);
+    assert!((torus.call(Vector([0.0, -0.7, 0.0])) + 0.1f64).abs() < 1e-12);
+    // Only the arc within a quarter turn of axis `1` is kept
+    let capped = CappedTorus::<3>::new(0.6, 0.2, FRAC_PI_2);
+    assert!((capped.call(Vector([0.0, 0.6, 0.0])) + 0.2f64).abs() < 1e-12);
+    assert!((capped.call(Vector([0.0, -0.6, 0.0])) - (0.72f64.sqrt() - 0.2)).abs() < 1e-12);
+    assert!((capped.call(Vector([0.6, -0.3, 0.0])) - 0.1f64).abs() < 1e-12);
+  }
+
+  /// Both put the ring in the plane of the first 2 axes,\
+  /// so they agree in every dimension, not only in 3D.
+  fn check_full_cap<const N: usize>() {
+    let (torus, capped) = (Torus::<N>::new(0.6, 0.2), CappedTorus::<N>::new(0.6, 0.2, PI));
+    let range = Vector([-0.97; N])..=Vector([1.03; N]);
+    for pos in GridIter::<N>::new(range, [9; N]) {
+      let (dist, expected): (f64, f64) = (capped.call(pos), torus.call(pos));
+      assert!((dist - expected).abs() < 1e-12, "{dist} != {expected} at {pos:?}");
+      assert_eq!(capped.hits(pos), torus.hits(pos), "at {pos:?}");
+    }
+    assert_eq!(capped.bounds(), torus.bounds());
+  }
+
+  #[test]
+  fn full_cap_is_torus() {
+    check_full_cap::<2>();
+    check_full_cap::<3>();
+    check_full_cap::<4>();
+  }
+
+  #[test]
+  fn hits_match_call() {
+    let torus = Torus::<3>::new(0.6, 0.2);
+    let capped = CappedTorus::<3>::new(0.6, 0.3, 2.0);
+    for pos in grid() {
+      assert_eq!(torus.hits(pos), torus.call(pos) <= 0.0, "at {pos:?}");
+      assert_eq!(capped.hits(pos), capped.call(pos) <= 0.0, "at {pos:?}");
+    }
+  }
+
+  #[test]
+  fn grads_match_dual_and_numeric() {
+    check_grad(&Torus::<3>::new(0.6, 0.2));
+    check_grad(&CappedTorus::<3>::new(0.6, 0.2, 2.0));
+  }
+
+  #[test]
+  fn bounds_contain_hits() {
+    // Arcs less than, and more than, a quarter turn
+    for angle in [0.4, 2.0, PI] {
+      check_bounds(&CappedTorus::<3>::new(0.7, 0.2, angle), grid());
+    }
+    let torus = Torus::<3>::new(0.75, 0.25);
+    check_bounds(&torus, grid());
+    assert_eq!(
+      torus.bounds(),
+      Aabb::new([-1.0, -1.0, -0.25], [1.0, 1.0, 0.25])
+    );
+  }
+
+  #[test]
+  fn intervals_contain_samples() {
+    check_intervals(&Torus::<3>::new(0.5, 0.2));
+    check_intervals(&CappedTorus::<3>::new(0.6, 0.1, 2.0));
+  }
+}
@@ -0,0 +1,281 @@
+use super::Segment;
+use crate::traits::{Sdf, SdfBounds, SdfGrad, SdfNoInfo};
+use marchrs_vectors::{Aabb, Scalar, Vector};
+
+/// A flat `Triangle(a, b, c)`, with no thickness.
+///
+/// As with `Segment`, this is unsigned, so is only hit exactly on\
+/// its surface. Use `.round` to thicken it, or a `Mesh` to combine\
+/// many triangles into a closed, signed surface.
+///
+/// The default lies in the plane of the first two axes.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Triangle<const N: usize> {
+  a: Vector<N>,
+  b: Vector<N>,
+  c: Vector<N>,
+}
+
+/// A point in the plane of the first two axes
+fn planar<const N: usize>(x: f64, y: f64) -> Vector<N> {
+  Vector::axis(0) * x + Vector::axis(1) * y
+}
+
+impl<const N: usize> Default for Triangle<N> {
+  fn default() -> Self {
+    Self::new(planar(-0.5, -0.5), planar(0.5, -0.5), planar(0.0, 0.5))
+  }
+}
+
+impl<const N: usize> Triangle<N> {
+  pub fn new(a: impl Into<Vector<N>>, b: impl Into<Vector<N>>, c: impl Into<Vector<N>>) -> Self {
+    Self {
+      a: a.into(),
+      b: b.into(),
+      c: c.into(),
+    }
+  }
+
+  /// The corners of the triangle, `[a, b, c]`
+  pub fn points(&self) -> [Vector<N>; 3] {
+    [self.a, self.b, self.c]
+  }
+
+  /// The edges of the triangle, `[ab, bc, ca]`
+  pub fn edges(&self) -> [Segment<N>; 3] {
+    [
+      Segment::new(self.a, self.b),
+      Segment::new(self.b, self.c),
+      Segment::new(self.c, self.a),
+    ]
+  }
+
+  /// The barycentric weights of `pos` projected onto the plane\
+  /// of the triangle, or `None` if the triangle is degenerate.
+  #[inline]
+  fn weights<S: Scalar>(&self, pos: Vector<N, S>) -> Option<[S; 3]> {
+    let (ab, ac) = (self.b - self.a, self.c - self.a);
+    let (d00, d01, d11) = (ab.mag2(), ab.dot(ac), ac.mag2());
+    let denom = d00 * d11 - d01 * d01;
+    if denom <= f64::EPSILON * d00 * d11 {
+      return None;
+    }
+    let rel = pos - self.a.cast();
+    let (d20, d21) = (rel.dot(ab.cast()), rel.dot(ac.cast()));
+    let scale = S::from_f64(denom.recip());
+    let v = (S::from_f64(d11) * d20 - S::from_f64(d01) * d21) * scale;
+    let w = (S::from_f64(d00) * d21 - S::from_f64(d01) * d20) * scale;
+    Some([S::ONE - v - w, v, w])
+  }
+
+  /// The offset from the projection of `pos` onto the plane to `pos`
+  #[inline]
+  fn plane_offset<S: Scalar>(&self, pos: Vector<N, S>, [_, v, w]: [S; 3]) -> Vector<N, S> {
+    let a: Vector<N, S> = self.a.cast();
+    pos - a - (self.b - self.a).cast() * v - (self.c - self.a).cast() * w
+  }
+
+  /// The offset from the closest point on the triangle to `pos`
+  #[inline]
+  pub(super) fn offset<S: Scalar>(&self, pos: Vector<N, S>) -> Vector<N, S> {
+    match self.weights(pos) {
+      Some(weights) if weights.iter().all(|&w| w >= S::ZERO) => self.plane_offset(pos, weights),
+      _ => self
+        .edges()
+        .map(|edge| edge.offset(pos))
+        .into_iter()
+        .reduce(|l, r| if r.mag2() < l.mag2() { r } else { l })
+        .unwrap(),
+    }
+  }
+
+  /// The squared distance from `pos` to the triangle.
+  ///
+  /// This branches like `offset`, but intervals can straddle the edge\
+  /// of the triangle, so they cover both the plane and edge distances.
+  #[inline]
+  pub(super) fn dist2<S: Scalar>(&self, pos: Vector<N, S>) -> S {
+    let edges = || {
+      self
+        .edges()
+        .map(|edge| edge.offset(pos).mag2())
+        .into_iter()
+        .fold(S::INFINITY, S::min)
+    };
+    let Some(weights) = self.weights(pos) else {
+      return edges();
+    };
+    let inside = weights[0].min(weights[1]).min(weights[2]);
+    let plane = || self.plane_offset(pos, weights).mag2();
+    if inside >= S::ZERO {
+      plane()
+    } else if inside < S::ZERO {
+      edges()
+    } else {
+      inside.if_positive(plane(), edges())
+    }
+  }
+}
+
+impl Triangle<3> {
+  /// The unit normal, facing the side where `a, b, c` run anticlockwise
+  pub fn normal(&self) -> Vector<3> {
+    (self.b - self.a).cross(self.c - self.a).normal()
+  }
+}
+
+impl<const N: usize, S: Scalar> Sdf<N, S> for Triangle<N> {
+  #[inline]
+  fn call(&self, pos: Vector<N, S>) -> S {
+    self.dist2(pos).sqrt()
+  }
+}
+
+impl<const N: usize> SdfNoInfo<N> for Triangle<N> {}
+
+impl<const N: usize, S: Scalar> SdfGrad<N, S> for Triangle<N> {
+  #[inline]
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
+    let offset = self.offset(pos);
+    (offset.mag(), offset.normal())
+  }
+}
+
+impl<const N: usize> SdfBounds<N> for Triangle<N> {
+  fn bounds(&self) -> Aabb<N> {
+    Aabb::from_points(self.points())
+  }
+}
+
+/// A flat `Quad(a, b, c, d)`, with no thickness.
+///
+/// This is split into the triangles `a, b, c` and `a, c, d`,\
+/// so doesn't need to be planar, though it's usually expected to be.
+///
+/// The default is the unit square in the plane of the first two axes.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Quad<const N: usize>([Triangle<N>; 2]);
+
+impl<const N: usize> Default for Quad<N> {
+  fn default() -> Self {
+    Self::new(
+      planar(-0.5, -0.5),
+      planar(0.5, -0.5),
+      planar(0.5, 0.5),
+      planar(-0.5, 0.5),
+    )
+  }
+}
+
+impl<const N: usize> Quad<N> {
+  pub fn new(
+    a: impl Into<Vector<N>>,
+    b: impl Into<Vector<N>>,
+    c: impl Into<Vector<N>>,
+    d: impl Into<Vector<N>>,
+  ) -> Self {
+    let (a, c) = (a.into(), c.into());
+    Self([Triangle::new(a, b, c), Triangle::new(a, c, d)])
+  }
+
+  /// The triangles making up the quad
+  pub fn triangles(&self) -> [Triangle<N>; 2] {
+    self.0
+  }
+}
+
+impl<const N: usize, S: Scalar> Sdf<N, S> for Quad<N> {
+  #[inline]
+  fn call(&self, pos: Vector<N, S>) -> S {
+    self.0[0].call(pos).min(self.0[1].call(pos))
+  }
+}
+
+impl<const N: usize> SdfNoInfo<N> for Quad<N> {}
+
+impl<const N: usize, S: Scalar> SdfGrad<N, S> for Quad<N> {
+  #[inline]
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
+    let (first, second) = (self.0[0].offset(pos), self.0[1].offset(pos));
+    let offset = if second.mag2() < first.mag2() {
+      second
+    } else {
+      first
+    };
+    (offset.mag(), offset.normal())
+  }
+}
+
+impl<const N: usize> SdfBounds<N> for Quad<N> {
+  fn bounds(&self) -> Aabb<N> {
+    self.0[0].bounds().union(self.0[1].bounds())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::items::checks::{check_bounds, check_grad, check_intervals};
+  use crate::traits::{SdfRotate3D, SdfTranslate};
+  use marchrs_vectors::GridIter;
+
+  #[test]
+  fn distances() {
+    let triangle = Triangle::<4>::default();
+    assert_eq!(triangle.call(Vector([0.0, 0.0, 3.0, 4.0])), 5.0);
+    assert_eq!(triangle.call(Vector([0.0, -1.5, 0.0, 0.0])), 1.0);
+    assert_eq!(triangle.call(Vector([-1.5, -0.5, 0.0, 0.0])), 1.0);
+    assert_eq!(triangle.call(Vector([0.0, 1.5, 0.0, 0.0])), 1.0);
+    assert!(triangle.hits(Vector([0.0, 0.0, 0.0, 0.0])));
+    assert!(!triangle.hits(Vector([0.0, 0.0, 0.1, 0.0])));
+    let quad = Quad::<3>::default();
+    assert_eq!(quad.call(Vector([0.2, -0.3, -0.5])), 0.5);
+    assert_eq!(quad.call(Vector([1.5, 0.0, 0.0])), 1.0);
+    let degenerate = Triangle::<3>::new([0.0; 3], [1.0, 0.0, 0.0], [2.0, 0.0, 0.0]);
+    assert_eq!(degenerate.call(Vector([1.0, 1.0, 0.0])), 1.0);
+  }
+
+  #[test]
+  fn grads_match_dual_and_numeric() {
+    check_grad(&Triangle::new(
+      [0.1, -0.2, 0.3],
+      [-0.4, 0.5, 0.0],
+      [0.6, 0.4, -0.5],
+    ));
+    check_grad(&Quad::new(
+      [0.1, -0.2, 0.3],
+      [-0.4, 0.5, 0.0],
+      [0.1, 0.9, -0.3],
+      [0.6, 0.4, -0.5],
+    ));
+  }
+
+  #[test]
+  fn bounds_contain_hits() {
+    // Triangles are only hit on their surface, so sample it directly
+    let triangle = Triangle::<3>::new([1.0, 1.0, 1.0], [1.5, 1.0, 1.0], [1.0, 1.5, 1.5]);
+    let [a, b, c] = triangle.points();
+    let surface = GridIter::<2>::new(Vector([0.0; 2])..=Vector([1.0; 2]), [11; 2])
+      .map(|Vector([u, v])| a + (b - a) * u + (c - a) * (v * (1.0 - u)));
+    assert_eq!(check_bounds(&triangle, surface), 121);
+    // The default quad lies on a plane of the grid
+    check_bounds(
+      &Quad::<3>::default(),
+      GridIter::<3>::new(Vector([-1.0; 3])..=Vector([1.0; 3]), [21; 3]),
+    );
+  }
+
+  #[test]
+  fn intervals_contain_samples() {
+    check_intervals(&Triangle::<3>::new(
+      [-1.0, 0.5, 0.2],
+      [-0.2, 0.9, 0.6],
+      [-0.6, -0.3, 0.9],
+    ));
+    check_intervals(
+      &Quad::<3>::default()
+        .rot([1.0, 0.0, 1.0], 0.7)
+        .translate([0.6, -0.6, 0.3]),
+    );
+  }
+}
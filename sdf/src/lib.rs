@@ -1,3 +1,5 @@
 pub mod interpolate;
 pub mod items;
+pub mod loaders;
+pub mod polygonise;
 pub mod traits;
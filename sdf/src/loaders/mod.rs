@@ -0,0 +1,154 @@
+//! Loaders for meshes stored in common file formats
+mod obj;
+pub use obj::*;
+mod stl;
+pub use stl::*;
+
+use crate::items::{weld, Mesh, Triangle};
+use marchrs_vectors::Vector;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io;
+
+/// The reasons a mesh file can fail to load
+#[derive(Debug)]
+pub enum LoadError {
+  /// The file couldn't be read
+  Io(io::Error),
+  /// The file isn't valid, with a description of the first problem
+  Parse(String),
+  /// The file (or group) contains no faces
+  Empty(String),
+  /// The named group has `count` edges only used by a single face,\
+  /// so has holes and no well defined inside.
+  Open { group: String, count: usize },
+  /// The named group has an edge between the given vertices\
+  /// shared by more than two faces.
+  NonManifold { group: String, edge: [Vector<3>; 2] },
+  /// The named group has two faces either side of the given edge\
+  /// that are wound in opposite directions.
+  Inconsistent { group: String, edge: [Vector<3>; 2] },
+}
+
+impl Display for LoadError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      LoadError::Io(err) => write!(f, "Couldn't read mesh: {err}"),
+      LoadError::Parse(message) => write!(f, "Invalid mesh file: {message}"),
+      LoadError::Empty(group) => write!(f, "Mesh '{group}' has no faces"),
+      LoadError::Open { group, count } => {
+        write!(
+          f,
+          "Mesh '{group}' isn't closed, {count} edges have only one face"
+        )
+      }
+      LoadError::NonManifold {
+        group,
+        edge: [a, b],
+      } => write!(
+        f,
+        "Mesh '{group}' isn't manifold, the edge {:?} to {:?} has more than two faces",
+        a.0, b.0
+      ),
+      LoadError::Inconsistent {
+        group,
+        edge: [a, b],
+      } => write!(
+        f,
+        "Mesh '{group}' has inconsistent winding, the faces either side of {:?} to {:?} disagree",
+        a.0, b.0
+      ),
+    }
+  }
+}
+
+impl std::error::Error for LoadError {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match self {
+      LoadError::Io(err) => Some(err),
+      _ => None,
+    }
+  }
+}
+
+impl From<io::Error> for LoadError {
+  fn from(err: io::Error) -> Self {
+    LoadError::Io(err)
+  }
+}
+
+/// Welds a triangle soup into a `Mesh`, checking that it's closed.
+///
+/// Degenerate faces are dropped before the check, as `Mesh` skips them,\
+/// so a surface that's only closed by slivers is reported as open.
+fn build_mesh(
+  group: &str,
+  triangles: impl IntoIterator<Item = [Vector<3>; 3]>,
+) -> Result<Mesh, LoadError> {
+  let (vertices, mut faces) = weld(triangles);
+  faces.retain(|face| {
+    let [a, b, c] = face.map(|i| vertices[i]);
+    Triangle::new(a, b, c).normal().is_finite()
+  });
+  check_closed(group, &vertices, &faces)?;
+  Ok(Mesh::from_indexed(&vertices, faces))
+}
+
+/// Checks that `faces` form a closed, manifold and consistently wound\
+/// surface, i.e. that every edge is used exactly once in each direction.
+fn check_closed(
+  group: &str,
+  vertices: &[Vector<3>],
+  faces: &[[usize; 3]],
+) -> Result<(), LoadError> {
+  if faces.is_empty() {
+    return Err(LoadError::Empty(group.to_owned()));
+  }
+
+  // The number of faces using each edge, and the number running `a -> b`
+  let mut edges: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+  for face in faces {
+    for i in 0..3 {
+      let (a, b) = (face[i], face[(i + 1) % 3]);
+      let (uses, forwards) = edges.entry((a.min(b), a.max(b))).or_default();
+      *uses += 1;
+      *forwards += usize::from(a < b);
+    }
+  }
+
+  let edge = |&(a, b): &(usize, usize)| [vertices[a], vertices[b]];
+  let group = group.to_owned();
+  if let Some((key, _)) = edges.iter().find(|(_, &(uses, _))| uses > 2) {
+    return Err(LoadError::NonManifold {
+      group,
+      edge: edge(key),
+    });
+  }
+  let count = edges.values().filter(|&&(uses, _)| uses == 1).count();
+  if count > 0 {
+    return Err(LoadError::Open { group, count });
+  }
+  match edges.iter().find(|(_, &(_, forwards))| forwards != 1) {
+    Some((key, _)) => Err(LoadError::Inconsistent {
+      group,
+      edge: edge(key),
+    }),
+    None => Ok(()),
+  }
+}
+
+/// Parses a whitespace separated vector, for error messages `line` is 1-indexed.
+fn parse_vector<'a>(
+  line: usize,
+  mut parts: impl Iterator<Item = &'a str>,
+) -> Result<Vector<3>, LoadError> {
+  let mut coord = || {
+    let part = parts
+      .next()
+      .ok_or_else(|| LoadError::Parse(format!("line {line}: expected 3 coordinates")))?;
+    part
+      .parse()
+      .map_err(|_| LoadError::Parse(format!("line {line}: '{part}' isn't a number")))
+  };
+  Ok(Vector([coord()?, coord()?, coord()?]))
+}
@@ -0,0 +1,221 @@
+use super::{build_mesh, parse_vector, LoadError};
+use crate::items::Mesh;
+use crate::traits::{DynModel, SdfDynWrap, SdfUnion, SdfWithInfo};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// The name of any faces before the first `o` or `g` line
+const DEFAULT_GROUP: &str = "default";
+
+/// A named group of faces from an OBJ file, as a closed `Mesh`
+#[derive(Clone)]
+pub struct ObjGroup {
+  pub name: String,
+  pub mesh: Mesh,
+}
+
+/// The groups of faces in an OBJ file, in the order they first appear.
+///
+/// Each group is started by an `o` or `g` line, and faces from groups\
+/// sharing a name are merged. Any other data (normals, texture coords,\
+/// materials, etc.) isn't needed for distances, so is ignored.
+#[derive(Clone)]
+pub struct Obj {
+  groups: Vec<ObjGroup>,
+}
+
+/// Loads the groups of an OBJ file, each as a closed `Mesh`.
+pub fn load_obj(path: impl AsRef<Path>) -> Result<Obj, LoadError> {
+  read_obj(BufReader::new(File::open(path)?))
+}
+
+/// Reads the groups of OBJ data, each as a closed `Mesh`.
+///
+/// Polygons are split into fans of triangles, so should be convex.
+pub fn read_obj(reader: impl BufRead) -> Result<Obj, LoadError> {
+  let mut vertices = vec![];
+  let mut groups = vec![(DEFAULT_GROUP.to_owned(), vec![])];
+  let mut current = 0;
+
+  for (line, text) in (1..).zip(reader.lines()) {
+    let text = text?;
+    let text = text.split('#').next().unwrap_or_default();
+    let mut parts = text.split_whitespace();
+    match parts.next() {
+      Some("v") => vertices.push(parse_vector(line, parts)?),
+      Some("f") => {
+        let corners = parts
+          .map(|part| vertex_index(line, part, vertices.len()))
+          .collect::<Result<Vec<_>, _>>()?;
+        if corners.len() < 3 {
+          return Err(LoadError::Parse(format!(
+            "line {line}: faces need at least 3 vertices"
+          )));
+        }
+        let faces = &mut groups[current].1;
+        faces.extend(
+          corners
+            .windows(2)
+            .skip(1)
+            .map(|pair| [corners[0], pair[0], pair[1]].map(|corner| vertices[corner])),
+        );
+      }
+      Some("o" | "g") => {
+        let name = match parts.collect::<Vec<_>>().join(" ") {
+          name if name.is_empty() => DEFAULT_GROUP.to_owned(),
+          name => name,
+        };
+        current = match groups.iter().position(|(group, _)| *group == name) {
+          Some(index) => index,
+          None => {
+            groups.push((name, vec![]));
+            groups.len() - 1
+          }
+        };
+      }
+      _ => {}
+    }
+  }
+
+  let groups = groups
+    .into_iter()
+    .filter(|(_, faces)| !faces.is_empty())
+    .map(|(name, faces)| {
+      let mesh = build_mesh(&name, faces)?;
+      Ok(ObjGroup { name, mesh })
+    })
+    .collect::<Result<Vec<_>, LoadError>>()?;
+  match groups.is_empty() {
+    true => Err(LoadError::Empty(DEFAULT_GROUP.to_owned())),
+    false => Ok(Obj { groups }),
+  }
+}
+
+/// Resolves a face corner, i.e. `v`, `v/vt`, `v//vn` or `v/vt/vn`,\
+/// into an index in `vertices`. Negative indices count backwards\
+/// from the last vertex, and positive indices start at `1`.
+fn vertex_index(line: usize, corner: &str, len: usize) -> Result<usize, LoadError> {
+  let vertex = corner.split('/').next().unwrap_or_default();
+  let index: isize = vertex
+    .parse()
+    .map_err(|_| LoadError::Parse(format!("line {line}: '{corner}' isn't a face vertex")))?;
+  let index = match index < 0 {
+    true => len.checked_sub(index.unsigned_abs()),
+    false => (index as usize).checked_sub(1),
+  };
+  index.filter(|&index| index < len).ok_or_else(|| {
+    LoadError::Parse(format!(
+      "line {line}: vertex '{vertex}' doesn't exist, there are {len} so far"
+    ))
+  })
+}
+
+impl Obj {
+  pub fn groups(&self) -> &[ObjGroup] {
+    &self.groups
+  }
+
+  pub fn into_groups(self) -> Vec<ObjGroup> {
+    self.groups
+  }
+
+  /// Combines all the groups into a single model, attaching\
+  /// `info(name)` to each group with `SdfWithInfo::with`.
+  pub fn model<I: Clone + Send + Sync + 'static>(
+    self,
+    mut info: impl FnMut(&str) -> I,
+  ) -> DynModel<3, I> {
+    self
+      .groups
+      .into_iter()
+      .map(|group| group.mesh.with(info(&group.name)).wrap())
+      .reduce(|model, group| model.or(group).wrap())
+      .expect("read_obj ensures there's at least one group")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::traits::{Sdf, SdfInfo};
+  use marchrs_vectors::Vector;
+
+  /// A unit cube, with quad faces, normals and texture coordinates
+  const CUBE: &str = "
+    v -0.5 -0.5 -0.5
+    v 0.5 -0.5 -0.5
+    v 0.5 0.5 -0.5
+    v -0.5 0.5 -0.5
+    v -0.5 -0.5 0.5
+    v 0.5 -0.5 0.5
+    v 0.5 0.5 0.5
+    v -0.5 0.5 0.5
+    vt 0 0
+    vn 0 0 1
+    f -8/1/1 -5/1/1 -6/1/1 -7/1/1
+    f -4//1 -3//1 -2//1 -1//1
+    f -8 -7 -3 -4
+    f -7 -6 -2 -3
+    f -6 -5 -1 -2
+    f -5 -8 -4 -1
+  ";
+
+  fn two_cubes() -> String {
+    let moved = CUBE.replace("v 0.5", "v 2.5").replace("v -0.5", "v 1.5");
+    format!("# two cubes\no first{CUBE}g second # the other one\n{moved}usemtl shiny\ns 1\n")
+  }
+
+  #[test]
+  fn reads_groups() {
+    let obj = read_obj(two_cubes().as_bytes()).unwrap();
+    let names: Vec<_> = obj
+      .groups()
+      .iter()
+      .map(|group| group.name.as_str())
+      .collect();
+    assert_eq!(names, ["first", "second"]);
+    for group in obj.groups() {
+      assert_eq!(group.mesh.len(), 12);
+    }
+
+    let model = obj.model(|name| name.to_owned());
+    let (dist, info) = model.call_info(Vector([0.0, 0.0, 0.0]));
+    assert!((dist + 0.5).abs() < 1e-12);
+    assert_eq!(info, "first");
+    assert_eq!(model.info(Vector([2.0, 0.0, 0.0])), "second");
+    assert!((model.call(Vector([1.0, 0.0, 0.0])) - 0.5).abs() < 1e-12);
+  }
+
+  #[test]
+  fn merges_groups() {
+    let (head, tail) = CUBE.split_at(CUBE.find("f -8 -7").unwrap());
+    let text = format!("g cube\n{head}g other\ng cube\n{tail}");
+    let obj = read_obj(text.as_bytes()).unwrap();
+    assert_eq!(obj.groups().len(), 1);
+    assert_eq!(obj.groups()[0].name, "cube");
+  }
+
+  #[test]
+  fn rejects_invalid() {
+    let open = CUBE.replace("f -8 -7 -3 -4", "");
+    assert!(matches!(
+      read_obj(open.as_bytes()),
+      Err(LoadError::Open { count: 4, .. })
+    ));
+    let flipped = CUBE.replace("f -8 -7 -3 -4", "f -4 -3 -7 -8");
+    assert!(matches!(
+      read_obj(flipped.as_bytes()),
+      Err(LoadError::Inconsistent { .. })
+    ));
+    let missing = CUBE.replace("f -8 -7 -3 -4", "f 1 2 9");
+    match read_obj(missing.as_bytes()) {
+      Err(LoadError::Parse(message)) => assert!(message.starts_with("line 14:"), "{message}"),
+      _ => panic!("expected a parse error"),
+    }
+    assert!(matches!(
+      read_obj("v 0 0 0".as_bytes()),
+      Err(LoadError::Empty(_))
+    ));
+  }
+}
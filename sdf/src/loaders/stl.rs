@@ -0,0 +1,285 @@
+use super::{build_mesh, parse_vector, LoadError};
+use crate::items::Mesh;
+use marchrs_vectors::Vector;
+use std::fs::File;
+use std::io::Read;
+use std::mem;
+use std::path::Path;
+
+/// Loads a closed `Mesh` from an STL file, in either format.
+pub fn load_stl(path: impl AsRef<Path>) -> Result<Mesh, LoadError> {
+  read_stl(File::open(path)?)
+}
+
+/// Reads a closed `Mesh` from STL data, in either format.
+///
+/// Binary files are detected by their length matching the face count\
+/// in the header, as binary headers can also start with `solid`.\
+/// The stored normals are ignored, relying on the winding instead.
+pub fn read_stl(mut reader: impl Read) -> Result<Mesh, LoadError> {
+  let mut bytes = vec![];
+  reader.read_to_end(&mut bytes)?;
+  if is_binary(&bytes) {
+    return build_mesh("solid", read_binary(&bytes));
+  }
+  let text = std::str::from_utf8(&bytes)
+    .map_err(|_| LoadError::Parse("not an ASCII or binary STL file".to_owned()))?;
+  let (name, triangles) = read_ascii(text)?;
+  build_mesh(&name, triangles)
+}
+
+/// The size of the header and face count in a binary file
+const HEADER: usize = 84;
+/// The size of each face in a binary file:\
+/// the normal and vertices as `f32`s, followed by a `u16` attribute.
+const FACE: usize = 50;
+
+fn is_binary(bytes: &[u8]) -> bool {
+  match bytes.get(80..HEADER) {
+    Some(count) => {
+      let count = u32::from_le_bytes(count.try_into().unwrap()) as usize;
+      count
+        .checked_mul(FACE)
+        .and_then(|len| len.checked_add(HEADER))
+        == Some(bytes.len())
+    }
+    None => false,
+  }
+}
+
+fn read_binary(bytes: &[u8]) -> impl Iterator<Item = [Vector<3>; 3]> + '_ {
+  bytes[HEADER..].chunks_exact(FACE).map(|face| {
+    let coord = |i: usize| f32::from_le_bytes(face[4 * i..4 * i + 4].try_into().unwrap()) as f64;
+    // Skip the normal, in `face[0..3]`
+    [1, 2, 3].map(|v| Vector([coord(3 * v), coord(3 * v + 1), coord(3 * v + 2)]))
+  })
+}
+
+/// Where the ascii parser is within the nested blocks of the file
+#[derive(Clone, Copy, PartialEq)]
+enum Block {
+  /// Between `endsolid` and the next `solid`
+  Outside,
+  /// Within a `solid`, between facets
+  Solid,
+  /// After `facet`, before its `outer loop`
+  Facet,
+  /// Within `outer loop`, reading vertices
+  Loop,
+  /// After `endloop`, before `endfacet`
+  EndLoop,
+}
+
+/// Reads every `solid ... endsolid` block into a single triangle soup,\
+/// named after the first block.
+///
+/// Vertices are only read within a `facet` and its `outer loop`,\
+/// and each loop must have exactly 3 of them.
+fn read_ascii(text: &str) -> Result<(String, Vec<[Vector<3>; 3]>), LoadError> {
+  let mut lines = (1..)
+    .zip(text.lines())
+    .filter(|(_, line)| !line.trim().is_empty());
+  let name = match lines.next() {
+    Some((_, line)) if line.trim().starts_with("solid") => line.trim()["solid".len()..].trim(),
+    _ => return Err(LoadError::Parse("line 1: expected 'solid'".to_owned())),
+  };
+
+  let mut triangles = vec![];
+  let mut corners = vec![];
+  let mut block = Block::Solid;
+  for (line, text) in lines {
+    let mut parts = text.split_whitespace();
+    let Some(keyword) = parts.next() else {
+      continue;
+    };
+    block = match (block, keyword) {
+      (Block::Outside, "solid") => Block::Solid,
+      (Block::Solid, "endsolid") => Block::Outside,
+      (Block::Solid, "facet") => Block::Facet,
+      (Block::Facet, "outer") => Block::Loop,
+      (Block::Loop, "vertex") if corners.len() < 3 => {
+        corners.push(parse_vector(line, parts)?);
+        Block::Loop
+      }
+      (Block::Loop, "vertex") => {
+        return Err(LoadError::Parse(format!(
+          "line {line}: expected 3 vertices, found more"
+        )))
+      }
+      (Block::Loop, "endloop") => match <[_; 3]>::try_from(mem::take(&mut corners)) {
+        Ok(triangle) => {
+          triangles.push(triangle);
+          Block::EndLoop
+        }
+        Err(corners) => {
+          return Err(LoadError::Parse(format!(
+            "line {line}: expected 3 vertices, found {}",
+            corners.len()
+          )))
+        }
+      },
+      (Block::EndLoop, "endfacet") => Block::Solid,
+      (_, "vertex") => {
+        return Err(LoadError::Parse(format!(
+          "line {line}: 'vertex' outside a facet's 'outer loop'"
+        )))
+      }
+      (_, other) => {
+        return Err(LoadError::Parse(format!(
+          "line {line}: unexpected '{other}'"
+        )))
+      }
+    };
+  }
+  if !matches!(block, Block::Outside | Block::Solid) {
+    return Err(LoadError::Parse("unexpected end of file within a facet".to_owned()));
+  }
+
+  let name = match name.is_empty() {
+    true => "solid",
+    false => name,
+  };
+  Ok((name.to_owned(), triangles))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::traits::Sdf;
+
+  const TETRAHEDRON: [[[f32; 3]; 3]; 4] = [
+    [[0.0, 0.0, 0.0], [0.0, 1.0, 0.0], [1.0, 0.0, 0.0]],
+    [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]],
+    [[0.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0, 0.0]],
+    [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+  ];
+
+  fn ascii(faces: &[[[f32; 3]; 3]]) -> String {
+    let mut text = "solid tetra\n".to_owned();
+    for face in faces {
+      text += "  facet normal 0 0 0\n    outer loop\n";
+      for [x, y, z] in face {
+        text += &format!("      vertex {x} {y} {z}\n");
+      }
+      text += "    endloop\n  endfacet\n";
+    }
+    text + "endsolid tetra\n"
+  }
+
+  fn binary(faces: &[[[f32; 3]; 3]]) -> Vec<u8> {
+    let mut bytes = b"solid but actually binary".to_vec();
+    bytes.resize(80, 0);
+    bytes.extend((faces.len() as u32).to_le_bytes());
+    for face in faces {
+      bytes.extend([0.0f32; 3].iter().flat_map(|x| x.to_le_bytes()));
+      bytes.extend(face.iter().flatten().flat_map(|x| x.to_le_bytes()));
+      bytes.extend(0u16.to_le_bytes());
+    }
+    bytes
+  }
+
+  fn check_tetrahedron(mesh: &Mesh) {
+    assert_eq!(mesh.len(), 4);
+    let dist: f64 = mesh.call(Vector([0.1; 3]));
+    assert!((dist + 0.1).abs() < 1e-12);
+    let dist: f64 = mesh.call(Vector([-1.0, 0.0, 0.0]));
+    assert!((dist - 1.0).abs() < 1e-12);
+  }
+
+  #[test]
+  fn reads_ascii() {
+    check_tetrahedron(&read_stl(ascii(&TETRAHEDRON).as_bytes()).unwrap());
+  }
+
+  #[test]
+  fn reads_binary() {
+    check_tetrahedron(&read_stl(binary(&TETRAHEDRON).as_slice()).unwrap());
+  }
+
+  #[test]
+  fn reads_several_solids() {
+    let text = ascii(&TETRAHEDRON[..2]) + &ascii(&TETRAHEDRON[2..]);
+    check_tetrahedron(&read_stl(text.as_bytes()).unwrap());
+  }
+
+  #[test]
+  fn rejects_sliver_closed() {
+    // Splits an edge of one face, closing the gap with a collinear sliver
+    let [o, x, mid, z] = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.5, 0.0, 0.0], [0.0, 0.0, 1.0]];
+    let faces = [
+      TETRAHEDRON[0],
+      [o, mid, z],
+      [mid, x, z],
+      TETRAHEDRON[2],
+      TETRAHEDRON[3],
+      [o, x, mid],
+    ];
+    let sliver = read_stl(ascii(&faces).as_bytes());
+    assert!(matches!(sliver, Err(LoadError::Open { count: 3, .. })));
+  }
+
+  /// Checks the ascii `text` is rejected with a parse error at `line`
+  fn check_parse_error(text: &str, line: usize) {
+    match read_stl(text.as_bytes()) {
+      Err(LoadError::Parse(message)) => {
+        assert!(message.starts_with(&format!("line {line}:")), "{message}")
+      }
+      Err(err) => panic!("expected a parse error at line {line}, found {err}"),
+      Ok(_) => panic!("expected a parse error at line {line}"),
+    }
+  }
+
+  #[test]
+  fn rejects_malformed_facets() {
+    let text = ascii(&TETRAHEDRON);
+    // A stray vertex between facets, and before the first facet
+    check_parse_error(&text.replacen("endfacet\n", "endfacet\nvertex 0 0 0\n", 1), 9);
+    check_parse_error(&text.replacen("  facet", "vertex 0 0 0\n  facet", 1), 2);
+    // A vertex after the loop has ended
+    check_parse_error(&text.replacen("endloop\n", "endloop\nvertex 0 0 0\n", 1), 8);
+    // A vertex before the loop has started
+    check_parse_error(&text.replacen("    outer", "vertex 0 0 0\n    outer", 1), 3);
+    // Too many and too few vertices in a loop
+    let four = text.replacen("vertex 1 0 0", "vertex 1 0 0\nvertex 0 0 2", 1);
+    check_parse_error(&four, 7);
+    let two = text.replacen("      vertex 0 1 0\n", "", 1);
+    check_parse_error(&two, 6);
+    // Blocks out of order
+    check_parse_error(&text.replacen("    outer loop\n", "", 1), 3);
+    check_parse_error(&text.replacen("  endfacet\n", "", 1), 8);
+    // A facet cut off by the end of the file
+    let cut = &text[..text.find("endloop").unwrap()];
+    assert!(matches!(read_stl(cut.as_bytes()), Err(LoadError::Parse(_))));
+  }
+
+  #[test]
+  fn rejects_invalid() {
+    let open = read_stl(ascii(&TETRAHEDRON[..3]).as_bytes());
+    assert!(matches!(open, Err(LoadError::Open { count: 3, .. })));
+
+    let mut flipped = TETRAHEDRON;
+    flipped[3].swap(0, 1);
+    let flipped = read_stl(binary(&flipped).as_slice());
+    assert!(matches!(flipped, Err(LoadError::Inconsistent { .. })));
+
+    let mut fin = TETRAHEDRON.to_vec();
+    fin.push([[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, -1.0, 0.0]]);
+    fin.push([[1.0, 0.0, 0.0], [0.0, 0.0, 0.0], [0.0, -1.0, 0.0]]);
+    let fin = read_stl(ascii(&fin).as_bytes());
+    assert!(matches!(fin, Err(LoadError::NonManifold { .. })));
+
+    let text = ascii(&TETRAHEDRON).replace("vertex 1 0 0", "vertex 1 zero 0");
+    match read_stl(text.as_bytes()) {
+      Err(LoadError::Parse(message)) => assert!(message.starts_with("line 6:"), "{message}"),
+      _ => panic!("expected a parse error"),
+    }
+    assert!(matches!(
+      read_stl(&[0xff; 10][..]),
+      Err(LoadError::Parse(_))
+    ));
+    assert!(matches!(
+      read_stl(&b"solid\nendsolid"[..]),
+      Err(LoadError::Empty(_))
+    ));
+  }
+}
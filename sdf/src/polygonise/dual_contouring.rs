@@ -0,0 +1,801 @@
+use super::{sheets, TriMesh};
+use crate::traits::SdfGrad;
+use marchrs_vectors::{Aabb, Matrix, Vector};
+use rayon::iter::{
+  IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator, ParallelIterator,
+};
+use std::array;
+use std::collections::{HashMap, HashSet};
+use std::ops::Add;
+
+/// Steps taken to find where the surface crosses each edge
+const EDGE_STEPS: usize = 16;
+/// Directions the QEF barely constrains, with an eigenvalue below this\
+/// fraction of the largest (i.e. a singular value below a tenth),\
+/// are left at the mass point of the crossings.
+const QEF_TOLERANCE: f64 = 0.01;
+/// The 4 cells around an edge along `axis`, anticlockwise around it,\
+/// as offsets along `(axis + 1) % 3` and `(axis + 2) % 3`
+const AROUND: [(usize, usize); 4] = [(0, 0), (1, 0), (1, 1), (0, 1)];
+
+/// Where the surface crosses each edge of the finest cells, as `(point, normal)`,\
+/// keyed by the edge's lower corner and axis
+type Hermite = HashMap<([usize; 3], usize), (Vector<3>, Vector<3>)>;
+
+/// Extracts the surface of `model` within `bounds` with dual contouring,\
+/// on an adaptive octree subdivided up to `depth` times around the surface.
+///
+/// Each leaf cell the surface passes through gets a vertex, placed by\
+/// solving a QEF (quadratic error function) to lie as close as possible\
+/// to the tangent planes where the surface crosses the cell's edges,\
+/// given by `SdfGrad::call_grad`. Unlike marching cubes, this places\
+/// vertices on sharp edges and corners, so models built from `Cube`s,\
+/// `Cuboid`s and `Intersect`ions keep their shape.
+///
+/// Cells are only subdivided when `call` at their centre is within\
+/// their radius, so empty space stays coarse. This relies on `model`\
+/// never overestimating the distance to the surface. The tree is then\
+/// collapsed from the bottom up, merging 8 cells into their parent\
+/// when one vertex fits all their tangent planes to within `tolerance`\
+/// (as the root of the summed squared distances), and doing so keeps the\
+/// topology of the surface, as in Ju et al., "Dual Contouring of Hermite\
+/// Data". So flat faces get a few large triangles, while curved ones\
+/// keep the finest cells. A `tolerance` of `0.0` never merges.
+///
+/// The finest cells get a vertex for each sheet of the surface through\
+/// them, as in `marching_cubes`, and where two sheets meet across a face\
+/// (i.e. near thin or sharp features) their quads are split around\
+/// an extra vertex, so the mesh is closed and manifold.
+///
+/// Panics if `depth` is `0`, as a single cell has no edges inside `bounds`\
+/// to join vertices around, or `usize::BITS` or more, as the cells couldn't be indexed.
+pub fn dual_contouring(
+  model: &(impl SdfGrad<3> + Sync),
+  bounds: Aabb<3>,
+  depth: u32,
+  tolerance: f64,
+) -> TriMesh {
+  assert!(depth >= 1, "need at least 1 level to join the cells");
+  assert!(
+    depth < usize::BITS,
+    "need fewer than {} levels to index the cells\ndepth = {depth}",
+    usize::BITS
+  );
+  let size = bounds.size() / (1usize << depth) as f64;
+  let position = |idx: [usize; 3]| bounds.min + size * Vector::from(idx);
+
+  // Each level is stored as indexes at that level's resolution
+  let mut cells = vec![[0; 3]];
+  for level in 1..=depth {
+    let scale = 1usize << (depth - level);
+    let radius = (size * scale as f64).mag() * 0.5;
+    cells = cells
+      .into_par_iter()
+      .flat_map_iter(|cell| (0..8).map(move |c| corner(cell.map(|i| i * 2), c)))
+      .filter(|&cell| {
+        let centre = position(cell.map(|i| i * scale)) + size * (scale as f64 * 0.5);
+        model.call(centre).abs() <= radius * (1.0 + 1e-9)
+      })
+      .collect();
+  }
+
+  let corners: HashSet<_> = cells
+    .iter()
+    .flat_map(|&cell| (0..8).map(move |c| corner(cell, c)))
+    .collect();
+  let values: HashMap<_, _> = corners
+    .into_par_iter()
+    .map(|idx| (idx, model.call(position(idx))))
+    .collect();
+  // Merged cells can have corners no finest cell shares, sampled as needed
+  let inside = |idx: [usize; 3]| match values.get(&idx) {
+    Some(&value) => value <= 0.0,
+    None => model.call(position(idx)) <= 0.0,
+  };
+  // Takes `cell` at the level with `scale` finest cells along each side
+  let config = |cell: [usize; 3], scale: usize| {
+    (0..8).fold(0, |config, c| {
+      config | usize::from(inside(corner(cell, c).map(|i| i * scale))) << c
+    })
+  };
+
+  let configs: Vec<_> = cells.par_iter().map(|&cell| config(cell, 1)).collect();
+  let edges: HashSet<_> = cells
+    .iter()
+    .zip(&configs)
+    .flat_map(|(&cell, &config)| {
+      sheets(config)
+        .iter()
+        .flatten()
+        .map(move |&edge| edge_key(cell, edge))
+    })
+    .collect();
+  let hermite: Hermite = edges
+    .into_par_iter()
+    .map(|(start, axis)| {
+      let mut end = start;
+      end[axis] += 1;
+      let (from, to) = (position(start), position(end));
+      let point = crossing(model, (from, values[&start]), (to, values[&end]));
+      (
+        (start, axis),
+        (point, normal(model, point, size.min() * 1e-6)),
+      )
+    })
+    .collect();
+
+  // Corners of the surface can poke into a neighbouring cell without\
+  // crossing its edges, so let vertices stray a little to reach them
+  let cell_bounds = |cell: [usize; 3], scale: usize| {
+    let (min, max) = (cell.map(|i| i * scale), corner(cell, 7).map(|i| i * scale));
+    Aabb::new(position(min) - size * 0.5, position(max) + size * 0.5)
+  };
+  let mut nodes: HashMap<_, _> = cells
+    .into_par_iter()
+    .zip(configs)
+    .filter(|&(_, config)| config != 0 && config != 255)
+    .map(|(cell, config)| {
+      let mut sheet = [0; 24];
+      let qefs: Vec<_> = sheets(config)
+        .iter()
+        .enumerate()
+        .map(|(i, ring)| {
+          ring.iter().fold(Qef::default(), |qef, &edge| {
+            sheet[usize::from(edge)] = i as u8;
+            qef.add(hermite[&edge_key(cell, edge)])
+          })
+        })
+        .collect();
+      let bounds = cell_bounds(cell, 1);
+      let leaf = Leaf {
+        config,
+        sheet,
+        vertices: qefs.iter().map(|qef| qef.solve(bounds)).collect(),
+        first: 0,
+        qef: (qefs.len() == 1 && !ambiguous(config)).then(|| qefs[0]),
+      };
+      (cell, Node::Leaf(leaf))
+    })
+    .collect();
+
+  // Ju et al.'s checks that merging keeps the topology: the merged cell\
+  // has one sheet, and the sign at each corner of its children (i.e. at\
+  // the midpoints of its edges and faces, and its centre) matches at\
+  // least one corner of the edge, face or cell it's in the middle of
+  let keeps_topology = |cell: [usize; 3], scale: usize, config: usize| {
+    let half = scale / 2;
+    sheets(config).len() == 1
+      && !ambiguous(config)
+      && (0..27).all(|o: usize| {
+        let o = [o % 3, o / 3 % 3, o / 9];
+        let sign = inside(array::from_fn(|i| cell[i] * scale + o[i] * half));
+        (0..8)
+          .filter(|c| (0..3).all(|i| o[i] == 1 || c >> i & 1 == o[i] / 2))
+          .any(|c| (config >> c & 1 == 1) == sign)
+      })
+  };
+  let merge = |cell: [usize; 3], scale: usize, children: [Node; 8]| {
+    if tolerance <= 0.0 || !children.iter().all(Node::mergeable) {
+      return Node::Branch(Box::new(children));
+    }
+    let config = config(cell, scale);
+    if !keeps_topology(cell, scale, config) {
+      return Node::Branch(Box::new(children));
+    }
+    let qef = children
+      .iter()
+      .filter_map(|child| match child {
+        Node::Leaf(leaf) => leaf.qef,
+        _ => None,
+      })
+      .fold(Qef::default(), Add::add);
+    let vertex = qef.solve(cell_bounds(cell, scale));
+    if qef.error(vertex) > tolerance * tolerance {
+      return Node::Branch(Box::new(children));
+    }
+    Node::Leaf(Leaf {
+      config,
+      sheet: [0; 24],
+      vertices: vec![vertex],
+      first: 0,
+      qef: Some(qef),
+    })
+  };
+  for level in (0..depth).rev() {
+    let mut parents = HashMap::new();
+    for (cell, node) in nodes {
+      let children = parents
+        .entry(cell.map(|i| i / 2))
+        .or_insert_with(|| array::from_fn(|_| Node::Empty));
+      children[(0..3).fold(0, |c, i| c | (cell[i] & 1) << i)] = node;
+    }
+    let scale = 1usize << (depth - level);
+    nodes = parents
+      .into_par_iter()
+      .map(|(cell, children)| (cell, merge(cell, scale, children)))
+      .collect();
+  }
+
+  let mut root = nodes.remove(&[0; 3]).unwrap_or(Node::Empty);
+  let mut vertices = vec![];
+  root.number(&mut vertices);
+  let mut contour = Contour {
+    depth,
+    hermite: &hermite,
+    vertices,
+    triangles: vec![],
+    splits: HashMap::new(),
+  };
+  contour.cell(Cell {
+    node: &root,
+    level: 0,
+    idx: [0; 3],
+  });
+  TriMesh::new(model, contour.vertices, contour.triangles)
+}
+
+/// A node of the octree, where empty cells don't touch the surface
+enum Node {
+  Empty,
+  Leaf(Leaf),
+  Branch(Box<[Node; 8]>),
+}
+
+/// A cell the surface passes through, with a vertex for each sheet
+struct Leaf {
+  /// The inside corners, numbered as in `marching_cubes`
+  config: usize,
+  /// The sheet crossing each edge, numbered as in `marching_cubes`
+  sheet: [u8; 24],
+  vertices: Vec<Vector<3>>,
+  /// The index of the first vertex in the mesh
+  first: usize,
+  /// The QEF of the whole cell, if it can be merged into its parent
+  qef: Option<Qef>,
+}
+
+impl Node {
+  /// Whether the node can be merged into its parent
+  fn mergeable(&self) -> bool {
+    match self {
+      Node::Empty => true,
+      Node::Leaf(leaf) => leaf.qef.is_some(),
+      Node::Branch(_) => false,
+    }
+  }
+
+  /// Adds the vertices of every leaf to `vertices`, in depth first order.
+  fn number(&mut self, vertices: &mut Vec<Vector<3>>) {
+    match self {
+      Node::Empty => {}
+      Node::Leaf(leaf) => {
+        leaf.first = vertices.len();
+        vertices.extend(&leaf.vertices);
+      }
+      Node::Branch(children) => children.iter_mut().for_each(|child| child.number(vertices)),
+    }
+  }
+}
+
+/// A node of the octree, with its level and index at that level
+#[derive(Clone, Copy)]
+struct Cell<'a> {
+  node: &'a Node,
+  level: u32,
+  idx: [usize; 3],
+}
+
+impl Cell<'_> {
+  /// Child `c` of a branch, or the cell itself if it's not split any further
+  fn child(self, c: usize) -> Self {
+    match self.node {
+      Node::Branch(children) => Cell {
+        node: &children[c],
+        level: self.level + 1,
+        idx: corner(self.idx.map(|i| i * 2), c),
+      },
+      _ => self,
+    }
+  }
+}
+
+/// Joins the vertices of the octree into triangles, visiting each\
+/// minimal edge (i.e. not containing a smaller one) once, with the\
+/// cell, face and edge procedures of Ju et al.
+struct Contour<'a> {
+  depth: u32,
+  hermite: &'a Hermite,
+  vertices: Vec<Vector<3>>,
+  triangles: Vec<[usize; 3]>,
+  /// Vertices splitting sheets that meet across a face, keyed by the\
+  /// inside corner they wrap around and the axis of the face
+  splits: HashMap<([usize; 3], usize), usize>,
+}
+
+impl Contour<'_> {
+  fn cell(&mut self, cell: Cell) {
+    if let Node::Branch(_) = cell.node {
+      let block = array::from_fn(|c| cell.child(c));
+      block.iter().for_each(|&child| self.cell(child));
+      self.block(block, &[0, 1, 2], &[0, 1, 2]);
+    }
+  }
+
+  /// Visits the faces perpendicular to `faces` and the edges along `edges`\
+  /// between the 8 cells of `block`, which meet in the middle.
+  fn block(&mut self, block: [Cell; 8], faces: &[usize], edges: &[usize]) {
+    for &axis in faces {
+      for c in (0..8).filter(|c| c >> axis & 1 == 0) {
+        self.face([block[c], block[c | 1 << axis]], axis);
+      }
+    }
+    for &axis in edges {
+      let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+      for side in 0..2 {
+        self.edge(
+          AROUND.map(|(du, dv)| block[side << axis | du << u | dv << v]),
+          axis,
+        );
+      }
+    }
+  }
+
+  /// Visits the face between `pair`, the first lower along `axis`.
+  fn face(&mut self, pair: [Cell; 2], axis: usize) {
+    let leaves = pair
+      .iter()
+      .all(|cell| !matches!(cell.node, Node::Branch(_)));
+    if leaves || pair.iter().any(|cell| matches!(cell.node, Node::Empty)) {
+      return;
+    }
+    let block = array::from_fn(|c| match c >> axis & 1 {
+      0 => pair[0].child(c | 1 << axis),
+      _ => pair[1].child(c & !(1 << axis)),
+    });
+    self.block(block, &[axis], &[(axis + 1) % 3, (axis + 2) % 3]);
+  }
+
+  /// Visits the edge along `axis` between the cells `around` it.
+  fn edge(&mut self, around: [Cell; 4], axis: usize) {
+    if around.iter().any(|cell| matches!(cell.node, Node::Empty)) {
+      return;
+    }
+    if around.iter().all(|cell| matches!(cell.node, Node::Leaf(_))) {
+      return self.polygon(around, axis);
+    }
+    let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+    let block = array::from_fn(|c| {
+      let (du, dv) = (c >> u & 1, c >> v & 1);
+      let q = AROUND.iter().position(|&q| q == (du, dv)).unwrap();
+      around[q].child(c & 1 << axis | (1 - du) << u | (1 - dv) << v)
+    });
+    self.block(block, &[], &[axis]);
+  }
+
+  /// Joins the vertices of the leaves `around` an edge along `axis`,\
+  /// if the surface crosses it.
+  fn polygon(&mut self, around: [Cell; 4], axis: usize) {
+    let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+    let leaves = around.map(|cell| match cell.node {
+      Node::Leaf(leaf) => leaf,
+      _ => unreachable!(),
+    });
+    // The edge in each cell, where the smallest cell's is all the 4 share
+    let edges = AROUND.map(|(du, dv)| axis * 8 + ((1 - du) << u | (1 - dv) << v));
+    let q = (0..4).max_by_key(|&q| around[q].level).unwrap();
+    let corner_inside = |c: usize| leaves[q].config >> c & 1 == 1;
+    let lower = edges[q] % 8;
+    let inside = corner_inside(lower);
+    if inside == corner_inside(lower | 1 << axis) {
+      return;
+    }
+
+    let mut polygon = vec![];
+    let mut centre = None;
+    for q in 0..4 {
+      let vertex = leaves[q].first + usize::from(leaves[q].sheet[edges[q]]);
+      if polygon.last() != Some(&vertex) {
+        polygon.push(vertex);
+      }
+      let next = (q + 1) % 4;
+      let w = if AROUND[q].0 != AROUND[next].0 { u } else { v };
+      let finest = [q, next].map(|q| around[q].level == self.depth);
+      if finest == [true; 2] {
+        let pair = [(around[q].idx, leaves[q]), (around[next].idx, leaves[next])];
+        if let Some(split) = self.split(pair, edges[q], axis, w) {
+          centre = Some(split);
+          polygon.push(split);
+        }
+      }
+    }
+    if polygon.len() > 1 && polygon.first() == polygon.last() {
+      polygon.pop();
+    }
+    if polygon.len() < 3 {
+      return;
+    }
+
+    // Anticlockwise around `axis`, so facing towards the outside
+    if !inside {
+      polygon.reverse();
+    }
+    match (polygon.as_slice(), centre) {
+      // Split along the shorter diagonal
+      (&[a, b, c, d], None) => {
+        let dist = |i: usize, j: usize| (self.vertices[i] - self.vertices[j]).mag2();
+        match dist(a, c) <= dist(b, d) {
+          true => self.triangles.extend([[a, b, c], [a, c, d]]),
+          false => self.triangles.extend([[a, b, d], [b, c, d]]),
+        }
+      }
+      _ => {
+        let centre = centre.unwrap_or(polygon[0]);
+        let start = polygon.iter().position(|&vertex| vertex == centre).unwrap();
+        polygon.rotate_left(start);
+        let fan = polygon[1..].windows(2);
+        self
+          .triangles
+          .extend(fan.map(|pair| [centre, pair[0], pair[1]]));
+      }
+    }
+  }
+
+  /// The vertex splitting the sheets of two neighbouring finest leaves, as\
+  /// `(index, leaf)`, across their face perpendicular to `w`, next to `edge`.
+  ///
+  /// When the face is ambiguous, the surface crosses it twice, and if\
+  /// both leaves join the two crossings into one sheet, their vertices\
+  /// would be joined on both sides of the face, pinching the mesh.\
+  /// So the quads around each inside corner of the face go through\
+  /// a vertex between its crossings instead.
+  fn split(
+    &mut self,
+    [(idx, a), (_, b)]: [([usize; 3], &Leaf); 2],
+    edge: usize,
+    axis: usize,
+    w: usize,
+  ) -> Option<usize> {
+    let t = 3 - axis - w;
+    let inside = |c: usize| a.config >> c & 1 == 1;
+    let lower = edge % 8;
+    let x = if inside(lower) {
+      lower
+    } else {
+      lower | 1 << axis
+    };
+    let y = x ^ (1 << axis | 1 << t);
+    if !inside(y) || inside(x ^ 1 << t) {
+      return None;
+    }
+    let far = axis * 8 + (y & !(1 << axis));
+    let joined = |leaf: &Leaf, flip: usize| leaf.sheet[edge ^ flip] == leaf.sheet[far ^ flip];
+    if !joined(a, 0) || !joined(b, 1 << w) {
+      return None;
+    }
+
+    let hermite = self.hermite;
+    let vertices = &mut self.vertices;
+    let split = self.splits.entry((corner(idx, x), w)).or_insert_with(|| {
+      let crossing = |axis: usize| hermite[&(corner(idx, x & !(1 << axis)), axis)].0;
+      vertices.push((crossing(axis) + crossing(t)) * 0.5);
+      vertices.len() - 1
+    });
+    Some(*split)
+  }
+}
+
+/// The sum of squared distances to a set of tangent planes, as\
+/// `xᵀAᵀAx - 2xᵀAᵀb + bᵀb`, with their mass point
+#[derive(Clone, Copy, Debug)]
+struct Qef {
+  ata: Matrix<3>,
+  atb: Vector<3>,
+  btb: f64,
+  /// The sum of the points on the planes, and how many there are
+  mass: Vector<3>,
+  count: usize,
+}
+
+impl Default for Qef {
+  fn default() -> Self {
+    Self {
+      ata: Matrix::zeros(),
+      atb: Vector::zeros(),
+      btb: 0.0,
+      mass: Vector::zeros(),
+      count: 0,
+    }
+  }
+}
+
+impl Qef {
+  /// Adds the plane through `point` with `normal`.
+  fn add(mut self, (point, normal): (Vector<3>, Vector<3>)) -> Self {
+    self.mass = self.mass + point;
+    self.count += 1;
+    if normal.is_finite() {
+      let offset = normal.dot(point);
+      for i in 0..3 {
+        for j in 0..3 {
+          self.ata[i][j] += normal[i] * normal[j];
+        }
+      }
+      self.atb = self.atb + normal * offset;
+      self.btb += offset * offset;
+    }
+    self
+  }
+
+  /// Finds the point closest to all the planes, measured from their\
+  /// mass point and clamped to within `cell`.
+  ///
+  /// Solves with the pseudo-inverse, so that directions the planes don't\
+  /// constrain (i.e. along a flat face or an edge) stay at the mass point.
+  fn solve(&self, cell: Aabb<3>) -> Vector<3> {
+    let mass = self.mass * (self.count as f64).recip();
+    let (values, vecs) = self.ata.sym_eigen();
+    let cutoff = values.max() * QEF_TOLERANCE;
+    let local = vecs.transpose() * (self.atb - self.ata * mass);
+    let local = Vector(array::from_fn(|i| match values[i] > cutoff {
+      true => local[i] / values[i],
+      false => 0.0,
+    }));
+    (mass + vecs * local).clamp(cell.min, cell.max)
+  }
+
+  /// The sum of squared distances from `point` to the planes
+  fn error(&self, point: Vector<3>) -> f64 {
+    point.dot(self.ata * point) - 2.0 * point.dot(self.atb) + self.btb
+  }
+}
+
+impl Add for Qef {
+  type Output = Self;
+
+  fn add(self, rhs: Self) -> Self {
+    Self {
+      ata: self.ata + rhs.ata,
+      atb: self.atb + rhs.atb,
+      btb: self.btb + rhs.btb,
+      mass: self.mass + rhs.mass,
+      count: self.count + rhs.count,
+    }
+  }
+}
+
+/// Whether any face of a cell with `config` is ambiguous, i.e. has\
+/// its inside corners diagonally opposite
+fn ambiguous(config: usize) -> bool {
+  let inside = |c: usize| config >> c & 1 == 1;
+  (0..3).any(|axis| {
+    let (u, v) = (1 << ((axis + 1) % 3), 1 << ((axis + 2) % 3));
+    (0..2).map(|side| side << axis).any(|c| {
+      inside(c) == inside(c | u | v) && inside(c | u) == inside(c | v) && inside(c) != inside(c | u)
+    })
+  })
+}
+
+/// The lower corner and axis of `edge` of `cell`, numbered as in `marching_cubes`
+#[inline]
+fn edge_key(cell: [usize; 3], edge: u8) -> ([usize; 3], usize) {
+  (corner(cell, usize::from(edge % 8)), usize::from(edge / 8))
+}
+
+/// Corner `c` of `cell`, at `(c & 1, c >> 1 & 1, c >> 2 & 1)` within it
+#[inline]
+fn corner(cell: [usize; 3], c: usize) -> [usize; 3] {
+  array::from_fn(|i| cell[i] + (c >> i & 1))
+}
+
+/// Finds where the surface crosses between `from` and `to`,\
+/// given the value of `model` at each, with opposite signs.
+///
+/// Uses the Illinois variant of false position, as distances\
+/// are close to linear, but the ends shouldn't get stuck.
+fn crossing(
+  model: &impl SdfGrad<3>,
+  (mut from, mut lo): (Vector<3>, f64),
+  (mut to, mut hi): (Vector<3>, f64),
+) -> Vector<3> {
+  let mut kept = None;
+  for _ in 0..EDGE_STEPS {
+    let point = from.lerp(to, lo / (lo - hi));
+    let value = model.call(point);
+    if value == 0.0 {
+      return point;
+    }
+    if (value <= 0.0) == (lo <= 0.0) {
+      (from, lo) = (point, value);
+      if kept == Some(false) {
+        hi *= 0.5;
+      }
+      kept = Some(false);
+    } else {
+      (to, hi) = (point, value);
+      if kept == Some(true) {
+        lo *= 0.5;
+      }
+      kept = Some(true);
+    }
+  }
+  from.lerp(to, lo / (lo - hi))
+}
+
+/// The surface normal at `point`, from `SdfGrad::call_grad`.
+///
+/// Crossings often lie exactly on the surface, where the analytic\
+/// gradient of some items is undefined (i.e. the length of a zero\
+/// vector), so we fall back to central differences with `step`.
+fn normal(model: &impl SdfGrad<3>, point: Vector<3>, step: f64) -> Vector<3> {
+  let grad = model.call_grad(point).1;
+  if grad.is_finite() {
+    return grad;
+  }
+  Vector(array::from_fn(|i| {
+    let offset = Vector::axis(i) * step;
+    model.call(point + offset) - model.call(point - offset)
+  }))
+  .normal()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::items::{Cuboid, Sphere};
+  use crate::polygonise::{check_closed, marching_cubes};
+  use crate::traits::{DualFn, Sdf, SdfIntersect, SdfRotate3D, SdfScale};
+  use marchrs_vectors::{Dual, GridIter, Scalar};
+
+  /// Whether some vertex is within `tol` of `point`
+  fn has_vertex(mesh: &TriMesh, point: Vector<3>, tol: f64) -> bool {
+    mesh
+      .vertices
+      .iter()
+      .any(|&vertex| (vertex - point).mag() < tol)
+  }
+
+  #[test]
+  fn cube_corners() {
+    let cube = Cuboid::<3>::default();
+    let bounds = Aabb::new([-0.83, -0.81, -0.79], [0.79, 0.8, 0.82]);
+    let mesh = dual_contouring(&cube, bounds, 4, 1e-6);
+    check_closed(&mesh);
+    for &vertex in &mesh.vertices {
+      let dist: f64 = cube.call(vertex);
+      assert!(dist.abs() < 1e-9, "{vertex:?} is {dist} from the surface");
+    }
+    let corners = || GridIter::<3>::new(Vector([-0.5; 3])..=Vector([0.5; 3]), [2; 3]);
+    for corner in corners() {
+      assert!(has_vertex(&mesh, corner, 1e-9), "missing {corner:?}");
+    }
+
+    // Marching cubes can only place vertices on cell edges
+    let rounded = marching_cubes(&cube, bounds, [17; 3]);
+    assert!(corners().all(|c| !has_vertex(&rounded, c, 1e-3)));
+  }
+
+  #[test]
+  fn rotated_cube() {
+    let rot = Matrix::<3>::rotation([1.0, 2.0, 3.0], 0.6);
+    let cube = Cuboid::<3>::default().rot([1.0, 2.0, 3.0], 0.6);
+    let bounds = Aabb::new([-0.93, -0.91, -0.89], [0.89, 0.9, 0.92]);
+    let mesh = dual_contouring(&cube, bounds, 5, 1e-6);
+    check_closed(&mesh);
+    let cell = bounds.size().max() / 32.0;
+    // Vertices splitting pinched faces lie between two crossings
+    for &vertex in &mesh.vertices {
+      let dist: f64 = cube.call(vertex);
+      assert!(
+        dist.abs() < cell * 0.1,
+        "{vertex:?} is {dist} from the surface"
+      );
+    }
+    for corner in GridIter::<3>::new(Vector([-0.5; 3])..=Vector([0.5; 3]), [2; 3]) {
+      let corner = rot * corner;
+      assert!(has_vertex(&mesh, corner, 1e-6), "missing {corner:?}");
+    }
+  }
+
+  #[test]
+  fn merges_flat_faces() {
+    let bounds = Aabb::new([-0.83, -0.81, -0.79], [0.79, 0.8, 0.82]);
+    let cube = Cuboid::<3>::default();
+    let merged = dual_contouring(&cube, bounds, 5, 1e-6);
+    let uniform = dual_contouring(&cube, bounds, 5, 0.0);
+    let sphere = dual_contouring(&Sphere, bounds, 5, 1e-6);
+    check_closed(&merged);
+    check_closed(&uniform);
+    assert!(merged.triangles.len() * 4 < uniform.triangles.len());
+    assert!(merged.triangles.len() * 4 < sphere.triangles.len());
+    for &vertex in &merged.vertices {
+      let dist: f64 = cube.call(vertex);
+      assert!(dist.abs() < 1e-9, "{vertex:?} is {dist} from the surface");
+    }
+  }
+
+  #[test]
+  fn splits_pinched_faces() {
+    // Trilinear between points on a grid, where the cells at `[1, 1, 1]`\
+    // and `[2, 1, 1]` each join both crossings of the face between them
+    let inside = [
+      [1, 1, 1],
+      [1, 2, 1],
+      [2, 2, 1],
+      [1, 1, 2],
+      [2, 1, 2],
+      [3, 1, 1],
+      [3, 2, 1],
+      [3, 1, 2],
+    ];
+    let value = |point: [usize; 3]| match inside.contains(&point) {
+      true => -0.1,
+      false => 0.07,
+    };
+    let model = DualFn(|pos: [Dual<3>; 3]| {
+      let cell = pos.map(|x| (x.value.floor() as usize).min(3));
+      let t: [_; 3] = array::from_fn(|i| pos[i] - Dual::constant(cell[i] as f64));
+      (0..8).fold(Dual::ZERO, |sum, c| {
+        let weight = (0..3).fold(Dual::ONE, |weight, i| match c >> i & 1 {
+          0 => weight * (Dual::ONE - t[i]),
+          _ => weight * t[i],
+        });
+        sum + weight * Dual::constant(value(corner(cell, c)))
+      })
+    });
+    let bounds = Aabb::new([0.0; 3], [4.0; 3]);
+    for tolerance in [0.0, 1e-3] {
+      let mesh = dual_contouring(&model, bounds, 2, tolerance);
+      check_closed(&mesh);
+      // Each inside corner of the face gets a vertex between its crossings
+      let on_face = mesh
+        .vertices
+        .iter()
+        .filter(|vertex| (vertex[0] - 2.0).abs() < 1e-9);
+      assert_eq!(on_face.count(), 2);
+    }
+  }
+
+  #[test]
+  #[should_panic(expected = "need fewer than")]
+  fn rejects_deep_trees() {
+    dual_contouring(&Sphere, Aabb::new([-1.0; 3], [1.0; 3]), usize::BITS, 0.0);
+  }
+
+  #[test]
+  #[should_panic(expected = "need at least 1 level")]
+  fn rejects_empty_trees() {
+    dual_contouring(&Sphere, Aabb::new([-1.0; 3], [1.0; 3]), 0, 0.0);
+  }
+
+  #[test]
+  fn sphere_and_intersection() {
+    let sphere = Sphere.scale([1.6; 3]);
+    let bounds = Aabb::new([-1.03, -0.97, -1.01], [0.99, 1.02, 0.98]);
+    let mesh = dual_contouring(&sphere, bounds, 5, 1e-6);
+    check_closed(&mesh);
+    for (&pos, &normal) in mesh.vertices.iter().zip(&mesh.normals) {
+      assert!(
+        (pos.mag() - 0.8).abs() < 5e-3,
+        "{pos:?} isn't on the surface"
+      );
+      assert!((normal - pos.normal()).mag() < 1e-6);
+    }
+    for &[a, b, c] in &mesh.triangles {
+      let [a, b, c] = [a, b, c].map(|i| mesh.vertices[i]);
+      assert!((b - a).cross(c - a).dot(a + b + c) > 0.0, "facing inwards");
+    }
+
+    let model = Cuboid::<3>::default().and(sphere);
+    let mesh = dual_contouring(&model, bounds, 5, 1e-6);
+    check_closed(&mesh);
+    // Tangent planes along the curved edges miss by a fraction of a cell
+    let cell = bounds.size().max() / 32.0;
+    for &vertex in &mesh.vertices {
+      let dist: f64 = model.call(vertex);
+      assert!(
+        dist.abs() < cell * 0.25,
+        "{vertex:?} is {dist} from the surface"
+      );
+    }
+  }
+}
@@ -0,0 +1,223 @@
+use super::TriMesh;
+use crate::traits::SdfGrad;
+use marchrs_iterators::{Grid, MultiDims, MultiRangeIter};
+use marchrs_vectors::{Aabb, GridIter, Vector};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use std::array;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Extracts the surface of `model` within `bounds` with marching cubes,\
+/// sampling `model` at `dims` evenly spaced points along each axis.
+///
+/// Vertices are shared between neighbouring cells, and ambiguous faces\
+/// always separate the inside corners, so the mesh is closed wherever\
+/// the surface lies within `bounds`. Sharp edges are rounded off, as\
+/// vertices can only be placed on the edges of each cell.
+pub fn marching_cubes(
+  model: &(impl SdfGrad<3> + Sync),
+  bounds: Aabb<3>,
+  dims: impl Into<MultiDims<3>>,
+) -> TriMesh {
+  let dims: MultiDims<3> = dims.into();
+  assert!(
+    dims.iter().all(|&dim| dim >= 2),
+    "need at least 2 samples along each axis\ndimensions = {dims:?}"
+  );
+  let points = GridIter::new(bounds.min..=bounds.max, dims);
+  let values = Grid::from_vec(
+    dims,
+    points.into_par_iter().map(|pos| model.call(pos)).collect(),
+  );
+  let step = bounds.size() / (Vector::from(dims.0) - 1.0);
+  let position = |idx: [usize; 3]| bounds.min + step * Vector::from(idx);
+
+  let mut vertices = vec![];
+  // The vertex on each edge, keyed by its lower point and axis
+  let mut edges = HashMap::new();
+  let mut triangles = vec![];
+  let cells = MultiDims::from(dims.0.map(|dim| dim - 1));
+  for cell in MultiRangeIter::from(cells) {
+    let corner = |c: u8| array::from_fn(|i| cell[i] + usize::from(c >> i & 1));
+    let config = (0..8).fold(0, |config, c| {
+      config | usize::from(values[corner(c)] <= 0.0) << c
+    });
+    for triangle in &table()[config] {
+      triangles.push(triangle.map(|edge| {
+        let (start, axis) = (corner(edge % 8), usize::from(edge / 8));
+        *edges.entry((start, axis)).or_insert_with(|| {
+          let mut end = start;
+          end[axis] += 1;
+          let (from, to) = (values[start], values[end]);
+          vertices.push(position(start).lerp(position(end), from / (from - to)));
+          vertices.len() - 1
+        })
+      }));
+    }
+  }
+  TriMesh::new(model, vertices, triangles)
+}
+
+/// The triangles for each configuration of inside corners, where\
+/// corner `c` is at `(c & 1, c >> 1 & 1, c >> 2 & 1)` in the cell and\
+/// inside if bit `c` is set. Edges are written as `axis * 8 + corner`,\
+/// for the lower corner along the edge.
+fn table() -> &'static [Vec<[u8; 3]>] {
+  static TABLE: OnceLock<Vec<Vec<[u8; 3]>>> = OnceLock::new();
+  TABLE.get_or_init(|| {
+    let fans = |ring: &Vec<u8>| -> Vec<_> {
+      let fan = ring[1..].windows(2);
+      fan.map(|pair| [ring[0], pair[0], pair[1]]).collect()
+    };
+    (0..256)
+      .map(|config| sheets(config).iter().flat_map(fans).collect())
+      .collect()
+  })
+}
+
+/// The sheets of the surface through a cell, for each configuration\
+/// of inside corners, as loops of edges numbered like `table`.
+pub(super) fn sheets(config: usize) -> &'static [Vec<u8>] {
+  static SHEETS: OnceLock<Vec<Vec<Vec<u8>>>> = OnceLock::new();
+  &SHEETS.get_or_init(|| (0..256).map(trace_sheets).collect())[config]
+}
+
+/// Traces the sheets of a configuration of inside corners.
+///
+/// Walking anticlockwise around each face (viewed from outside), the\
+/// surface runs from each edge entering the inside to the next edge\
+/// leaving it. Following these around the cell gives a loop for each\
+/// sheet of the surface, which `table` then splits into a fan of triangles.
+fn trace_sheets(config: usize) -> Vec<Vec<u8>> {
+  let inside = |c: u8| config >> c & 1 == 1;
+  let edge = |from: u8, to: u8| (from ^ to).trailing_zeros() as u8 * 8 + (from & to);
+
+  let mut next = [None; 24];
+  for (axis, side) in (0..3).flat_map(|axis| [(axis, 0), (axis, 1)]) {
+    let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+    let mut cycle = [(0, 0), (1, 0), (1, 1), (0, 1)];
+    if side == 0 {
+      cycle.reverse();
+    }
+    let corners = cycle.map(|(cu, cv)| side << axis | cu << u | cv << v);
+    let crossings: Vec<_> = (0..4)
+      .map(|i| (corners[i], corners[(i + 1) % 4]))
+      .filter(|&(from, to)| inside(from) != inside(to))
+      .map(|(from, to)| (edge(from, to), inside(to)))
+      .collect();
+    for (i, &(edge, enters)) in crossings.iter().enumerate() {
+      if enters {
+        next[usize::from(edge)] = Some(crossings[(i + 1) % crossings.len()].0);
+      }
+    }
+  }
+
+  let mut rings = vec![];
+  let mut visited = [false; 24];
+  for start in 0..24 {
+    if visited[usize::from(start)] || next[usize::from(start)].is_none() {
+      continue;
+    }
+    let mut ring = vec![];
+    let mut edge = start;
+    while !visited[usize::from(edge)] {
+      visited[usize::from(edge)] = true;
+      ring.push(edge);
+      edge = next[usize::from(edge)].unwrap();
+    }
+    rings.push(ring);
+  }
+  rings
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::items::{Mesh, Sphere};
+  use crate::loaders::read_obj;
+  use crate::polygonise::check_closed;
+  use crate::traits::{Sdf, SdfScale, SdfTranslate, SdfUnion, SdfWithInfo};
+
+  #[test]
+  fn table_uses_crossing_edges() {
+    assert!(table()[0].is_empty() && table()[255].is_empty());
+    for config in 0..256 {
+      let mut used: Vec<_> = table()[config].iter().flatten().copied().collect();
+      used.sort();
+      used.dedup();
+      let crossing: Vec<_> = (0..3u8)
+        .flat_map(|axis| (0..8u8).map(move |c| (axis, c)))
+        .filter(|&(axis, c)| c >> axis & 1 == 0)
+        .filter(|&(axis, c)| config >> c & 1 != config >> (c | 1 << axis) & 1)
+        .map(|(axis, c)| axis * 8 + c)
+        .collect();
+      assert_eq!(used, crossing, "config {config:08b}");
+    }
+  }
+
+  #[test]
+  fn sphere() {
+    let sphere = Sphere.scale([1.6; 3]);
+    let bounds = Aabb::new([-1.03, -0.97, -1.01], [0.99, 1.02, 0.98]);
+    let mesh = marching_cubes(&sphere, bounds, [21, 19, 23]);
+    assert!(!mesh.triangles.is_empty());
+    check_closed(&mesh);
+
+    for (&pos, &normal) in mesh.vertices.iter().zip(&mesh.normals) {
+      assert!(
+        (pos.mag() - 0.8).abs() < 0.01,
+        "{pos:?} isn't on the surface"
+      );
+      assert!((normal - pos.normal()).mag() < 1e-6);
+    }
+    for &[a, b, c] in &mesh.triangles {
+      let [a, b, c] = [a, b, c].map(|i| mesh.vertices[i]);
+      assert!((b - a).cross(c - a).dot(a + b + c) > 0.0, "facing inwards");
+    }
+  }
+
+  #[test]
+  fn ambiguous_faces() {
+    // A gyroid, clipped by a sphere, has plenty of saddles
+    let gyroid = |[x, y, z]: [f64; 3]| {
+      let (x, y, z) = (x * 7.0, y * 7.0, z * 7.0);
+      let gyroid = x.sin() * y.cos() + y.sin() * z.cos() + z.sin() * x.cos();
+      (gyroid * 0.1).max(Vector([x, y, z]).mag() / 7.0 - 0.9)
+    };
+    let bounds = Aabb::new([-1.01, -0.98, -1.02], [1.03, 0.99, 1.0]);
+    let mesh = marching_cubes(&gyroid, bounds, [31, 29, 33]);
+    assert!(mesh.triangles.len() > 1000);
+    check_closed(&mesh);
+  }
+
+  #[test]
+  fn infos() {
+    let model = Sphere
+      .translate([-0.6, 0.0, 0.0])
+      .with::<char>('a')
+      .or(Sphere.translate([0.6, 0.0, 0.0]).with::<char>('b'));
+    let bounds = Aabb::new([-1.12, -0.61, -0.59], [1.13, 0.62, 0.6]);
+    let mesh = marching_cubes(&model, bounds, [25, 15, 15]).with_info(&model);
+    assert_eq!(mesh.infos.len(), mesh.vertices.len());
+    for (pos, info) in mesh.vertices.iter().zip(mesh.infos) {
+      assert_eq!(info, if pos[0] < 0.0 { 'a' } else { 'b' });
+    }
+  }
+
+  #[test]
+  fn exports_obj() {
+    let bounds = Aabb::new([-0.61, -0.62, -0.59], [0.6, 0.63, 0.58]);
+    let mesh = marching_cubes(&Sphere, bounds, [13; 3]);
+    let mut bytes = vec![];
+    mesh.write_obj(&mut bytes).unwrap();
+
+    let loaded = read_obj(bytes.as_slice()).unwrap().into_groups();
+    let direct = Mesh::from_indexed(&mesh.vertices, mesh.triangles.iter().copied());
+    assert_eq!(loaded[0].mesh.len(), direct.len());
+    for pos in GridIter::<3>::new(bounds.min..=bounds.max, [5; 3]) {
+      let dist: f64 = direct.call(pos);
+      assert!((loaded[0].mesh.call(pos) - dist).abs() < 1e-9);
+      assert!((dist - Sphere.call(pos)).abs() < 0.02);
+    }
+  }
+}
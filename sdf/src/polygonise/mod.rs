@@ -0,0 +1,87 @@
+//! Extracting triangle meshes from the surface of models, i.e. for export
+mod dual_contouring;
+pub use dual_contouring::*;
+mod marching_cubes;
+pub use marching_cubes::*;
+
+use crate::traits::{SdfGrad, SdfInfo};
+use marchrs_vectors::Vector;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::io::{self, Write};
+
+/// An indexed triangle mesh, approximating the surface of a model.
+///
+/// Triangles index into `vertices`, and are wound anticlockwise\
+/// when viewed from outside. `normals` holds the gradient of the\
+/// model at each vertex, and `infos` is only filled by `with_info`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TriMesh<I = ()> {
+  pub vertices: Vec<Vector<3>>,
+  pub normals: Vec<Vector<3>>,
+  pub infos: Vec<I>,
+  pub triangles: Vec<[usize; 3]>,
+}
+
+impl TriMesh {
+  /// Wraps `vertices` and `triangles`, finding normals from `model` in parallel.
+  fn new(
+    model: &(impl SdfGrad<3> + Sync),
+    vertices: Vec<Vector<3>>,
+    triangles: Vec<[usize; 3]>,
+  ) -> Self {
+    let normals = vertices.par_iter().map(|&pos| model.grad(pos)).collect();
+    Self {
+      vertices,
+      normals,
+      infos: vec![],
+      triangles,
+    }
+  }
+}
+
+impl<I> TriMesh<I> {
+  /// Attaches the info from `model` to every vertex, fetched in parallel.
+  pub fn with_info<J: Send>(self, model: &(impl SdfInfo<3, Info = J> + Sync)) -> TriMesh<J> {
+    let infos = self
+      .vertices
+      .par_iter()
+      .map(|&pos| model.info(pos))
+      .collect();
+    TriMesh {
+      vertices: self.vertices,
+      normals: self.normals,
+      infos,
+      triangles: self.triangles,
+    }
+  }
+
+  /// Writes the vertices, normals and triangles in the OBJ format.
+  pub fn write_obj(&self, mut writer: impl Write) -> io::Result<()> {
+    for Vector([x, y, z]) in &self.vertices {
+      writeln!(writer, "v {x} {y} {z}")?;
+    }
+    for Vector([x, y, z]) in &self.normals {
+      writeln!(writer, "vn {x} {y} {z}")?;
+    }
+    // OBJ indices start at 1
+    for [a, b, c] in self.triangles.iter().map(|tri| tri.map(|i| i + 1)) {
+      writeln!(writer, "f {a}//{a} {b}//{b} {c}//{c}")?;
+    }
+    Ok(())
+  }
+}
+
+/// Checks every edge is used exactly once in each direction,\
+/// i.e. the mesh is closed, manifold and consistently wound.
+#[cfg(test)]
+fn check_closed(mesh: &TriMesh<impl Sized>) {
+  let mut edges = std::collections::HashSet::new();
+  for &[a, b, c] in &mesh.triangles {
+    for edge in [(a, b), (b, c), (c, a)] {
+      assert!(edges.insert(edge), "{edge:?} used twice");
+    }
+  }
+  for &(a, b) in &edges {
+    assert!(edges.contains(&(b, a)), "{:?} has one face", (a, b));
+  }
+}
@@ -0,0 +1,124 @@
+use super::{Sdf, SdfGrad, SdfInfo};
+use marchrs_vectors::{Dual, Scalar, Vector};
+
+/// Calls the `SDF` with dual numbers, finding the exact gradient.
+///
+/// This works for any item generic over `Scalar`, so analytical\
+/// gradients only need to be hand-written where they're cheaper.
+#[inline]
+pub fn call_dual<const N: usize, S: Scalar>(
+  sdf: &(impl Sdf<N, Dual<N, S>> + ?Sized),
+  pos: Vector<N, S>,
+) -> (S, Vector<N, S>) {
+  let dist = sdf.call(Dual::variables(pos));
+  (dist.value, dist.grad.normal())
+}
+
+pub trait SdfAutoGrad<const N: usize>: Sdf<N> + Sized {
+  /// Computes gradients for the whole model with dual numbers,\
+  /// rather than combining the gradients of its items.
+  fn auto_grad(self) -> AutoGrad<Self> {
+    AutoGrad(self)
+  }
+}
+
+impl<const N: usize, T: Sdf<N>> SdfAutoGrad<N> for T {}
+
+/// An `SDF` item with exact gradients from automatic differentiation.
+#[derive(Clone, Copy, Default, PartialEq)]
+pub struct AutoGrad<T>(pub T);
+
+impl<const N: usize, S: Scalar, T: Sdf<N, S>> Sdf<N, S> for AutoGrad<T> {
+  #[inline]
+  fn call(&self, pos: Vector<N, S>) -> S {
+    self.0.call(pos)
+  }
+
+  #[inline]
+  fn hits(&self, pos: Vector<N, S>) -> bool {
+    self.0.hits(pos)
+  }
+}
+
+impl<const N: usize, S: Scalar, T: SdfInfo<N, S>> SdfInfo<N, S> for AutoGrad<T> {
+  type Info = T::Info;
+  #[inline]
+  fn call_info(&self, pos: Vector<N, S>) -> (S, Self::Info) {
+    self.0.call_info(pos)
+  }
+}
+
+impl<const N: usize, S: Scalar, T: Sdf<N, S> + Sdf<N, Dual<N, S>>> SdfGrad<N, S> for AutoGrad<T> {
+  #[inline]
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
+    call_dual(&self.0, pos)
+  }
+}
+
+/// A closure `SDF` written over dual numbers, with exact gradients.
+///
+/// Closures can't be generic, so this evaluates the closure with\
+/// constant duals when only the distance is needed, e.g.
+/// ```
+/// # use marchrs_sdf::traits::{DualFn, SdfGrad};
+/// # use marchrs_vectors::{Dual, Scalar};
+/// let sphere = DualFn(|[x, y]: [Dual<2>; 2]| (x * x + y * y).sqrt() - Dual::ONE);
+/// assert_eq!(sphere.grad([0.0, 2.0].into()), [0.0, 1.0].into());
+/// ```
+#[derive(Clone, Copy, Default, PartialEq)]
+pub struct DualFn<F>(pub F);
+
+impl<const N: usize, S: Scalar, F> Sdf<N, S> for DualFn<F>
+where
+  F: Fn([Dual<N, S>; N]) -> Dual<N, S> + Send + Sync,
+{
+  #[inline]
+  fn call(&self, pos: Vector<N, S>) -> S {
+    (self.0)(pos.0.map(Dual::constant)).value
+  }
+}
+
+impl<const N: usize, S: Scalar, F> SdfGrad<N, S> for DualFn<F>
+where
+  F: Fn([Dual<N, S>; N]) -> Dual<N, S> + Send + Sync,
+{
+  #[inline]
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
+    let dist = (self.0)(Dual::variables(pos).0);
+    (dist.value, dist.grad.normal())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::items::{CapCylinder, Cube, Cylinder, Sphere};
+  use crate::traits::{SdfScale, SdfTranslate};
+  use marchrs_vectors::GridIter;
+
+  #[test]
+  fn matches_analytic() {
+    let model = Sphere.scale([2.0; 3]).translate([0.1, 0.2, 0.3]);
+    let auto = model.auto_grad();
+    for pos in GridIter::<3>::new(Vector([-1.0; 3])..=Vector([1.0; 3]), [6; 3]) {
+      let (dist, grad) = model.call_grad(pos);
+      let (auto_dist, auto_grad) = auto.call_grad(pos);
+      assert_eq!(dist, auto_dist);
+      assert!(
+        (grad - auto_grad).mag() < 1e-12,
+        "{grad:?} != {auto_grad:?}"
+      );
+    }
+  }
+
+  #[test]
+  fn primitive_grads() {
+    let pos = Vector([0.3, 0.4, 7.0]);
+    assert_eq!(Cylinder::<3>::default().grad(pos), Vector([0.6, 0.8, 0.0]));
+    assert_eq!(
+      CapCylinder::<3>::default().grad(pos),
+      Vector([0.0, 0.0, 1.0])
+    );
+    assert_eq!(Cube::<3>::default().grad(-pos), Vector([0.0, 0.0, -1.0]));
+  }
+}
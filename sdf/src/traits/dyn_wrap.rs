@@ -1,10 +1,9 @@
-use std::rc::Rc;
-
 use super::{Sdf, SdfFull, SdfGrad, SdfInfo};
-use marchrs_vectors::Vector;
+use marchrs_vectors::{Scalar, Vector};
+use std::sync::Arc;
 
-pub trait SdfDynWrap<const N: usize>: SdfFull<N> + Sized + 'static {
-  /// Wraps the model in an `Rc<dyn ...>` to make typing easier.
+pub trait SdfDynWrap<const N: usize>: SdfFull<N> + Send + Sync + Sized + 'static {
+  /// Wraps the model in an `Arc<dyn ...>` to make typing easier.
   ///
   /// Effectively, this erases all the compound types from a SDF model,\
   /// turning `WithInfo<Scale<Sphere<3>>, bool>` into `DynWrap<3, bool>`,\
@@ -13,45 +12,83 @@ pub trait SdfDynWrap<const N: usize>: SdfFull<N> + Sized + 'static {
   /// This also sort of has the benefit of allowing `PartialEq` on things\
   /// that don't typically support it, i.e. closures.
   fn wrap(self) -> DynModel<N, Self::Info> {
-    DynModel(Rc::new(self))
+    DynModel(Arc::new(self))
   }
 }
 
-impl<const N: usize, T: SdfFull<N> + 'static> SdfDynWrap<N> for T {}
+impl<const N: usize, T: SdfFull<N> + Send + Sync + 'static> SdfDynWrap<N> for T {}
 
-/// A model in a dynamic `Rc<dyn ...>` wrapper.
+/// A model in a dynamic `Arc<dyn ...>` wrapper,\
+/// which can be shared between threads to render in parallel.
+///
+/// Executors share one render closure between all their workers,\
+/// so anything it captures (i.e. the model) must be `Send + Sync`,\
+/// which an `Rc` never is, even when rendering on a single thread.
 #[derive(Clone)]
-pub struct DynModel<const N: usize, I>(Rc<dyn SdfFull<N, Info = I>>);
+pub struct DynModel<const N: usize, I, S: Scalar = f64>(
+  Arc<dyn SdfFull<N, S, Info = I> + Send + Sync>,
+);
+
+impl<const N: usize, I, S: Scalar> DynModel<N, I, S> {
+  /// Wraps a model evaluated with any scalar type.\
+  /// For `f64` models, `SdfDynWrap::wrap` is more convenient.
+  pub fn new(model: impl SdfFull<N, S, Info = I> + Send + Sync + 'static) -> Self {
+    DynModel(Arc::new(model))
+  }
+}
 
-impl<const N: usize, I> PartialEq for DynModel<N, I> {
+impl<const N: usize, I, S: Scalar> PartialEq for DynModel<N, I, S> {
   fn eq(&self, other: &Self) -> bool {
-    Rc::ptr_eq(&self.0, &other.0)
+    Arc::ptr_eq(&self.0, &other.0)
   }
 }
 
-impl<const N: usize, I> Sdf<N> for DynModel<N, I> {
+impl<const N: usize, I, S: Scalar> Sdf<N, S> for DynModel<N, I, S> {
   #[inline]
-  fn call(&self, pos: Vector<N>) -> f64 {
+  fn call(&self, pos: Vector<N, S>) -> S {
     self.0.call(pos)
   }
 
   #[inline]
-  fn hits(&self, pos: Vector<N>) -> bool {
+  fn hits(&self, pos: Vector<N, S>) -> bool {
     self.0.hits(pos)
   }
 }
 
-impl<const N: usize, I> SdfInfo<N> for DynModel<N, I> {
+impl<const N: usize, I, S: Scalar> SdfInfo<N, S> for DynModel<N, I, S> {
   type Info = I;
   #[inline]
-  fn call_info(&self, pos: Vector<N>) -> (f64, Self::Info) {
+  fn call_info(&self, pos: Vector<N, S>) -> (S, Self::Info) {
     self.0.call_info(pos)
   }
 }
 
-impl<const N: usize, I> SdfGrad<N> for DynModel<N, I> {
+impl<const N: usize, I, S: Scalar> SdfGrad<N, S> for DynModel<N, I, S> {
   #[inline]
-  fn call_grad(&self, pos: Vector<N>) -> (f64, Vector<N>) {
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
     self.0.call_grad(pos)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{items::Sphere, traits::SdfWithInfo};
+  use std::thread;
+
+  #[test]
+  fn shares_between_threads() {
+    let model = Sphere.with(1).wrap();
+    let dists: Vec<_> = thread::scope(|scope| {
+      let handles: Vec<_> = (0..4)
+        .map(|i| {
+          let model = &model;
+          scope.spawn(move || model.call_info(Vector([i as f64, 0.0, 0.0])))
+        })
+        .collect();
+      handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+    assert_eq!(dists, [(-0.5, 1), (0.5, 1), (1.5, 1), (2.5, 1)]);
+    assert!(model == model.clone());
+  }
+}
@@ -0,0 +1,345 @@
+use super::{
+  AutoGrad, DualFn, DynModel, Intersect, Invert, Remove, Rotate2D, Rotate3D, Round, Scale, Sdf,
+  SdfGrad, SdfNoInfo, SdfWithDefault, SmoothUnion, Transform, Translate, Union, WithDefault,
+  WithInfo,
+};
+use marchrs_vectors::{Affine, Scalar, Vector};
+
+/// An optimisation pass over a model,\
+/// fusing chains of `Translate`, `Scale`, `Rotate2D`, `Rotate3D`\
+/// and `Transform` wrappers into a single `Transform`.
+///
+/// This means deeply nested models only pay for one matrix multiply\
+/// per chain of wrappers, rather than one operation per wrapper.
+pub trait SdfFuse<const N: usize>: Sdf<N> + Sized {
+  /// The model after fusion
+  type Fused: Sdf<N>;
+  /// The model beneath any outer affine wrappers, after fusion
+  type Inner: Sdf<N>;
+
+  /// Fuses all chains of affine wrappers within the model.
+  fn fuse(self) -> Self::Fused;
+
+  /// Fuses the model, pulling any outer affine wrappers into a `Transform`.
+  fn fuse_transform(self) -> Transform<N, Self::Inner>;
+}
+
+/// Items can't contain affine wrappers, so are unchanged by fusion.
+impl<const N: usize, T: SdfNoInfo<N>> SdfFuse<N> for T {
+  type Fused = Self;
+  type Inner = Self;
+
+  fn fuse(self) -> Self::Fused {
+    self
+  }
+
+  fn fuse_transform(self) -> Transform<N, Self::Inner> {
+    Transform::new(self, Affine::identity())
+  }
+}
+
+/// A model that's left unchanged by fusion.
+///
+/// Closures can't implement `SdfNoInfo` without also gaining `SdfInfo`,\
+/// so are wrapped in this to take part in fusion, e.g.
+/// ```
+/// # use marchrs_sdf::traits::{Leaf, SdfFuse, SdfTranslate};
+/// let model = Leaf(|[x, y]: [f64; 2]| x.abs() + y.abs() - 1.0).translate([1.0, 0.0]);
+/// let fused = model.fuse();
+/// ```
+#[derive(Clone, Copy, Default, PartialEq)]
+pub struct Leaf<T>(pub T);
+
+impl<const N: usize, S: Scalar, T: Sdf<N, S>> Sdf<N, S> for Leaf<T> {
+  #[inline]
+  fn call(&self, pos: Vector<N, S>) -> S {
+    self.0.call(pos)
+  }
+
+  #[inline]
+  fn hits(&self, pos: Vector<N, S>) -> bool {
+    self.0.hits(pos)
+  }
+}
+
+impl<const N: usize, S: Scalar, T: SdfGrad<N, S>> SdfGrad<N, S> for Leaf<T> {
+  #[inline]
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
+    self.0.call_grad(pos)
+  }
+}
+
+impl<const N: usize, T: Sdf<N>> SdfFuse<N> for Leaf<T> {
+  type Fused = Self;
+  type Inner = Self;
+
+  fn fuse(self) -> Self::Fused {
+    self
+  }
+
+  fn fuse_transform(self) -> Transform<N, Self::Inner> {
+    Transform::new(self, Affine::identity())
+  }
+}
+
+impl<const N: usize, F> SdfFuse<N> for DualFn<F>
+where
+  Self: Sdf<N>,
+{
+  type Fused = Self;
+  type Inner = Self;
+
+  fn fuse(self) -> Self::Fused {
+    self
+  }
+
+  fn fuse_transform(self) -> Transform<N, Self::Inner> {
+    Transform::new(self, Affine::identity())
+  }
+}
+
+impl<const N: usize, I> SdfFuse<N> for DynModel<N, I> {
+  type Fused = Self;
+  type Inner = Self;
+
+  fn fuse(self) -> Self::Fused {
+    self
+  }
+
+  fn fuse_transform(self) -> Transform<N, Self::Inner> {
+    Transform::new(self, Affine::identity())
+  }
+}
+
+// Combinators fuse their children, but act as a barrier to fusion themselves.
+
+impl<const N: usize, T: SdfFuse<N>, U: SdfFuse<N>> SdfFuse<N> for Union<T, U> {
+  type Fused = Union<T::Fused, U::Fused>;
+  type Inner = Self::Fused;
+
+  fn fuse(self) -> Self::Fused {
+    Union(self.0.fuse(), self.1.fuse())
+  }
+
+  fn fuse_transform(self) -> Transform<N, Self::Inner> {
+    Transform::new(self.fuse(), Affine::identity())
+  }
+}
+
+impl<const N: usize, T: SdfFuse<N>, U: SdfFuse<N>> SdfFuse<N> for Intersect<T, U> {
+  type Fused = Intersect<T::Fused, U::Fused>;
+  type Inner = Self::Fused;
+
+  fn fuse(self) -> Self::Fused {
+    Intersect(self.0.fuse(), self.1.fuse())
+  }
+
+  fn fuse_transform(self) -> Transform<N, Self::Inner> {
+    Transform::new(self.fuse(), Affine::identity())
+  }
+}
+
+impl<const N: usize, T: SdfFuse<N>, U: SdfFuse<N>> SdfFuse<N> for Remove<T, U> {
+  type Fused = Remove<T::Fused, U::Fused>;
+  type Inner = Self::Fused;
+
+  fn fuse(self) -> Self::Fused {
+    Remove(self.0.fuse(), self.1.fuse())
+  }
+
+  fn fuse_transform(self) -> Transform<N, Self::Inner> {
+    Transform::new(self.fuse(), Affine::identity())
+  }
+}
+
+impl<const N: usize, T: SdfFuse<N>, U: SdfFuse<N>> SdfFuse<N> for SmoothUnion<T, U> {
+  type Fused = SmoothUnion<T::Fused, U::Fused>;
+  type Inner = Self::Fused;
+
+  fn fuse(self) -> Self::Fused {
+    SmoothUnion(self.0.fuse(), self.1.fuse(), self.2)
+  }
+
+  fn fuse_transform(self) -> Transform<N, Self::Inner> {
+    Transform::new(self.fuse(), Affine::identity())
+  }
+}
+
+impl<const N: usize, T: SdfFuse<N>> SdfFuse<N> for Invert<T> {
+  type Fused = Invert<T::Fused>;
+  type Inner = Self::Fused;
+
+  fn fuse(self) -> Self::Fused {
+    Invert(self.0.fuse())
+  }
+
+  fn fuse_transform(self) -> Transform<N, Self::Inner> {
+    Transform::new(self.fuse(), Affine::identity())
+  }
+}
+
+impl<const N: usize, T: SdfFuse<N>> SdfFuse<N> for Round<N, T> {
+  type Fused = Round<N, T::Fused>;
+  type Inner = Self::Fused;
+
+  fn fuse(self) -> Self::Fused {
+    Round(self.0.fuse(), self.1)
+  }
+
+  fn fuse_transform(self) -> Transform<N, Self::Inner> {
+    Transform::new(self.fuse(), Affine::identity())
+  }
+}
+
+impl<const N: usize, T: SdfFuse<N>, I> SdfFuse<N> for WithInfo<T, I> {
+  type Fused = WithInfo<T::Fused, I>;
+  type Inner = Self::Fused;
+
+  fn fuse(self) -> Self::Fused {
+    WithInfo(self.0.fuse(), self.1)
+  }
+
+  fn fuse_transform(self) -> Transform<N, Self::Inner> {
+    Transform::new(self.fuse(), Affine::identity())
+  }
+}
+
+impl<const N: usize, T: SdfFuse<N>, I> SdfFuse<N> for WithDefault<T, I> {
+  type Fused = WithDefault<T::Fused, I>;
+  type Inner = Self::Fused;
+
+  fn fuse(self) -> Self::Fused {
+    self.0.fuse().with_default()
+  }
+
+  fn fuse_transform(self) -> Transform<N, Self::Inner> {
+    Transform::new(self.fuse(), Affine::identity())
+  }
+}
+
+impl<const N: usize, T: SdfFuse<N>> SdfFuse<N> for AutoGrad<T> {
+  type Fused = AutoGrad<T::Fused>;
+  type Inner = Self::Fused;
+
+  fn fuse(self) -> Self::Fused {
+    AutoGrad(self.0.fuse())
+  }
+
+  fn fuse_transform(self) -> Transform<N, Self::Inner> {
+    Transform::new(self.fuse(), Affine::identity())
+  }
+}
+
+// Affine wrappers are pulled into the `Transform` of their child.
+
+impl<const N: usize, T: SdfFuse<N>> SdfFuse<N> for Translate<N, T> {
+  type Fused = Transform<N, T::Inner>;
+  type Inner = T::Inner;
+
+  fn fuse(self) -> Self::Fused {
+    self.fuse_transform()
+  }
+
+  fn fuse_transform(self) -> Transform<N, Self::Inner> {
+    let affine = self.affine();
+    self.0.fuse_transform().transform(affine)
+  }
+}
+
+impl<const N: usize, T: SdfFuse<N>> SdfFuse<N> for Scale<N, T> {
+  type Fused = Transform<N, T::Inner>;
+  type Inner = T::Inner;
+
+  fn fuse(self) -> Self::Fused {
+    self.fuse_transform()
+  }
+
+  fn fuse_transform(self) -> Transform<N, Self::Inner> {
+    let affine = self.affine();
+    self.0.fuse_transform().transform(affine)
+  }
+}
+
+impl<T: SdfFuse<2>> SdfFuse<2> for Rotate2D<T> {
+  type Fused = Transform<2, T::Inner>;
+  type Inner = T::Inner;
+
+  fn fuse(self) -> Self::Fused {
+    self.fuse_transform()
+  }
+
+  fn fuse_transform(self) -> Transform<2, Self::Inner> {
+    let affine = self.affine();
+    self.0.fuse_transform().transform(affine)
+  }
+}
+
+impl<T: SdfFuse<3>> SdfFuse<3> for Rotate3D<T> {
+  type Fused = Transform<3, T::Inner>;
+  type Inner = T::Inner;
+
+  fn fuse(self) -> Self::Fused {
+    self.fuse_transform()
+  }
+
+  fn fuse_transform(self) -> Transform<3, Self::Inner> {
+    let affine = self.affine();
+    self.0.fuse_transform().transform(affine)
+  }
+}
+
+impl<const N: usize, T: SdfFuse<N>> SdfFuse<N> for Transform<N, T> {
+  type Fused = Transform<N, T::Inner>;
+  type Inner = T::Inner;
+
+  fn fuse(self) -> Self::Fused {
+    self.fuse_transform()
+  }
+
+  fn fuse_transform(self) -> Transform<N, Self::Inner> {
+    let affine = self.affine();
+    self.0.fuse_transform().transform(affine)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::items::{Capsule, Cuboid, Sphere};
+  use crate::traits::{SdfRotate3D, SdfScale, SdfTranslate, SdfUnion};
+  use marchrs_vectors::{GridIter, Vector};
+  use std::f64::consts::PI;
+
+  #[test]
+  fn fused_matches_nested() {
+    let nested = Cuboid::default()
+      .scale([0.2; 3])
+      .rot([1.0, 0.0, 0.0], PI / 3.0)
+      .translate([0.1, -0.2, 0.3])
+      .or(Sphere.translate([0.0, 0.5, 0.0]).scale([0.5; 3]))
+      .or(Leaf(|[x, y, z]: [f64; 3]| x.abs() + y.abs() + z.abs() - 0.3).translate([0.5; 3]));
+    let fused = nested.fuse();
+
+    for pos in GridIter::<3>::new(Vector([-1.0; 3])..=Vector([1.0; 3]), [7; 3]) {
+      assert!((nested.call(pos) - fused.call(pos)).abs() < 1e-9);
+    }
+  }
+
+  #[test]
+  fn fused_grads_match_nested() {
+    let nested = Capsule::<3>::default()
+      .scale([0.5, 1.5, 1.0])
+      .rot([1.0, 0.0, 1.0], PI / 5.0)
+      .translate([0.1, -0.2, 0.3]);
+    let fused = nested.fuse();
+
+    let range = Vector([-0.93, -0.87, -0.97])..=Vector([0.91, 0.89, 0.83]);
+    for pos in GridIter::<3>::new(range, [7; 3]) {
+      let (grad, fused_grad) = (nested.grad(pos), fused.grad(pos));
+      assert!(
+        (grad - fused_grad).mag() < 1e-9,
+        "{grad:?} != {fused_grad:?} at {pos:?}"
+      );
+    }
+  }
+}
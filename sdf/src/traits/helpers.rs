@@ -1,12 +1,12 @@
 use super::{Sdf, SdfInfo};
-use marchrs_vectors::Vector;
+use marchrs_vectors::{Scalar, Vector};
 
 /// A simple implementation of `SdfInfo` for the unit type `()`
 pub trait SdfNoInfo<const N: usize>: Sdf<N> {}
 
-impl<const N: usize, T: SdfNoInfo<N>> SdfInfo<N> for T {
+impl<const N: usize, S: Scalar, T: SdfNoInfo<N> + Sdf<N, S>> SdfInfo<N, S> for T {
   type Info = ();
-  fn call_info(&self, pos: Vector<N>) -> (f64, Self::Info) {
+  fn call_info(&self, pos: Vector<N, S>) -> (S, Self::Info) {
     (self.call(pos), ())
   }
 }
@@ -1,5 +1,5 @@
-use super::{Sdf, SdfGrad, SdfInfo};
-use marchrs_vectors::Vector;
+use super::{Sdf, SdfBounds, SdfGrad, SdfInfo};
+use marchrs_vectors::{Aabb, Scalar, Vector};
 use std::marker::PhantomData;
 
 pub trait SdfWithInfo<const N: usize>: Sdf<N> + Sized {
@@ -13,34 +13,34 @@ impl<const N: usize, T: Sdf<N>> SdfWithInfo<N> for T {}
 #[derive(Clone, Copy, Default, PartialEq)]
 pub struct WithInfo<T, I>(pub T, pub I);
 
-impl<const N: usize, I, T: Sdf<N>> Sdf<N> for WithInfo<T, I> {
+impl<const N: usize, S: Scalar, I, T: Sdf<N, S>> Sdf<N, S> for WithInfo<T, I> {
   #[inline]
-  fn call(&self, pos: Vector<N>) -> f64 {
+  fn call(&self, pos: Vector<N, S>) -> S {
     self.0.call(pos)
   }
 
   #[inline]
-  fn hits(&self, pos: Vector<N>) -> bool {
+  fn hits(&self, pos: Vector<N, S>) -> bool {
     self.0.hits(pos)
   }
 }
 
-impl<const N: usize, I: Clone, T: Sdf<N>> SdfInfo<N> for WithInfo<T, I> {
+impl<const N: usize, S: Scalar, I: Clone, T: Sdf<N, S>> SdfInfo<N, S> for WithInfo<T, I> {
   type Info = I;
   #[inline]
-  fn call_info(&self, pos: Vector<N>) -> (f64, Self::Info) {
+  fn call_info(&self, pos: Vector<N, S>) -> (S, Self::Info) {
     (self.0.call(pos), self.1.clone())
   }
 
   #[inline]
-  fn info(&self, _: Vector<N>) -> Self::Info {
+  fn info(&self, _: Vector<N, S>) -> Self::Info {
     self.1.clone()
   }
 }
 
-impl<const N: usize, I, T: SdfGrad<N>> SdfGrad<N> for WithInfo<T, I> {
+impl<const N: usize, S: Scalar, I, T: SdfGrad<N, S>> SdfGrad<N, S> for WithInfo<T, I> {
   #[inline]
-  fn call_grad(&self, pos: Vector<N>) -> (f64, Vector<N>) {
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
     self.0.call_grad(pos)
   }
 }
@@ -56,33 +56,45 @@ impl<const N: usize, T: Sdf<N>> SdfWithDefault<N> for T {}
 #[derive(Clone, Copy, Default, PartialEq)]
 pub struct WithDefault<T, I>(pub T, PhantomData<I>);
 
-impl<const N: usize, I, T: Sdf<N>> Sdf<N> for WithDefault<T, I> {
+impl<const N: usize, S: Scalar, I, T: Sdf<N, S>> Sdf<N, S> for WithDefault<T, I> {
   #[inline]
-  fn call(&self, pos: Vector<N>) -> f64 {
+  fn call(&self, pos: Vector<N, S>) -> S {
     self.0.call(pos)
   }
 
   #[inline]
-  fn hits(&self, pos: Vector<N>) -> bool {
+  fn hits(&self, pos: Vector<N, S>) -> bool {
     self.0.hits(pos)
   }
 }
 
-impl<const N: usize, I: Default, T: Sdf<N>> SdfInfo<N> for WithDefault<T, I> {
+impl<const N: usize, S: Scalar, I: Default, T: Sdf<N, S>> SdfInfo<N, S> for WithDefault<T, I> {
   type Info = I;
   #[inline]
-  fn call_info(&self, pos: Vector<N>) -> (f64, Self::Info) {
+  fn call_info(&self, pos: Vector<N, S>) -> (S, Self::Info) {
     (self.0.call(pos), I::default())
   }
 
   #[inline]
-  fn info(&self, _: Vector<N>) -> Self::Info {
+  fn info(&self, _: Vector<N, S>) -> Self::Info {
     I::default()
   }
 }
 
-impl<const N: usize, I, T: SdfGrad<N>> SdfGrad<N> for WithDefault<T, I> {
-  fn call_grad(&self, pos: Vector<N>) -> (f64, Vector<N>) {
+impl<const N: usize, S: Scalar, I, T: SdfGrad<N, S>> SdfGrad<N, S> for WithDefault<T, I> {
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
     self.0.call_grad(pos)
   }
 }
+
+impl<const N: usize, I, T: SdfBounds<N>> SdfBounds<N> for WithInfo<T, I> {
+  fn bounds(&self) -> Aabb<N> {
+    self.0.bounds()
+  }
+}
+
+impl<const N: usize, I, T: SdfBounds<N>> SdfBounds<N> for WithDefault<T, I> {
+  fn bounds(&self) -> Aabb<N> {
+    self.0.bounds()
+  }
+}
@@ -1,5 +1,5 @@
-use super::{Sdf, SdfGrad, SdfInfo};
-use marchrs_vectors::Vector;
+use super::{Sdf, SdfBounds, SdfGrad, SdfInfo};
+use marchrs_vectors::{Aabb, Scalar, Vector};
 
 pub trait SdfIntersect<const N: usize>: Sdf<N> + Sized {
   fn and<S: Sdf<N>>(self, other: S) -> Intersect<Self, S> {
@@ -12,23 +12,25 @@ impl<const N: usize, T: Sdf<N>> SdfIntersect<N> for T {}
 #[derive(Clone, Copy, Default, PartialEq)]
 pub struct Intersect<T, U>(pub T, pub U);
 
-impl<const N: usize, T: Sdf<N>, U: Sdf<N>> Sdf<N> for Intersect<T, U> {
+impl<const N: usize, S: Scalar, T: Sdf<N, S>, U: Sdf<N, S>> Sdf<N, S> for Intersect<T, U> {
   #[inline]
-  fn call(&self, pos: Vector<N>) -> f64 {
+  fn call(&self, pos: Vector<N, S>) -> S {
     self.0.call(pos).max(self.1.call(pos))
   }
 
   #[inline]
-  fn hits(&self, pos: Vector<N>) -> bool {
+  fn hits(&self, pos: Vector<N, S>) -> bool {
     // `&&` short circuits, so can cull function calls
     self.0.hits(pos) && self.1.hits(pos)
   }
 }
 
-impl<const N: usize, T: SdfInfo<N>, U: SdfInfo<N, Info = T::Info>> SdfInfo<N> for Intersect<T, U> {
+impl<const N: usize, S: Scalar, T: SdfInfo<N, S>, U: SdfInfo<N, S, Info = T::Info>> SdfInfo<N, S>
+  for Intersect<T, U>
+{
   type Info = T::Info;
   #[inline]
-  fn call_info(&self, pos: Vector<N>) -> (f64, Self::Info) {
+  fn call_info(&self, pos: Vector<N, S>) -> (S, Self::Info) {
     let (value0, info0) = self.0.call_info(pos);
     let (value1, info1) = self.1.call_info(pos);
     if value0 >= value1 {
@@ -39,9 +41,11 @@ impl<const N: usize, T: SdfInfo<N>, U: SdfInfo<N, Info = T::Info>> SdfInfo<N> fo
   }
 }
 
-impl<const N: usize, T: SdfGrad<N>, U: SdfGrad<N>> SdfGrad<N> for Intersect<T, U> {
+impl<const N: usize, S: Scalar, T: SdfGrad<N, S>, U: SdfGrad<N, S>> SdfGrad<N, S>
+  for Intersect<T, U>
+{
   #[inline]
-  fn call_grad(&self, pos: Vector<N>) -> (f64, Vector<N>) {
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
     let (value0, grad0) = self.0.call_grad(pos);
     let (value1, grad1) = self.1.call_grad(pos);
     if value0 >= value1 {
@@ -51,3 +55,9 @@ impl<const N: usize, T: SdfGrad<N>, U: SdfGrad<N>> SdfGrad<N> for Intersect<T, U
     }
   }
 }
+
+impl<const N: usize, T: SdfBounds<N>, U: SdfBounds<N>> SdfBounds<N> for Intersect<T, U> {
+  fn bounds(&self) -> Aabb<N> {
+    self.0.bounds().intersection(self.1.bounds())
+  }
+}
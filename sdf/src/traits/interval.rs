@@ -0,0 +1,60 @@
+use super::Sdf;
+use marchrs_vectors::{Aabb, Interval, Vector};
+
+/// Extension to the `Sdf` that bounds `call` over a whole region.
+///
+/// This is implemented for any item that can be evaluated with\
+/// interval arithmetic, i.e. every primitive and combinator,\
+/// and can be used to cull regions that can't contain a surface.
+pub trait SdfInterval<const N: usize>: Sdf<N> {
+  /// Bounds the values `call` can take for any point in `region`.
+  fn call_interval(&self, region: Aabb<N>) -> Interval;
+
+  /// Whether `region` could contain part of the surface,\
+  /// i.e. whether `call` could be `0` for any point in it.
+  #[inline]
+  fn may_contain_surface(&self, region: Aabb<N>) -> bool {
+    self.call_interval(region).contains(0.0)
+  }
+}
+
+impl<const N: usize, T: Sdf<N> + Sdf<N, Interval>> SdfInterval<N> for T {
+  #[inline]
+  fn call_interval(&self, region: Aabb<N>) -> Interval {
+    Sdf::<N, Interval>::call(self, Vector::from(region))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::items::checks::check_intervals;
+  use crate::items::{CapCylinder, Cube, Cuboid, Sphere};
+  use crate::traits::{
+    Remove, SdfIntersect, SdfRotate3D, SdfScale, SdfSmoothUnion, SdfTranslate, SdfUnion,
+  };
+  use std::f64::consts::PI;
+
+  #[test]
+  fn bounds_samples() {
+    let scene = Remove(
+      Cuboid::default()
+        .scale([0.4, 1.0, 0.6])
+        .rot([1.0, 1.0, 0.0], PI / 3.0)
+        .smooth_or(Sphere.translate([0.3, 0.0, 0.0]), 0.2),
+      CapCylinder::<3>::default().scale([0.5; 3]),
+    )
+    .or(Cube::default().translate([0.0, -1.0, 0.0]))
+    .and(Sphere.scale([3.0; 3]));
+
+    check_intervals(&scene);
+  }
+
+  #[test]
+  fn culls_empty_regions() {
+    let sphere = Sphere.translate([1.0, 0.0]);
+    assert!(sphere.may_contain_surface(Aabb::new([0.4, -0.1], [0.6, 0.1])));
+    assert!(!sphere.may_contain_surface(Aabb::new([-1.0, -1.0], [0.0, 0.0])));
+    assert!(!sphere.may_contain_surface(Aabb::new([0.9, -0.1], [1.1, 0.1])));
+  }
+}
@@ -1,5 +1,5 @@
-use super::{Sdf, SdfGrad, SdfInfo};
-use marchrs_vectors::Vector;
+use super::{Sdf, SdfBounds, SdfGrad, SdfInfo};
+use marchrs_vectors::{Aabb, Scalar, Vector};
 
 pub trait SdfInvert<const N: usize>: Sdf<N> + Sized {
   fn not(self) -> Invert<Self> {
@@ -12,31 +12,38 @@ impl<const N: usize, T: Sdf<N>> SdfInvert<N> for T {}
 #[derive(Clone, Copy, Default, PartialEq)]
 pub struct Invert<T>(pub T);
 
-impl<const N: usize, T: Sdf<N>> Sdf<N> for Invert<T> {
+impl<const N: usize, S: Scalar, T: Sdf<N, S>> Sdf<N, S> for Invert<T> {
   #[inline]
-  fn call(&self, pos: Vector<N>) -> f64 {
+  fn call(&self, pos: Vector<N, S>) -> S {
     -self.0.call(pos)
   }
 
   #[inline]
-  fn hits(&self, pos: Vector<N>) -> bool {
+  fn hits(&self, pos: Vector<N, S>) -> bool {
     !self.0.hits(pos)
   }
 }
 
-impl<const N: usize, T: SdfInfo<N>> SdfInfo<N> for Invert<T> {
+impl<const N: usize, S: Scalar, T: SdfInfo<N, S>> SdfInfo<N, S> for Invert<T> {
   type Info = T::Info;
   #[inline]
-  fn call_info(&self, pos: Vector<N>) -> (f64, Self::Info) {
+  fn call_info(&self, pos: Vector<N, S>) -> (S, Self::Info) {
     let (value, info) = self.0.call_info(pos);
     (-value, info)
   }
 }
 
-impl<const N: usize, T: SdfGrad<N>> SdfGrad<N> for Invert<T> {
+impl<const N: usize, S: Scalar, T: SdfGrad<N, S>> SdfGrad<N, S> for Invert<T> {
   #[inline]
-  fn call_grad(&self, pos: Vector<N>) -> (f64, Vector<N>) {
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
     let (value, grad) = self.0.call_grad(pos);
     (-value, -grad)
   }
 }
+
+/// The inverse of an item is unbounded.
+impl<const N: usize, T: Sdf<N>> SdfBounds<N> for Invert<T> {
+  fn bounds(&self) -> Aabb<N> {
+    Aabb::infinite()
+  }
+}
@@ -1,13 +1,19 @@
 use std::array;
 
+mod auto_grad;
+pub use auto_grad::*;
 mod dyn_wrap;
 pub use dyn_wrap::*;
+mod fuse;
+pub use fuse::*;
 mod helpers;
 pub use helpers::*;
 mod info;
 pub use info::*;
 mod intersect;
 pub use intersect::*;
+mod interval;
+pub use interval::*;
 mod invert;
 pub use invert::*;
 mod remove;
@@ -22,37 +28,47 @@ mod scale;
 pub use scale::*;
 mod smooth_unions;
 pub use smooth_unions::*;
+mod transform;
+pub use transform::*;
 mod translate;
 pub use translate::*;
 mod unions;
 pub use unions::*;
 
-use marchrs_vectors::Vector;
+use marchrs_vectors::{Aabb, Scalar, Vector};
 
 /// A small step size, used to approximate derivatives
 const EPSILON: f64 = 1e-8;
 
+/// The step size used to approximate derivatives for a given scalar.\
+/// Single precision floats can't resolve steps as small as `EPSILON`,\
+/// so we fall back to the square root of machine epsilon for those.
+#[inline]
+fn grad_step<S: Scalar>() -> S {
+  S::from_f64(EPSILON).max(S::EPSILON.sqrt())
+}
+
 /// Objects implemented with SDFs (Signed Distance Functions),\
 /// where the surface is defined by `f([x, y, ...]) == 0`
 ///
 /// @note this would be a lot more ergonomic as a `Fn([f64; N]) -> f64`\
 /// but implementing function traits in rust isn't stable yet.
 /// see [#29625](https://github.com/rust-lang/rust/issues/29625)
-pub trait Sdf<const N: usize> {
+pub trait Sdf<const N: usize, S: Scalar = f64> {
   /// Finds the distance to the nearest surface.\
   /// This can be negative if `pos` is within the item.
-  fn call(&self, pos: Vector<N>) -> f64;
+  fn call(&self, pos: Vector<N, S>) -> S;
 
   /// Tests whether a specific point is inside or on an item.\
   /// This is implemented seperately as some items can short-circuit.
   #[inline]
-  fn hits(&self, pos: Vector<N>) -> bool {
-    self.call(pos) <= 0.0
+  fn hits(&self, pos: Vector<N, S>) -> bool {
+    self.call(pos) <= S::ZERO
   }
 }
 
-impl<const N: usize, F: Fn([f64; N]) -> f64 + Send + Sync> Sdf<N> for F {
-  fn call(&self, pos: Vector<N>) -> f64 {
+impl<const N: usize, S: Scalar, F: Fn([S; N]) -> S + Send + Sync> Sdf<N, S> for F {
+  fn call(&self, pos: Vector<N, S>) -> S {
     self(pos.0)
   }
 }
@@ -63,16 +79,16 @@ impl<const N: usize, F: Fn([f64; N]) -> f64 + Send + Sync> Sdf<N> for F {
 /// issues where intersection and union would have to fetch this\
 /// distance every time the info is fetched from them, leading to\
 /// significant recomputation in `Sdf`s with a lot of unions.
-pub trait SdfInfo<const N: usize>: Sdf<N> {
+pub trait SdfInfo<const N: usize, S: Scalar = f64>: Sdf<N, S> {
   /// Information type attached to an `SDF` item.
   type Info;
 
   /// Calls the `SDF` and returns the info attached to the nearest item.
-  fn call_info(&self, pos: Vector<N>) -> (f64, Self::Info);
+  fn call_info(&self, pos: Vector<N, S>) -> (S, Self::Info);
 
   /// The information attached to the nearest item.
   #[inline]
-  fn info(&self, pos: Vector<N>) -> Self::Info {
+  fn info(&self, pos: Vector<N, S>) -> Self::Info {
     self.call_info(pos).1
   }
 }
@@ -102,19 +118,20 @@ pub trait SdfInfo<const N: usize>: Sdf<N> {
 /// more than happy to implement it, as it would significantly smooth\
 /// out the implementation of `Sdf`s and could help prevent potential\
 /// feature creep of this trait in the future.
-pub trait SdfGrad<const N: usize>: Sdf<N> {
+pub trait SdfGrad<const N: usize, S: Scalar = f64>: Sdf<N, S> {
   /// Calls the `SDF` and fetches the gradient at the given position.
   ///
   /// Uses a numerical approximation by default.
   #[inline]
-  fn call_grad(&self, pos: Vector<N>) -> (f64, Vector<N>) {
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
+    let step = grad_step::<S>();
     (
       self.call(pos),
       Vector(array::from_fn(|i| {
         let mut pos = pos;
-        pos[i] -= EPSILON * 0.5;
+        pos[i] -= step * S::from_f64(0.5);
         let value = self.call(pos);
-        pos[i] += EPSILON;
+        pos[i] += step;
         self.call(pos) - value
       }))
       .normal(),
@@ -123,13 +140,55 @@ pub trait SdfGrad<const N: usize>: Sdf<N> {
 
   /// The gradient at a given position.
   #[inline]
-  fn grad(&self, pos: Vector<N>) -> Vector<N> {
+  fn grad(&self, pos: Vector<N, S>) -> Vector<N, S> {
     self.call_grad(pos).1
   }
 }
 
-impl<const N: usize, F: Fn([f64; N]) -> f64 + Send + Sync> SdfGrad<N> for F {}
+impl<const N: usize, S: Scalar, F: Fn([S; N]) -> S + Send + Sync> SdfGrad<N, S> for F {}
+
+/// Extension to the `Sdf` that bounds the region it hits,\
+/// so that callers can compute the extent of a scene.
+///
+/// Bounds only need to be conservative, i.e. they must contain\
+/// every point the item hits, but may contain more than that.\
+/// Unbounded items should return an (at least partially) infinite box.
+pub trait SdfBounds<const N: usize>: Sdf<N> {
+  /// A box containing every point that the item hits.
+  fn bounds(&self) -> Aabb<N>;
+}
+
+pub trait SdfFull<const N: usize, S: Scalar = f64>: SdfInfo<N, S> + SdfGrad<N, S> {}
+
+impl<const N: usize, S: Scalar, M: SdfInfo<N, S> + SdfGrad<N, S>> SdfFull<N, S> for M {}
+
+#[cfg(test)]
+mod tests {
+  use super::{Sdf, SdfBounds};
+  use crate::items::{CapCylinder, Cuboid, Sphere};
+  use crate::traits::{SdfIntersect, SdfRotate3D, SdfScale, SdfTranslate, SdfUnion};
+  use marchrs_vectors::{GridIter, Vector};
+  use std::f64::consts::PI;
 
-pub trait SdfFull<const N: usize>: SdfInfo<N> + SdfGrad<N> {}
+  #[test]
+  fn bounds_contain_hits() {
+    let scene = Cuboid::default()
+      .scale([0.4, 1.0, 0.6])
+      .rot([1.0, 1.0, 0.0], PI / 3.0)
+      .translate([0.5, 0.0, -0.5])
+      .or(Sphere.scale([0.5, 1.0, 2.0]))
+      .or(CapCylinder::new([0.0, 0.6, 0.8]).translate([0.0, 1.0, 0.0]))
+      .and(Sphere.scale([3.0; 3]));
+    let bounds = scene.bounds();
+    assert!(bounds.is_finite());
 
-impl<const N: usize, S: Sdf<N> + SdfInfo<N> + SdfGrad<N>> SdfFull<N> for S {}
+    for pos in GridIter::<3>::new(Vector([-2.0; 3])..=Vector([2.0; 3]), [41; 3]) {
+      if scene.hits(pos) {
+        assert!(
+          bounds.expand(1e-9).contains(pos),
+          "{pos:?} outside {bounds:?}"
+        );
+      }
+    }
+  }
+}
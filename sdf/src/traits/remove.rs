@@ -1,5 +1,5 @@
-use super::{Sdf, SdfInfo};
-use marchrs_vectors::Vector;
+use super::{Sdf, SdfBounds, SdfInfo};
+use marchrs_vectors::{Aabb, Scalar, Vector};
 
 pub trait SdfRemove<const N: usize>: Sdf<N> + Sized {
   fn or<S: Sdf<N>>(self, other: S) -> Remove<Self, S> {
@@ -12,23 +12,25 @@ impl<const N: usize, T: Sdf<N>> SdfRemove<N> for T {}
 #[derive(Clone, Copy, Default, PartialEq)]
 pub struct Remove<T, U>(pub T, pub U);
 
-impl<const N: usize, T: Sdf<N>, U: Sdf<N>> Sdf<N> for Remove<T, U> {
+impl<const N: usize, S: Scalar, T: Sdf<N, S>, U: Sdf<N, S>> Sdf<N, S> for Remove<T, U> {
   #[inline]
-  fn call(&self, pos: Vector<N>) -> f64 {
+  fn call(&self, pos: Vector<N, S>) -> S {
     self.0.call(pos).max(-self.1.call(pos))
   }
 
   #[inline]
-  fn hits(&self, pos: Vector<N>) -> bool {
+  fn hits(&self, pos: Vector<N, S>) -> bool {
     // `&&` short circuits, so can cull function calls
     self.0.hits(pos) && !self.1.hits(pos)
   }
 }
 
-impl<const N: usize, T: SdfInfo<N>, U: SdfInfo<N, Info = T::Info>> SdfInfo<N> for Remove<T, U> {
+impl<const N: usize, S: Scalar, T: SdfInfo<N, S>, U: SdfInfo<N, S, Info = T::Info>> SdfInfo<N, S>
+  for Remove<T, U>
+{
   type Info = T::Info;
   #[inline]
-  fn call_info(&self, pos: Vector<N>) -> (f64, Self::Info) {
+  fn call_info(&self, pos: Vector<N, S>) -> (S, Self::Info) {
     let (value0, info0) = self.0.call_info(pos);
     let (value1, info1) = self.1.call_info(pos);
     if value0 > -value1 {
@@ -38,3 +40,10 @@ impl<const N: usize, T: SdfInfo<N>, U: SdfInfo<N, Info = T::Info>> SdfInfo<N> fo
     }
   }
 }
+
+/// Removing an item can only shrink the original item.
+impl<const N: usize, T: SdfBounds<N>, U: Sdf<N>> SdfBounds<N> for Remove<T, U> {
+  fn bounds(&self) -> Aabb<N> {
+    self.0.bounds()
+  }
+}
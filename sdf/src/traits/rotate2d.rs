@@ -1,48 +1,126 @@
-use super::{Sdf, SdfGrad, SdfInfo};
-use marchrs_vectors::Vector;
+use super::{Sdf, SdfBounds, SdfGrad, SdfInfo};
+use marchrs_vectors::{Aabb, Affine, Matrix, Scalar, Vector};
 
 pub trait SdfRotate2D: Sdf<2> + Sized {
   fn rot(self, angle: f64) -> Rotate2D<Self> {
-    let s = (-angle).sin();
-    let c = (-angle).cos();
-    Rotate2D(self, [[c, -s], [c, s]])
+    Rotate2D(self, Matrix::<2>::rotation(-angle))
+  }
+
+  /// Rotates by an arbitrary rotation matrix.
+  ///
+  /// We store the inverse rotation to apply to coordinates,\
+  /// which for rotation matrices is just the transpose.
+  fn rot_matrix(self, rotation: Matrix<2>) -> Rotate2D<Self> {
+    debug_assert!(
+      rotation.is_rotation(),
+      "Can only rotate by rotation matrices\nGot {rotation:?}"
+    );
+    Rotate2D(self, rotation.transpose())
   }
 }
 
 impl<T: Sdf<2>> SdfRotate2D for T {}
 
 #[derive(Clone, Copy, PartialEq)]
-pub struct Rotate2D<T>(pub T, [[f64; 2]; 2]);
+pub struct Rotate2D<T>(pub T, Matrix<2>);
+
+impl<T> Rotate2D<T> {
+  /// The map from item space to world space
+  pub fn affine(&self) -> Affine<2> {
+    self.1.transpose().into()
+  }
+
+  /// Rotates by `angle` radians.
+  ///
+  /// This composes with the existing rotation,\
+  /// rather than nesting another `Rotate2D`.
+  pub fn rot(self, angle: f64) -> Self {
+    Self(self.0, self.1 * Matrix::<2>::rotation(-angle))
+  }
+
+  /// Rotates by a rotation matrix, composing with the existing rotation.
+  pub fn rot_matrix(self, rotation: Matrix<2>) -> Self {
+    debug_assert!(
+      rotation.is_rotation(),
+      "Can only rotate by rotation matrices\nGot {rotation:?}"
+    );
+    Self(self.0, self.1 * rotation.transpose())
+  }
+}
 
 impl<T: Default> Default for Rotate2D<T> {
   fn default() -> Self {
-    Self(T::default(), [[1.0, 0.0], [0.0, 1.0]])
+    Self(T::default(), Matrix::identity())
   }
 }
 
-impl<T: Sdf<2>> Sdf<2> for Rotate2D<T> {
+impl<S: Scalar, T: Sdf<2, S>> Sdf<2, S> for Rotate2D<T> {
   #[inline]
-  fn call(&self, pos: Vector<2>) -> f64 {
+  fn call(&self, pos: Vector<2, S>) -> S {
     self.0.call(self.1 * pos)
   }
 
   #[inline]
-  fn hits(&self, pos: Vector<2>) -> bool {
+  fn hits(&self, pos: Vector<2, S>) -> bool {
     self.0.hits(self.1 * pos)
   }
 }
 
-impl<T: SdfInfo<2>> SdfInfo<2> for Rotate2D<T> {
+impl<S: Scalar, T: SdfInfo<2, S>> SdfInfo<2, S> for Rotate2D<T> {
   type Info = T::Info;
   #[inline]
-  fn call_info(&self, pos: Vector<2>) -> (f64, Self::Info) {
+  fn call_info(&self, pos: Vector<2, S>) -> (S, Self::Info) {
     self.0.call_info(self.1 * pos)
   }
 }
 
-impl<T: SdfGrad<2>> SdfGrad<2> for Rotate2D<T> {
+impl<S: Scalar, T: SdfGrad<2, S>> SdfGrad<2, S> for Rotate2D<T> {
   #[inline]
-  fn call_grad(&self, pos: Vector<2>) -> (f64, Vector<2>) {
-    self.0.call_grad(self.1 * pos)
+  fn call_grad(&self, pos: Vector<2, S>) -> (S, Vector<2, S>) {
+    let (dist, grad) = self.0.call_grad(self.1 * pos);
+    (dist, self.1.transpose() * grad)
+  }
+}
+
+impl<T: SdfBounds<2>> SdfBounds<2> for Rotate2D<T> {
+  fn bounds(&self) -> Aabb<2> {
+    self.0.bounds().transform(&self.affine())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::items::checks::check_grad;
+  use crate::items::{Capsule, Cuboid};
+  use crate::traits::{SdfScale, SdfTranslate};
+  use marchrs_vectors::GridIter;
+  use std::f64::consts::{FRAC_PI_2, PI};
+
+  #[test]
+  fn rotates_anticlockwise() {
+    let model = Cuboid::default().scale([0.5, 0.2]).translate([1.0, 0.0]);
+    let rotated = model.rot(FRAC_PI_2);
+    assert!((rotated.call(Vector([0.0, 1.0])) + 0.1).abs() < 1e-12);
+    assert!(rotated.call(Vector([1.0, 0.0])) > 0.5);
+  }
+
+  #[test]
+  fn inverse_rotation() {
+    let model = Cuboid::default().scale([0.5, 0.2]).translate([0.3, -0.1]);
+    for angle in [0.3, FRAC_PI_2, 2.0, PI] {
+      let composed = model.rot(angle).rot(-angle);
+      let nested = Rotate2D(model.rot(angle), Matrix::<2>::rotation(angle));
+      for pos in GridIter::<2>::new(Vector([-1.0; 2])..=Vector([1.0; 2]), [9; 2]) {
+        let expected: f64 = model.call(pos);
+        assert!((composed.call(pos) - expected).abs() < 1e-12);
+        assert!((nested.call(pos) - expected).abs() < 1e-12);
+      }
+    }
+  }
+
+  #[test]
+  fn grads_match_dual() {
+    check_grad(&Capsule::new([0.1, -0.2], [0.5, 0.3], 0.2).rot(0.7));
   }
 }
@@ -1,75 +1,176 @@
-use marchrs_vectors::Vector;
+use marchrs_vectors::{Aabb, Affine, Matrix, Quaternion, Scalar, Vector};
 
-use super::{Sdf, SdfGrad, SdfInfo};
+use super::{Sdf, SdfBounds, SdfGrad, SdfInfo};
 
-/// 3D rotation around an axis.\
-/// we just use the matrix transformation from [wikipedia](http://en.wikipedia.org/wiki/Rotation_matrix#Rotation_matrix_from_axis_and_angle)
-/// to get the inverse rotation matrix to be applied to coordinates.
+/// 3D rotation of an item.\
+/// we store the inverse rotation matrix to be applied to coordinates.
 pub trait SdfRotate3D: Sdf<3> + Sized {
+  /// Rotates by `angle` radians around `axis`.
   fn rot(self, axis: impl Into<Vector<3>>, angle: f64) -> Rotate3D<Self> {
-    let s = (-angle).sin();
-    let c = (-angle).cos();
-    let Vector([x, y, z]) = axis.into().normal();
-    Rotate3D(
-      self,
-      [
-        [
-          c + x * x * (1.0 - c),
-          x * y * (1.0 - c) - z * s,
-          x * z * (1.0 - c) + y * s,
-        ],
-        [
-          x * y * (1.0 - c) + z * s,
-          c + y * y * (1.0 - c),
-          y * z * (1.0 - c) - x * s,
-        ],
-        [
-          x * z * (1.0 - c) - y * s,
-          y * z * (1.0 - c) + x * s,
-          c + z * z * (1.0 - c),
-        ],
-      ],
-    )
+    self.rot_quat(Quaternion::from_axis_angle(axis, angle))
+  }
+
+  /// Rotates by a unit quaternion.
+  fn rot_quat(self, rotation: Quaternion) -> Rotate3D<Self> {
+    Rotate3D::new(self, rotation)
+  }
+
+  /// Rotates by an arbitrary rotation matrix.
+  fn rot_matrix(self, rotation: Matrix<3>) -> Rotate3D<Self> {
+    self.rot_quat(Quaternion::from_matrix(rotation))
   }
 }
 
 impl<T: Sdf<3>> SdfRotate3D for T {}
 
+/// A Rotated `SDF` item `Rotate3D(item, rotation, inverse)`
+/// - `item`: the `SDF` item to apply rotation to
+/// - `rotation`: the rotation to apply, as a unit quaternion
+/// - `inverse`: the inverse rotation matrix, precomputed for speed
 #[derive(Clone, Copy, PartialEq)]
-pub struct Rotate3D<T>(pub T, [[f64; 3]; 3]);
+pub struct Rotate3D<T>(pub T, Quaternion, Matrix<3>);
+
+impl<T> Rotate3D<T> {
+  fn new(item: T, rotation: Quaternion) -> Self {
+    let rotation = rotation.normal();
+    Self(item, rotation, Matrix::from(rotation.conjugate()))
+  }
+
+  /// The overall rotation applied to the item
+  pub fn rotation(&self) -> Quaternion {
+    self.1
+  }
+
+  /// The map from item space to world space
+  pub fn affine(&self) -> Affine<3> {
+    self.1.into()
+  }
+
+  /// Rotates by `angle` radians around `axis`.
+  ///
+  /// This composes with the existing rotation,\
+  /// rather than nesting another `Rotate3D`.
+  pub fn rot(self, axis: impl Into<Vector<3>>, angle: f64) -> Self {
+    self.rot_quat(Quaternion::from_axis_angle(axis, angle))
+  }
+
+  /// Rotates by a unit quaternion, composing with the existing rotation.
+  pub fn rot_quat(self, rotation: Quaternion) -> Self {
+    Self::new(self.0, rotation * self.1)
+  }
+
+  /// Rotates by a rotation matrix, composing with the existing rotation.
+  pub fn rot_matrix(self, rotation: Matrix<3>) -> Self {
+    self.rot_quat(Quaternion::from_matrix(rotation))
+  }
+}
 
 impl<T: Default> Default for Rotate3D<T> {
   fn default() -> Self {
-    Self(
-      T::default(),
-      [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
-    )
+    Self::new(T::default(), Quaternion::identity())
   }
 }
 
-impl<T: Sdf<3>> Sdf<3> for Rotate3D<T> {
+impl<S: Scalar, T: Sdf<3, S>> Sdf<3, S> for Rotate3D<T> {
   #[inline]
-  fn call(&self, pos: Vector<3>) -> f64 {
-    self.0.call(self.1 * pos)
+  fn call(&self, pos: Vector<3, S>) -> S {
+    self.0.call(self.2 * pos)
   }
 
   #[inline]
-  fn hits(&self, pos: Vector<3>) -> bool {
-    self.0.hits(self.1 * pos)
+  fn hits(&self, pos: Vector<3, S>) -> bool {
+    self.0.hits(self.2 * pos)
   }
 }
 
-impl<T: SdfInfo<3>> SdfInfo<3> for Rotate3D<T> {
+impl<S: Scalar, T: SdfInfo<3, S>> SdfInfo<3, S> for Rotate3D<T> {
   type Info = T::Info;
   #[inline]
-  fn call_info(&self, pos: Vector<3>) -> (f64, Self::Info) {
-    self.0.call_info(self.1 * pos)
+  fn call_info(&self, pos: Vector<3, S>) -> (S, Self::Info) {
+    self.0.call_info(self.2 * pos)
   }
 }
 
-impl<T: SdfGrad<3>> SdfGrad<3> for Rotate3D<T> {
+impl<S: Scalar, T: SdfGrad<3, S>> SdfGrad<3, S> for Rotate3D<T> {
   #[inline]
-  fn call_grad(&self, pos: Vector<3>) -> (f64, Vector<3>) {
-    self.0.call_grad(self.1 * pos)
+  fn call_grad(&self, pos: Vector<3, S>) -> (S, Vector<3, S>) {
+    let (dist, grad) = self.0.call_grad(self.2 * pos);
+    (dist, self.1 * grad)
+  }
+}
+
+impl<T: SdfBounds<3>> SdfBounds<3> for Rotate3D<T> {
+  fn bounds(&self) -> Aabb<3> {
+    self.0.bounds().transform(&self.affine())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::items::checks::check_grad;
+  use crate::items::{Capsule, Cuboid};
+  use crate::traits::{SdfScale, SdfTranslate};
+  use marchrs_vectors::GridIter;
+  use std::f64::consts::FRAC_PI_2;
+
+  fn model() -> impl Sdf<3> + Copy {
+    Cuboid::default()
+      .scale([0.5, 0.2, 0.3])
+      .translate([0.3, -0.1, 0.2])
+  }
+
+  #[test]
+  fn rotates_anticlockwise() {
+    let model = Cuboid::default()
+      .scale([0.5, 0.2, 0.2])
+      .translate([1.0, 0.0, 0.0]);
+    let rotated = model.rot([0.0, 0.0, 1.0], FRAC_PI_2);
+    assert!((rotated.call(Vector([0.0, 1.0, 0.0])) + 0.1).abs() < 1e-12);
+    assert!(rotated.call(Vector([1.0, 0.0, 0.0])) > 0.5);
+  }
+
+  #[test]
+  fn stacked_rotations_collapse() {
+    let first = Quaternion::from_axis_angle([1.0, 2.0, 0.5], 0.7);
+    let second = Matrix::<3>::rotation([-0.3, 0.4, 1.0], 2.1);
+    let third = ([0.0, 1.0, 1.0], -1.3);
+    // Each call composes into a single `Rotate3D` around the model
+    let stacked: Rotate3D<_> = model()
+      .rot_quat(first)
+      .rot_matrix(second)
+      .rot(third.0, third.1);
+    let nested = Rotate3D::new(
+      Rotate3D::new(
+        Rotate3D::new(model(), first),
+        Quaternion::from_matrix(second),
+      ),
+      Quaternion::from_axis_angle(third.0, third.1),
+    );
+    for pos in GridIter::<3>::new(Vector([-1.0; 3])..=Vector([1.0; 3]), [9; 3]) {
+      let expected: f64 = nested.call(pos);
+      assert!(
+        (stacked.call(pos) - expected).abs() < 1e-12,
+        "{} != {expected} at {pos:?}",
+        stacked.call(pos)
+      );
+    }
+  }
+
+  #[test]
+  fn inverse_rotation() {
+    let axis = [0.2, -1.0, 0.6];
+    let composed = model().rot(axis, 1.1).rot(axis, -1.1);
+    for pos in GridIter::<3>::new(Vector([-1.0; 3])..=Vector([1.0; 3]), [9; 3]) {
+      let expected: f64 = model().call(pos);
+      assert!((composed.call(pos) - expected).abs() < 1e-12);
+    }
+  }
+
+  #[test]
+  fn grads_match_dual_and_numeric() {
+    let capsule = Capsule::new([0.1, -0.2, 0.3], [0.5, 0.3, -0.2], 0.2);
+    check_grad(&capsule.rot([1.0, 1.0, 0.0], 0.9));
+    check_grad(&capsule.rot([0.0, 0.0, 1.0], 0.4).rot([1.0, 0.0, 0.0], -2.0));
   }
 }
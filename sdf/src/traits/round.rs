@@ -1,5 +1,5 @@
-use super::{Sdf, SdfGrad, SdfInfo};
-use marchrs_vectors::Vector;
+use super::{Sdf, SdfBounds, SdfGrad, SdfInfo};
+use marchrs_vectors::{Aabb, Scalar, Vector};
 
 pub trait SdfRound<const N: usize>: Sdf<N> + Sized {
   fn round(self, radius: f64) -> Round<N, Self> {
@@ -18,26 +18,32 @@ impl<const N: usize, T: Default> Default for Round<N, T> {
   }
 }
 
-impl<const N: usize, T: Sdf<N>> Sdf<N> for Round<N, T> {
+impl<const N: usize, S: Scalar, T: Sdf<N, S>> Sdf<N, S> for Round<N, T> {
   #[inline]
-  fn call(&self, pos: Vector<N>) -> f64 {
-    self.0.call(pos) - self.1
+  fn call(&self, pos: Vector<N, S>) -> S {
+    self.0.call(pos) - S::from_f64(self.1)
   }
 }
 
-impl<const N: usize, T: SdfInfo<N>> SdfInfo<N> for Round<N, T> {
+impl<const N: usize, S: Scalar, T: SdfInfo<N, S>> SdfInfo<N, S> for Round<N, T> {
   type Info = T::Info;
   #[inline]
-  fn call_info(&self, pos: Vector<N>) -> (f64, Self::Info) {
+  fn call_info(&self, pos: Vector<N, S>) -> (S, Self::Info) {
     let (value, info) = self.0.call_info(pos);
-    (value - self.1, info)
+    (value - S::from_f64(self.1), info)
   }
 }
 
-impl<const N: usize, T: SdfGrad<N>> SdfGrad<N> for Round<N, T> {
+impl<const N: usize, S: Scalar, T: SdfGrad<N, S>> SdfGrad<N, S> for Round<N, T> {
   #[inline]
-  fn call_grad(&self, pos: Vector<N>) -> (f64, Vector<N>) {
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
     let (value, grad) = self.0.call_grad(pos);
-    (value - self.1, grad)
+    (value - S::from_f64(self.1), grad)
+  }
+}
+
+impl<const N: usize, T: SdfBounds<N>> SdfBounds<N> for Round<N, T> {
+  fn bounds(&self) -> Aabb<N> {
+    self.0.bounds().expand(self.1)
   }
 }
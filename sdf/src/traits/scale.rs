@@ -1,5 +1,5 @@
-use super::{Sdf, SdfGrad, SdfInfo};
-use marchrs_vectors::Vector;
+use super::{Sdf, SdfBounds, SdfGrad, SdfInfo};
+use marchrs_vectors::{Aabb, Affine, Scalar, Vector};
 
 pub trait SdfScale<const N: usize>: Sdf<N> + Sized {
   fn scale(self, scale: impl Into<Vector<N>>) -> Scale<N, Self> {
@@ -17,37 +17,63 @@ impl<const N: usize, T: Sdf<N>> SdfScale<N> for T {}
 #[derive(Clone, Copy, PartialEq)]
 pub struct Scale<const N: usize, T>(pub T, Vector<N>, f64);
 
+impl<const N: usize, T> Scale<N, T> {
+  /// The map from item space to world space
+  pub fn affine(&self) -> Affine<N> {
+    Affine::scaling(1.0 / self.1)
+  }
+}
+
 impl<const N: usize, T: Default> Default for Scale<N, T> {
   fn default() -> Self {
     Self(T::default(), Vector([1.0; N]), 1.0)
   }
 }
 
-impl<const N: usize, T: Sdf<N>> Sdf<N> for Scale<N, T> {
+impl<const N: usize, S: Scalar, T: Sdf<N, S>> Sdf<N, S> for Scale<N, T> {
   #[inline]
-  fn call(&self, pos: Vector<N>) -> f64 {
-    self.0.call(pos * self.1) * self.2
+  fn call(&self, pos: Vector<N, S>) -> S {
+    self.0.call(pos * self.1.cast()) * S::from_f64(self.2)
   }
 
   #[inline]
-  fn hits(&self, pos: Vector<N>) -> bool {
-    self.0.hits(pos * self.1)
+  fn hits(&self, pos: Vector<N, S>) -> bool {
+    self.0.hits(pos * self.1.cast())
   }
 }
 
-impl<const N: usize, T: SdfInfo<N>> SdfInfo<N> for Scale<N, T> {
+impl<const N: usize, S: Scalar, T: SdfInfo<N, S>> SdfInfo<N, S> for Scale<N, T> {
   type Info = T::Info;
   #[inline]
-  fn call_info(&self, pos: Vector<N>) -> (f64, Self::Info) {
-    let (dist, info) = self.0.call_info(pos * self.1);
-    (dist * self.2, info)
+  fn call_info(&self, pos: Vector<N, S>) -> (S, Self::Info) {
+    let (dist, info) = self.0.call_info(pos * self.1.cast());
+    (dist * S::from_f64(self.2), info)
   }
 }
 
-impl<const N: usize, T: SdfGrad<N>> SdfGrad<N> for Scale<N, T> {
+impl<const N: usize, S: Scalar, T: SdfGrad<N, S>> SdfGrad<N, S> for Scale<N, T> {
   #[inline]
-  fn call_grad(&self, pos: Vector<N>) -> (f64, Vector<N>) {
-    let (dist, grad) = self.0.call_grad(pos * self.1);
-    (dist * self.2, grad)
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
+    let (dist, grad) = self.0.call_grad(pos * self.1.cast());
+    // the chain rule scales each axis of the gradient by `iscale`
+    (dist * S::from_f64(self.2), (grad * self.1.cast()).normal())
+  }
+}
+
+impl<const N: usize, T: SdfBounds<N>> SdfBounds<N> for Scale<N, T> {
+  fn bounds(&self) -> Aabb<N> {
+    self.0.bounds().transform(&self.affine())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::items::checks::check_grad;
+  use crate::items::Capsule;
+
+  #[test]
+  fn grads_match_dual() {
+    check_grad(&Capsule::new([0.1, -0.2], [0.5, 0.3], 0.2).scale([0.5, 1.5]));
   }
 }
@@ -1,7 +1,7 @@
 use crate::interpolate::Interpolate;
 
-use super::{Sdf, SdfGrad, SdfInfo};
-use marchrs_vectors::Vector;
+use super::{Sdf, SdfBounds, SdfGrad, SdfInfo};
+use marchrs_vectors::{Aabb, Scalar, Vector};
 
 /// Smooth unions for `SDF` items.\
 /// heavily inspired by [this article](https://iquilezles.org/articles/smin/)
@@ -23,52 +23,58 @@ impl<const N: usize, T: Sdf<N>> SdfSmoothUnion<N> for T {}
 #[derive(Clone, Copy, Default, PartialEq)]
 pub struct SmoothUnion<T, U>(pub T, pub U, pub f64);
 
-impl<const N: usize, T: Sdf<N>, U: Sdf<N>> Sdf<N> for SmoothUnion<T, U> {
+impl<const N: usize, S: Scalar, T: Sdf<N, S>, U: Sdf<N, S>> Sdf<N, S> for SmoothUnion<T, U> {
   #[inline]
-  fn call(&self, pos: Vector<N>) -> f64 {
+  fn call(&self, pos: Vector<N, S>) -> S {
     // the modern implementation of quadratic blending,
     // faster but *doesn't* support arbitrary linear interpolation
+    let k = S::from_f64(self.2);
     let v0 = self.0.call(pos);
     let v1 = self.1.call(pos);
-    let f = (1.0 - (v0 - v1).abs() / (self.2 * 4.0)).max(0.0);
-    v0.min(v1) - f * f * self.2
+    let f = (S::ONE - (v0 - v1).abs() / (k * S::from_f64(4.0))).max(S::ZERO);
+    v0.min(v1) - f * f * k
   }
 
   #[inline]
-  fn hits(&self, pos: Vector<N>) -> bool {
+  fn hits(&self, pos: Vector<N, S>) -> bool {
     let v0 = self.0.call(pos);
-    if v0 <= 0.0 {
+    if v0 <= S::ZERO {
       return true;
     }
     let v1 = self.1.call(pos);
-    if v1 <= 0.0 {
+    if v1 <= S::ZERO {
       return true;
     }
-    let f = (1.0 - (v0 - v1).abs() / (self.2 * 4.0)).max(0.0);
-    v0.min(v1) - f * f * self.2 <= 0.0
+    let k = S::from_f64(self.2);
+    let f = (S::ONE - (v0 - v1).abs() / (k * S::from_f64(4.0))).max(S::ZERO);
+    v0.min(v1) - f * f * k <= S::ZERO
   }
 }
 
-impl<const N: usize, T, U> SdfInfo<N> for SmoothUnion<T, U>
+impl<const N: usize, S: Scalar, T, U> SdfInfo<N, S> for SmoothUnion<T, U>
 where
-  T: SdfInfo<N>,
-  U: SdfInfo<N, Info = T::Info>,
+  T: SdfInfo<N, S>,
+  U: SdfInfo<N, S, Info = T::Info>,
   T::Info: Interpolate,
 {
   type Info = T::Info;
   #[inline]
-  fn call_info(&self, pos: Vector<N>) -> (f64, Self::Info) {
+  fn call_info(&self, pos: Vector<N, S>) -> (S, Self::Info) {
+    let k = S::from_f64(self.2);
     let (v0, info0) = self.0.call_info(pos);
     let (v1, info1) = self.1.call_info(pos);
-    let f = (0.5 + 0.5 * (v0 - v1) / self.2).clamp(0.0, 1.0);
+    let half = S::from_f64(0.5);
+    let f = (half + half * (v0 - v1) / k).clamp(S::ZERO, S::ONE);
     (
-      v0.lerp(v1, f) - self.2 * f * (1.0 - f),
-      info0.lerp(info1, f),
+      v0 + (v1 - v0) * f - k * f * (S::ONE - f),
+      info0.lerp(info1, f.to_f64()),
     )
   }
 }
 
-impl<const N: usize, T: SdfGrad<N>, U: SdfGrad<N>> SdfGrad<N> for SmoothUnion<T, U> {
+impl<const N: usize, S: Scalar, T: SdfGrad<N, S>, U: SdfGrad<N, S>> SdfGrad<N, S>
+  for SmoothUnion<T, U>
+{
   /// Derived from the `call` definition:
   ///
   /// ```ignore
@@ -95,20 +101,32 @@ impl<const N: usize, T: SdfGrad<N>, U: SdfGrad<N>> SdfGrad<N> for SmoothUnion<T,
   ///
   /// which we return alongside the original value, allowing us to re-use code.
   #[inline]
-  fn call_grad(&self, pos: Vector<N>) -> (f64, Vector<N>) {
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
     let (v0, grad0) = self.0.call_grad(pos);
     let (v1, grad1) = self.1.call_grad(pos);
-    let k = self.2;
+    let k = S::from_f64(self.2);
+    let two_k = k * S::from_f64(2.0);
 
-    let d = (v1 - v0) / (2.0 * k);
-    let grad_d = (grad1 - grad0) / (2.0 * k);
+    let d = (v1 - v0) / two_k;
+    let grad_d = (grad1 - grad0) / two_k;
 
-    let f = d.clamp(0.0, 1.0);
-    let grad_f = f64::from((0.0..=1.0).contains(&d)) * grad_d;
+    let f = d.clamp(S::ZERO, S::ONE);
+    let grad_f = if (S::ZERO..=S::ONE).contains(&d) {
+      grad_d
+    } else {
+      Vector::zeros()
+    };
 
-    let r = v1.lerp(v0, f) - k * f * (1.0 - f);
-    let grad_r = grad1.lerp(grad0, f) + grad_f * (v1 + v0 - k);
+    let r = v1 + (v0 - v1) * f - k * f * (S::ONE - f);
+    let grad_r = grad1 + (grad0 - grad1) * f + grad_f * (v1 + v0 - k);
 
     (r, grad_r)
   }
 }
+
+/// Blending subtracts at most `factor` from the union.
+impl<const N: usize, T: SdfBounds<N>, U: SdfBounds<N>> SdfBounds<N> for SmoothUnion<T, U> {
+  fn bounds(&self) -> Aabb<N> {
+    self.0.bounds().union(self.1.bounds()).expand(self.2.abs())
+  }
+}
@@ -0,0 +1,181 @@
+use super::{Sdf, SdfBounds, SdfGrad, SdfInfo};
+use marchrs_vectors::{Aabb, Affine, Matrix, Quaternion, Scalar, Vector};
+
+pub trait SdfTransform<const N: usize>: Sdf<N> + Sized {
+  /// Applies an arbitrary affine map to the item.
+  ///
+  /// Panics if the map isn't invertible, i.e. it flattens the item.
+  fn transform(self, affine: impl Into<Affine<N>>) -> Transform<N, Self> {
+    Transform::new(self, affine.into())
+  }
+}
+
+impl<const N: usize, T: Sdf<N>> SdfTransform<N> for T {}
+
+/// An affinely transformed `SDF` item `Transform(item, affine, inverse, lipschitz)`
+/// - `item`: the `SDF` item to apply the transform to
+/// - `affine`: the map from item space to world space
+/// - `inverse`: the map from world space to item space, precomputed for speed
+/// - `lipschitz`: the smallest factor `affine` can scale a distance by,\
+///   used to keep the distance conservative, precomputed for speed
+#[derive(Clone, Copy, PartialEq)]
+pub struct Transform<const N: usize, T>(pub T, Affine<N>, Affine<N>, f64);
+
+impl<const N: usize, T> Transform<N, T> {
+  pub fn new(item: T, affine: Affine<N>) -> Self {
+    let inverse = affine
+      .inverse()
+      .unwrap_or_else(|| panic!("Affine transforms should be invertible\nGot {affine:?}"));
+    // the smallest singular value of `affine` is the reciprocal of the largest
+    // for `inverse`, but the latter is more accurate for near singular maps.
+    let lipschitz = inverse.linear.norm().recip();
+    Self(item, affine, inverse, lipschitz)
+  }
+
+  /// The map from item space to world space
+  pub fn affine(&self) -> Affine<N> {
+    self.1
+  }
+
+  /// Applies another affine map after this one.
+  ///
+  /// This composes with the existing transform,\
+  /// rather than nesting another `Transform`.
+  pub fn transform(self, affine: impl Into<Affine<N>>) -> Self {
+    Self::new(self.0, affine.into() * self.1)
+  }
+
+  /// Translates, composing with the existing transform.
+  pub fn translate(self, translation: impl Into<Vector<N>>) -> Self {
+    self.transform(Affine::translation(translation))
+  }
+
+  /// Scales, composing with the existing transform.
+  pub fn scale(self, scale: impl Into<Vector<N>>) -> Self {
+    self.transform(Affine::scaling(scale))
+  }
+}
+
+impl<T> Transform<2, T> {
+  /// Rotates by `angle` radians, composing with the existing transform.
+  pub fn rot(self, angle: f64) -> Self {
+    self.transform(Matrix::<2>::rotation(angle))
+  }
+}
+
+impl<T> Transform<3, T> {
+  /// Rotates by `angle` radians around `axis`,\
+  /// composing with the existing transform.
+  pub fn rot(self, axis: impl Into<Vector<3>>, angle: f64) -> Self {
+    self.rot_quat(Quaternion::from_axis_angle(axis, angle))
+  }
+
+  /// Rotates by a unit quaternion, composing with the existing transform.
+  pub fn rot_quat(self, rotation: Quaternion) -> Self {
+    self.transform(rotation)
+  }
+}
+
+impl<const N: usize, T: Default> Default for Transform<N, T> {
+  fn default() -> Self {
+    Self(T::default(), Affine::identity(), Affine::identity(), 1.0)
+  }
+}
+
+impl<const N: usize, S: Scalar, T: Sdf<N, S>> Sdf<N, S> for Transform<N, T> {
+  #[inline]
+  fn call(&self, pos: Vector<N, S>) -> S {
+    self.0.call(self.2 * pos) * S::from_f64(self.3)
+  }
+
+  #[inline]
+  fn hits(&self, pos: Vector<N, S>) -> bool {
+    self.0.hits(self.2 * pos)
+  }
+}
+
+impl<const N: usize, S: Scalar, T: SdfInfo<N, S>> SdfInfo<N, S> for Transform<N, T> {
+  type Info = T::Info;
+  #[inline]
+  fn call_info(&self, pos: Vector<N, S>) -> (S, Self::Info) {
+    let (dist, info) = self.0.call_info(self.2 * pos);
+    (dist * S::from_f64(self.3), info)
+  }
+}
+
+impl<const N: usize, S: Scalar, T: SdfGrad<N, S>> SdfGrad<N, S> for Transform<N, T> {
+  /// By the chain rule, the gradient in world space is\
+  /// the item space gradient multiplied by the transposed inverse.
+  #[inline]
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
+    let (dist, grad) = self.0.call_grad(self.2 * pos);
+    (
+      dist * S::from_f64(self.3),
+      (self.2.linear.transpose() * grad).normal(),
+    )
+  }
+}
+
+impl<const N: usize, T: SdfBounds<N>> SdfBounds<N> for Transform<N, T> {
+  fn bounds(&self) -> Aabb<N> {
+    self.0.bounds().transform(&self.affine())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::items::checks::{check_grad, check_intervals};
+  use crate::items::{Capsule, Sphere};
+  use marchrs_vectors::GridIter;
+  use std::f64::consts::TAU;
+
+  /// A rotation, shear and non-uniform scale, with an offset
+  fn skewed<const N: usize>(rotation: Matrix<N>) -> Affine<N> {
+    let mut scale = Vector([0.5; N]);
+    scale[0] = 1.5;
+    Affine::translation(Vector([0.1; N]))
+      * Affine::from(rotation)
+      * Affine::shear(0, 1, 0.8)
+      * Affine::scaling(scale)
+  }
+
+  #[test]
+  fn distances_conservative() {
+    let affine = skewed(Matrix::<2>::rotation(0.4));
+    let model = Sphere.transform(affine);
+    // The surface is the image of the circle, so sample it directly
+    let surface: Vec<_> = (0..20_000)
+      .map(|i| {
+        let angle = TAU * i as f64 / 20_000.0;
+        affine * (Vector([angle.cos(), angle.sin()]) * 0.5)
+      })
+      .collect();
+
+    for pos in GridIter::<2>::new(Vector([-1.5; 2])..=Vector([1.5; 2]), [31; 2]) {
+      let dist: f64 = model.call(pos);
+      let exact = surface
+        .iter()
+        .map(|&point| (point - pos).mag())
+        .fold(f64::INFINITY, f64::min);
+      assert!(dist.abs() <= exact + 1e-6, "{dist} > {exact} at {pos:?}");
+      if exact > 1e-3 {
+        assert_eq!(dist < 0.0, model.hits(pos), "at {pos:?}");
+      }
+    }
+  }
+
+  #[test]
+  fn grads_match_dual() {
+    let rotation = Matrix::<3>::rotation([1.0, -1.0, 2.0], 0.9);
+    let model = Capsule::new([0.1, -0.2, 0.3], [-0.4, 0.5, 0.0], 0.2).transform(skewed(rotation));
+    check_grad(&model);
+  }
+
+  #[test]
+  fn intervals_contain_samples() {
+    let rotation = Matrix::<3>::rotation([1.0, -1.0, 2.0], 0.9);
+    let model = Capsule::new([0.1, -0.2, 0.3], [-0.4, 0.5, 0.0], 0.2).transform(skewed(rotation));
+    check_intervals(&model);
+  }
+}
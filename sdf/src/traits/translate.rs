@@ -1,5 +1,5 @@
-use super::{Sdf, SdfGrad, SdfInfo};
-use marchrs_vectors::Vector;
+use super::{Sdf, SdfBounds, SdfGrad, SdfInfo};
+use marchrs_vectors::{Aabb, Affine, Scalar, Vector};
 
 pub trait SdfTranslate<const N: usize>: Sdf<N> + Sized {
   fn translate(self, translation: impl Into<Vector<N>>) -> Translate<N, Self> {
@@ -12,35 +12,48 @@ impl<const N: usize, T: Sdf<N>> SdfTranslate<N> for T {}
 #[derive(Clone, Copy, PartialEq)]
 pub struct Translate<const N: usize, T>(pub T, pub Vector<N>);
 
+impl<const N: usize, T> Translate<N, T> {
+  /// The map from item space to world space
+  pub fn affine(&self) -> Affine<N> {
+    Affine::translation(self.1)
+  }
+}
+
 impl<const N: usize, T: Default> Default for Translate<N, T> {
   fn default() -> Self {
     Self(T::default(), Vector([0.0; N]))
   }
 }
 
-impl<const N: usize, T: Sdf<N>> Sdf<N> for Translate<N, T> {
+impl<const N: usize, S: Scalar, T: Sdf<N, S>> Sdf<N, S> for Translate<N, T> {
   #[inline]
-  fn call(&self, pos: Vector<N>) -> f64 {
-    self.0.call(pos - self.1)
+  fn call(&self, pos: Vector<N, S>) -> S {
+    self.0.call(pos - self.1.cast())
   }
 
   #[inline]
-  fn hits(&self, pos: Vector<N>) -> bool {
-    self.0.hits(pos - self.1)
+  fn hits(&self, pos: Vector<N, S>) -> bool {
+    self.0.hits(pos - self.1.cast())
   }
 }
 
-impl<const N: usize, T: SdfInfo<N>> SdfInfo<N> for Translate<N, T> {
+impl<const N: usize, S: Scalar, T: SdfInfo<N, S>> SdfInfo<N, S> for Translate<N, T> {
   type Info = T::Info;
   #[inline]
-  fn call_info(&self, pos: Vector<N>) -> (f64, Self::Info) {
-    self.0.call_info(pos - self.1)
+  fn call_info(&self, pos: Vector<N, S>) -> (S, Self::Info) {
+    self.0.call_info(pos - self.1.cast())
   }
 }
 
-impl<const N: usize, T: SdfGrad<N>> SdfGrad<N> for Translate<N, T> {
+impl<const N: usize, S: Scalar, T: SdfGrad<N, S>> SdfGrad<N, S> for Translate<N, T> {
   #[inline]
-  fn call_grad(&self, pos: Vector<N>) -> (f64, Vector<N>) {
-    self.0.call_grad(pos - self.1)
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
+    self.0.call_grad(pos - self.1.cast())
+  }
+}
+
+impl<const N: usize, T: SdfBounds<N>> SdfBounds<N> for Translate<N, T> {
+  fn bounds(&self) -> Aabb<N> {
+    self.0.bounds().transform(&self.affine())
   }
 }
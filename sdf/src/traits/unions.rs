@@ -1,5 +1,5 @@
-use super::{Sdf, SdfGrad, SdfInfo};
-use marchrs_vectors::Vector;
+use super::{Sdf, SdfBounds, SdfGrad, SdfInfo};
+use marchrs_vectors::{Aabb, Scalar, Vector};
 
 pub trait SdfUnion<const N: usize>: Sdf<N> + Sized {
   fn or<S: Sdf<N>>(self, other: S) -> Union<Self, S> {
@@ -12,23 +12,25 @@ impl<const N: usize, T: Sdf<N>> SdfUnion<N> for T {}
 #[derive(Clone, Copy, Default, PartialEq)]
 pub struct Union<T, U>(pub T, pub U);
 
-impl<const N: usize, T: Sdf<N>, U: Sdf<N>> Sdf<N> for Union<T, U> {
+impl<const N: usize, S: Scalar, T: Sdf<N, S>, U: Sdf<N, S>> Sdf<N, S> for Union<T, U> {
   #[inline]
-  fn call(&self, pos: Vector<N>) -> f64 {
+  fn call(&self, pos: Vector<N, S>) -> S {
     self.0.call(pos).min(self.1.call(pos))
   }
 
   #[inline]
-  fn hits(&self, pos: Vector<N>) -> bool {
+  fn hits(&self, pos: Vector<N, S>) -> bool {
     // `||` short circuits, so can cull function calls
     self.0.hits(pos) || self.1.hits(pos)
   }
 }
 
-impl<const N: usize, T: SdfInfo<N>, U: SdfInfo<N, Info = T::Info>> SdfInfo<N> for Union<T, U> {
+impl<const N: usize, S: Scalar, T: SdfInfo<N, S>, U: SdfInfo<N, S, Info = T::Info>> SdfInfo<N, S>
+  for Union<T, U>
+{
   type Info = T::Info;
   #[inline]
-  fn call_info(&self, pos: Vector<N>) -> (f64, Self::Info) {
+  fn call_info(&self, pos: Vector<N, S>) -> (S, Self::Info) {
     let (value0, info0) = self.0.call_info(pos);
     let (value1, info1) = self.1.call_info(pos);
     if value0 <= value1 {
@@ -39,9 +41,9 @@ impl<const N: usize, T: SdfInfo<N>, U: SdfInfo<N, Info = T::Info>> SdfInfo<N> fo
   }
 }
 
-impl<const N: usize, T: SdfGrad<N>, U: SdfGrad<N>> SdfGrad<N> for Union<T, U> {
+impl<const N: usize, S: Scalar, T: SdfGrad<N, S>, U: SdfGrad<N, S>> SdfGrad<N, S> for Union<T, U> {
   #[inline]
-  fn call_grad(&self, pos: Vector<N>) -> (f64, Vector<N>) {
+  fn call_grad(&self, pos: Vector<N, S>) -> (S, Vector<N, S>) {
     let (value0, grad0) = self.0.call_grad(pos);
     let (value1, grad1) = self.1.call_grad(pos);
     if value0 <= value1 {
@@ -51,3 +53,9 @@ impl<const N: usize, T: SdfGrad<N>, U: SdfGrad<N>> SdfGrad<N> for Union<T, U> {
     }
   }
 }
+
+impl<const N: usize, T: SdfBounds<N>, U: SdfBounds<N>> SdfBounds<N> for Union<T, U> {
+  fn bounds(&self) -> Aabb<N> {
+    self.0.bounds().union(self.1.bounds())
+  }
+}
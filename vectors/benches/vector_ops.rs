@@ -0,0 +1,117 @@
+//! Compares `Vector` arithmetic against plain scalar loops over arrays.
+//!
+//! Run with and without the `simd` feature to compare the two paths:
+//! ```bash
+//! cargo bench -p marchrs_vectors
+//! cargo bench -p marchrs_vectors --features simd
+//! ```
+//!
+//! On x86_64, built for the default target (µs, default / `simd`):
+//!
+//! | group       | vector      | scalar loop   |
+//! | ----------- | ----------- | ------------- |
+//! | `f64x3`     | 7.46 / 8.99 | 8.38 / 9.49   |
+//! | `f32x3`     | 8.26 / 8.90 | 8.63 / 10.60  |
+//! | `f32x4`     | 8.62 / 8.09 | 10.14 / 11.25 |
+//! | `f64 cross` | 1.95 / 1.86 | 2.33 / 1.60   |
+//! | `f32 cross` | 1.97 / 1.97 | 2.39 / 1.97   |
+//!
+//! Only `f32x4` takes the `SIMD` path. `Vector<3>` runs the same loops in both\
+//! builds, so the gaps in its rows are run to run noise. Padding 3 elements\
+//! out to 4 lanes was measured too, and ran slower (`f64x3` 10.40 / 13.66).
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use marchrs_vectors::{Scalar, Vector};
+use std::array;
+
+/// The number of vectors operated on per iteration
+const COUNT: usize = 1024;
+
+fn inputs<const N: usize, S: Scalar>() -> Vec<Vector<N, S>> {
+  (0..COUNT)
+    .map(|i| Vector(array::from_fn(|j| S::from_usize(i * N + j + 1).recip())))
+    .collect()
+}
+
+/// The operations in `examples/perf.rs` that dominate marching:\
+/// stepping along the ray, distance to a cuboid and normalising.
+fn bench_vector<const N: usize, S: Scalar>(c: &mut Criterion, name: &str) {
+  let xs = inputs::<N, S>();
+  let ys: Vec<_> = xs.iter().rev().copied().collect();
+  let mut group = c.benchmark_group(format!("{name}x{N}"));
+
+  group.bench_function("vector", |b| {
+    b.iter(|| {
+      let mut total = S::ZERO;
+      for (&x, &y) in black_box(&xs).iter().zip(black_box(&ys)) {
+        let step = x + y * S::from_f64(0.5);
+        let outside = (step.abs() - y).el_max(S::ZERO).mag();
+        total += outside + step.normal().dot(x.el_min(y));
+      }
+      total
+    })
+  });
+
+  group.bench_function("scalar", |b| {
+    b.iter(|| {
+      let mut total = S::ZERO;
+      for (&x, &y) in black_box(&xs).iter().zip(black_box(&ys)) {
+        let step: [S; N] = array::from_fn(|i| x[i] + y[i] * S::from_f64(0.5));
+        let outside: S = (0..N)
+          .map(|i| (step[i].abs() - y[i]).max(S::ZERO))
+          .map(|d| d * d)
+          .sum();
+        let mag = step.iter().map(|&s| s * s).sum::<S>().sqrt();
+        let dot: S = (0..N).map(|i| step[i] / mag * x[i].min(y[i])).sum();
+        total += outside.sqrt() + dot;
+      }
+      total
+    })
+  });
+
+  group.finish();
+}
+
+fn bench_cross<S: Scalar>(c: &mut Criterion, name: &str) {
+  let xs = inputs::<3, S>();
+  let ys: Vec<_> = xs.iter().rev().copied().collect();
+  let mut group = c.benchmark_group(format!("{name} cross"));
+
+  group.bench_function("vector", |b| {
+    b.iter(|| {
+      let mut total = Vector::zeros();
+      for (&x, &y) in black_box(&xs).iter().zip(black_box(&ys)) {
+        total = total + x.cross(y);
+      }
+      total
+    })
+  });
+
+  group.bench_function("scalar", |b| {
+    b.iter(|| {
+      let mut total = [S::ZERO; 3];
+      for (&x, &y) in black_box(&xs).iter().zip(black_box(&ys)) {
+        let [a0, a1, a2] = x.0;
+        let [b0, b1, b2] = y.0;
+        total[0] += a1 * b2 - a2 * b1;
+        total[1] += a2 * b0 - a0 * b2;
+        total[2] += a0 * b1 - a1 * b0;
+      }
+      total
+    })
+  });
+
+  group.finish();
+}
+
+fn benches(c: &mut Criterion) {
+  bench_vector::<2, f64>(c, "f64");
+  bench_vector::<3, f64>(c, "f64");
+  bench_vector::<4, f64>(c, "f64");
+  bench_vector::<3, f32>(c, "f32");
+  bench_vector::<4, f32>(c, "f32");
+  bench_cross::<f64>(c, "f64");
+  bench_cross::<f32>(c, "f32");
+}
+
+criterion_group!(vector_ops, benches);
+criterion_main!(vector_ops);
@@ -0,0 +1,203 @@
+use super::{Affine, Scalar, Vector};
+use std::array;
+
+/// An axis-aligned bounding box, containing all points with\
+/// `min[i] <= x[i] <= max[i]` on every axis `i`.
+///
+/// A box with `min[i] > max[i]` on any axis contains no points,\
+/// and infinite bounds can be used for unbounded regions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb<const N: usize, S = f64> {
+  pub min: Vector<N, S>,
+  pub max: Vector<N, S>,
+}
+
+// Constructors
+
+impl<const N: usize, S: Scalar> Aabb<N, S> {
+  #[inline]
+  pub fn new(min: impl Into<Vector<N, S>>, max: impl Into<Vector<N, S>>) -> Self {
+    Self {
+      min: min.into(),
+      max: max.into(),
+    }
+  }
+
+  /// The box containing no points
+  #[inline]
+  pub fn empty() -> Self {
+    Self::new(S::INFINITY, -S::INFINITY)
+  }
+
+  /// The box containing every point
+  #[inline]
+  pub fn infinite() -> Self {
+    Self::new(-S::INFINITY, S::INFINITY)
+  }
+
+  /// The box containing only `point`
+  #[inline]
+  pub fn point(point: impl Into<Vector<N, S>>) -> Self {
+    let point = point.into();
+    Self::new(point, point)
+  }
+
+  /// The smallest box containing all of `points`
+  pub fn from_points(points: impl IntoIterator<Item = Vector<N, S>>) -> Self {
+    points
+      .into_iter()
+      .fold(Self::empty(), |aabb, point| aabb.union(Self::point(point)))
+  }
+}
+
+// Properties
+
+impl<const N: usize, S: Scalar> Aabb<N, S> {
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.min.into_iter().zip(self.max).any(|(lo, hi)| lo > hi)
+  }
+
+  /// Whether the box is unbounded on any axis
+  #[inline]
+  pub fn is_finite(&self) -> bool {
+    self.min.into_iter().chain(self.max).all(S::is_finite)
+  }
+
+  #[inline]
+  pub fn center(&self) -> Vector<N, S> {
+    (self.min + self.max) * S::from_f64(0.5)
+  }
+
+  /// The side lengths of the box
+  #[inline]
+  pub fn size(&self) -> Vector<N, S> {
+    self.max - self.min
+  }
+
+  /// Whether `point` is inside or on the box
+  #[inline]
+  pub fn contains(&self, point: impl Into<Vector<N, S>>) -> bool {
+    let point = point.into();
+    (0..N).all(|i| self.min[i] <= point[i] && point[i] <= self.max[i])
+  }
+
+  /// The smallest box containing both boxes
+  #[inline]
+  pub fn union(self, rhs: Self) -> Self {
+    Self::new(self.min.el_min(rhs.min), self.max.el_max(rhs.max))
+  }
+
+  /// The box of points contained in both boxes, which may be empty
+  #[inline]
+  pub fn intersection(self, rhs: Self) -> Self {
+    Self::new(self.min.el_max(rhs.min), self.max.el_min(rhs.max))
+  }
+
+  /// Grows the box by `margin` in every direction.\
+  /// Negative margins shrink the box instead.
+  #[inline]
+  pub fn expand(self, margin: S) -> Self {
+    Self::new(self.min - margin, self.max + margin)
+  }
+
+  /// The smallest box containing the image of this box under `affine`.
+  ///
+  /// Each axis of the result is the sum of the absolute contributions\
+  /// of every input axis, i.e. `|linear| * size / 2` around the new center.
+  pub fn transform(self, affine: &Affine<N>) -> Self {
+    if self.is_empty() {
+      return self;
+    }
+    let center = *affine * self.center();
+    let half = self.size() * S::from_f64(0.5);
+    let half: Vector<N, S> = Vector(array::from_fn(|i| {
+      (0..N)
+        .filter(|&j| affine.linear[i][j] != 0.0)
+        .map(|j| S::from_f64(affine.linear[i][j].abs()) * half[j])
+        .sum()
+    }));
+    // infinite boxes have a `NaN` center, so only use it where bounded
+    Self::new(
+      Vector(array::from_fn(|i| {
+        if half[i].is_finite() {
+          center[i] - half[i]
+        } else {
+          -S::INFINITY
+        }
+      })),
+      Vector(array::from_fn(|i| {
+        if half[i].is_finite() {
+          center[i] + half[i]
+        } else {
+          S::INFINITY
+        }
+      })),
+    )
+  }
+
+  /// Intersects the ray `pos + t * dir` with the box, via the slab method.
+  ///
+  /// Returns the entry and exit `t` along the ray, or `None` if it misses.\
+  /// The entry is negative when `pos` starts inside the box,\
+  /// and rays pointing away from the box are treated as misses.
+  #[inline]
+  pub fn ray_intersect(&self, pos: Vector<N, S>, dir: Vector<N, S>) -> Option<(S, S)> {
+    if self.is_empty() {
+      return None;
+    }
+    let t0 = (self.min - pos) / dir;
+    let t1 = (self.max - pos) / dir;
+    // `min`/`max` ignore the `NaN`s from rays parallel to and on a slab
+    let entry = t0.el_min(t1).max().max(-S::INFINITY);
+    let exit = t0.el_max(t1).min().min(S::INFINITY);
+    (entry <= exit && exit >= S::ZERO).then_some((entry, exit))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::Matrix;
+  use std::f64::consts::PI;
+
+  #[test]
+  fn union_intersection() {
+    let a = Aabb::<2>::new([0.0, 0.0], [2.0, 1.0]);
+    let b = Aabb::<2>::new([1.0, -1.0], [3.0, 0.5]);
+    assert_eq!(a.union(b), Aabb::new([0.0, -1.0], [3.0, 1.0]));
+    assert_eq!(a.intersection(b), Aabb::new([1.0, 0.0], [2.0, 0.5]));
+    assert!(a.intersection(b.expand(-1.0)).is_empty());
+    assert_eq!(a.union(Aabb::empty()), a);
+  }
+
+  #[test]
+  fn transform_contains_corners() {
+    let aabb = Aabb::<3>::new([-1.0, 0.0, 2.0], [1.0, 0.5, 3.0]);
+    let affine = Affine::new(Matrix::<3>::rotation([1.0, 2.0, 3.0], PI / 5.0), [1.0; 3]);
+    let image = aabb.transform(&affine);
+    for i in 0..8 {
+      let corner = Vector(array::from_fn(|j| {
+        if i >> j & 1 == 0 {
+          aabb.min[j]
+        } else {
+          aabb.max[j]
+        }
+      }));
+      assert!(image.expand(1e-9).contains(affine * corner));
+    }
+  }
+
+  #[test]
+  fn ray_slab() {
+    let aabb = Aabb::<3>::new([-1.0; 3], [1.0; 3]);
+    let hit = aabb.ray_intersect(Vector([-3.0, 0.0, 0.0]), Vector([1.0, 0.0, 0.0]));
+    assert_eq!(hit, Some((2.0, 4.0)));
+    let inside = aabb.ray_intersect(Vector([0.0; 3]), Vector([0.0, 0.0, 1.0]));
+    assert_eq!(inside, Some((-1.0, 1.0)));
+    let miss = aabb.ray_intersect(Vector([-3.0, 2.0, 0.0]), Vector([1.0, 0.0, 0.0]));
+    assert_eq!(miss, None);
+    let behind = aabb.ray_intersect(Vector([3.0, 0.0, 0.0]), Vector([1.0, 0.0, 0.0]));
+    assert_eq!(behind, None);
+  }
+}
@@ -0,0 +1,145 @@
+use super::{Matrix, Quaternion, Scalar, Vector};
+use std::ops::Mul;
+
+/// An affine map `x -> linear * x + offset`.
+///
+/// This can represent any combination of translation, rotation,\
+/// (non-uniform) scaling, reflection and shearing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Affine<const N: usize> {
+  pub linear: Matrix<N>,
+  pub offset: Vector<N>,
+}
+
+impl<const N: usize> Default for Affine<N> {
+  fn default() -> Self {
+    Self::identity()
+  }
+}
+
+impl<const N: usize> From<Matrix<N>> for Affine<N> {
+  fn from(linear: Matrix<N>) -> Self {
+    Self::new(linear, Vector::zeros())
+  }
+}
+
+impl From<Quaternion> for Affine<3> {
+  fn from(rotation: Quaternion) -> Self {
+    Matrix::from(rotation).into()
+  }
+}
+
+// Constructors
+
+impl<const N: usize> Affine<N> {
+  #[inline]
+  pub fn new(linear: impl Into<Matrix<N>>, offset: impl Into<Vector<N>>) -> Self {
+    Self {
+      linear: linear.into(),
+      offset: offset.into(),
+    }
+  }
+
+  #[inline]
+  pub fn identity() -> Self {
+    Self::new(Matrix::identity(), Vector::zeros())
+  }
+
+  #[inline]
+  pub fn translation(offset: impl Into<Vector<N>>) -> Self {
+    Self::new(Matrix::identity(), offset)
+  }
+
+  /// Scaling by a (possibly different) factor on each axis
+  #[inline]
+  pub fn scaling(scale: impl Into<Vector<N>>) -> Self {
+    Matrix::diagonal(scale).into()
+  }
+
+  /// Shears axis `i` by `factor` times axis `j`,\
+  /// i.e. `x[i] -> x[i] + factor * x[j]`.
+  pub fn shear(i: usize, j: usize, factor: f64) -> Self {
+    debug_assert!(i != j, "Cannot shear an axis by itself, got axis {i}");
+    let mut linear = Matrix::identity();
+    linear[i][j] = factor;
+    linear.into()
+  }
+}
+
+// Properties
+
+impl<const N: usize> Affine<N> {
+  /// The inverse map, if the linear part is invertible.
+  pub fn inverse(self) -> Option<Self> {
+    let linear = self.linear.inverse()?;
+    Some(Self::new(linear, -(linear * self.offset)))
+  }
+
+  /// Applies only the linear part of the map,\
+  /// as is needed for directions rather than positions.
+  #[inline]
+  pub fn apply_dir<S: Scalar>(&self, dir: Vector<N, S>) -> Vector<N, S> {
+    self.linear * dir
+  }
+}
+
+/// Applies the map to a position
+impl<const N: usize, S: Scalar> Mul<Vector<N, S>> for Affine<N> {
+  type Output = Vector<N, S>;
+  fn mul(self, rhs: Vector<N, S>) -> Self::Output {
+    self.linear * rhs + self.offset.cast()
+  }
+}
+
+/// Composes maps, such that `(a * b) * v == a * (b * v)`
+impl<const N: usize> Mul<Affine<N>> for Affine<N> {
+  type Output = Affine<N>;
+  fn mul(self, rhs: Affine<N>) -> Self::Output {
+    Self::new(self.linear * rhs.linear, self * rhs.offset)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::f64::consts::PI;
+
+  fn close<const N: usize>(a: Vector<N>, b: Vector<N>) -> bool {
+    (a - b).mag() < 1e-12
+  }
+
+  fn sheared() -> Affine<3> {
+    Affine::translation([0.3, -1.0, 2.0])
+      * Affine::from(Matrix::<3>::rotation([1.0, 2.0, -1.0], PI / 5.0))
+      * Affine::shear(0, 2, 0.7)
+      * Affine::scaling([2.0, 0.5, 1.5])
+  }
+
+  #[test]
+  fn inverse_undoes() {
+    let affine = sheared();
+    let inverse = affine.inverse().expect("map should be invertible");
+    let pos = Vector([0.4, -0.2, 1.3]);
+    assert!(close(inverse * (affine * pos), pos));
+    assert!(close(affine * (inverse * pos), pos));
+    assert!((affine * inverse).linear.approx_eq(&Matrix::identity(), 1e-12));
+    assert!(close((inverse * affine).offset, Vector::zeros()));
+    assert_eq!(Affine::scaling([1.0, 0.0]).inverse(), None);
+  }
+
+  #[test]
+  fn composes_in_order() {
+    let (a, b) = (sheared(), Affine::shear(1, 0, -0.4) * Affine::translation([1.0; 3]));
+    let pos = Vector([0.4, -0.2, 1.3]);
+    assert!(close((a * b) * pos, a * (b * pos)));
+    assert!(close((b * a) * pos, b * (a * pos)));
+    assert!(!close((a * b) * pos, (b * a) * pos));
+    assert!(close(a.apply_dir(pos), (a * pos) - (a * Vector::zeros())));
+  }
+
+  #[test]
+  fn shears_one_axis() {
+    let shear = Affine::<3>::shear(0, 2, 0.5);
+    assert_eq!(shear * Vector([1.0, 2.0, 4.0]), Vector([3.0, 2.0, 4.0]));
+  }
+}
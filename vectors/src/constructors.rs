@@ -1,32 +1,32 @@
-use super::Vector;
+use super::{Scalar, Vector};
 
-impl<const N: usize> From<[f64; N]> for Vector<N> {
-  fn from(value: [f64; N]) -> Self {
+impl<const N: usize, S: Scalar> From<[S; N]> for Vector<N, S> {
+  fn from(value: [S; N]) -> Self {
     Self(value)
   }
 }
 
-impl<const N: usize> From<f64> for Vector<N> {
-  fn from(value: f64) -> Self {
+impl<const N: usize, S: Scalar> From<S> for Vector<N, S> {
+  fn from(value: S) -> Self {
     Self([value; N])
   }
 }
 
-impl<const N: usize> From<[usize; N]> for Vector<N> {
+impl<const N: usize, S: Scalar> From<[usize; N]> for Vector<N, S> {
   fn from(value: [usize; N]) -> Self {
-    Self(value.map(|x| x as f64))
+    Self(value.map(S::from_usize))
   }
 }
 
-impl<const N: usize> Vector<N> {
+impl<const N: usize, S: Scalar> Vector<N, S> {
   #[inline]
   pub fn zeros() -> Self {
-    Self([0.0; N])
+    Self([S::ZERO; N])
   }
 
   #[inline]
   pub fn ones() -> Self {
-    Self([1.0; N])
+    Self([S::ONE; N])
   }
 
   #[inline]
@@ -36,8 +36,8 @@ impl<const N: usize> Vector<N> {
       "Can only construct axis vectors for axes [0, {N})\n\
       Got axis {i} >= {N}.",
     );
-    let mut arr = [0.0; N];
-    arr[i] = 1.0;
+    let mut arr = [S::ZERO; N];
+    arr[i] = S::ONE;
     Self(arr)
   }
 }
@@ -0,0 +1,283 @@
+use super::{Scalar, Vector};
+use std::{
+  array,
+  cmp::Ordering,
+  iter::Sum,
+  ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+/// A dual number `value + grad · ε`, where `ε² = 0`,\
+/// tracking the gradient of a value with respect to `N` variables.
+///
+/// Evaluating any function generic over `Scalar` with dual numbers\
+/// computes its exact gradient alongside its value (forward-mode autodiff),\
+/// at the cost of `N` extra operations per operation.
+///
+/// Comparisons only consider the `value`, so that branches in the\
+/// function are taken exactly as they would be with plain scalars.
+#[derive(Clone, Copy, Debug)]
+pub struct Dual<const N: usize, S = f64> {
+  pub value: S,
+  pub grad: Vector<N, S>,
+}
+
+// Constructors
+
+impl<const N: usize, S: Scalar> Dual<N, S> {
+  #[inline]
+  pub fn new(value: S, grad: impl Into<Vector<N, S>>) -> Self {
+    Self {
+      value,
+      grad: grad.into(),
+    }
+  }
+
+  /// A value that doesn't depend on any variables
+  #[inline]
+  pub fn constant(value: S) -> Self {
+    Self::new(value, Vector::zeros())
+  }
+
+  /// The `i`th variable, with the given value
+  #[inline]
+  pub fn variable(value: S, i: usize) -> Self {
+    Self::new(value, Vector::axis(i))
+  }
+
+  /// Seeds each element of `pos` as a separate variable,\
+  /// so that the gradient of a result is with respect to `pos`.
+  #[inline]
+  pub fn variables(pos: Vector<N, S>) -> Vector<N, Self> {
+    Vector(array::from_fn(|i| Self::variable(pos[i], i)))
+  }
+
+  /// Applies the chain rule for `f(value)`, given `f` and its derivative.
+  #[inline]
+  fn chain(self, value: S, deriv: S) -> Self {
+    Self::new(value, self.grad * deriv)
+  }
+}
+
+impl<const N: usize, S: Scalar> PartialEq for Dual<N, S> {
+  fn eq(&self, other: &Self) -> bool {
+    self.value == other.value
+  }
+}
+
+impl<const N: usize, S: Scalar> PartialOrd for Dual<N, S> {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    self.value.partial_cmp(&other.value)
+  }
+}
+
+// Operators
+
+impl<const N: usize, S: Scalar> Neg for Dual<N, S> {
+  type Output = Self;
+  fn neg(self) -> Self::Output {
+    Self::new(-self.value, -self.grad)
+  }
+}
+
+impl<const N: usize, S: Scalar> Add for Dual<N, S> {
+  type Output = Self;
+  fn add(self, rhs: Self) -> Self::Output {
+    Self::new(self.value + rhs.value, self.grad + rhs.grad)
+  }
+}
+
+impl<const N: usize, S: Scalar> Sub for Dual<N, S> {
+  type Output = Self;
+  fn sub(self, rhs: Self) -> Self::Output {
+    Self::new(self.value - rhs.value, self.grad - rhs.grad)
+  }
+}
+
+impl<const N: usize, S: Scalar> Mul for Dual<N, S> {
+  type Output = Self;
+  fn mul(self, rhs: Self) -> Self::Output {
+    Self::new(
+      self.value * rhs.value,
+      self.grad * rhs.value + rhs.grad * self.value,
+    )
+  }
+}
+
+impl<const N: usize, S: Scalar> Div for Dual<N, S> {
+  type Output = Self;
+  fn div(self, rhs: Self) -> Self::Output {
+    let recip = rhs.value.recip();
+    Self::new(
+      self.value * recip,
+      (self.grad - rhs.grad * (self.value * recip)) * recip,
+    )
+  }
+}
+
+macro_rules! impl_assign {
+  ($($trait:ident::$fn:ident => $op:tt),*) => {$(
+    impl<const N: usize, S: Scalar> $trait for Dual<N, S> {
+      fn $fn(&mut self, rhs: Self) {
+        *self = *self $op rhs;
+      }
+    }
+  )*};
+}
+
+impl_assign!(
+  AddAssign::add_assign => +,
+  SubAssign::sub_assign => -,
+  MulAssign::mul_assign => *,
+  DivAssign::div_assign => /
+);
+
+impl<const N: usize, S: Scalar> Sum for Dual<N, S> {
+  fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+    iter.fold(Self::ZERO, Add::add)
+  }
+}
+
+impl<const N: usize, S: Scalar> Scalar for Dual<N, S> {
+  const ZERO: Self = Self::constant_const(S::ZERO);
+  const ONE: Self = Self::constant_const(S::ONE);
+  const EPSILON: Self = Self::constant_const(S::EPSILON);
+  const MIN: Self = Self::constant_const(S::MIN);
+  const MAX: Self = Self::constant_const(S::MAX);
+  const INFINITY: Self = Self::constant_const(S::INFINITY);
+  const NAN: Self = Self::constant_const(S::NAN);
+
+  #[inline]
+  fn from_f64(value: f64) -> Self {
+    Self::constant(S::from_f64(value))
+  }
+
+  #[inline]
+  fn to_f64(self) -> f64 {
+    self.value.to_f64()
+  }
+
+  #[inline]
+  fn abs(self) -> Self {
+    self.chain(self.value.abs(), self.value.signum())
+  }
+
+  #[inline]
+  fn signum(self) -> Self {
+    Self::constant(self.value.signum())
+  }
+
+  #[inline]
+  fn sqrt(self) -> Self {
+    let sqrt = self.value.sqrt();
+    self.chain(sqrt, (sqrt + sqrt).recip())
+  }
+
+  #[inline]
+  fn recip(self) -> Self {
+    let recip = self.value.recip();
+    self.chain(recip, -recip * recip)
+  }
+
+  #[inline]
+  fn floor(self) -> Self {
+    Self::constant(self.value.floor())
+  }
+
+  #[inline]
+  fn sin(self) -> Self {
+    self.chain(self.value.sin(), self.value.cos())
+  }
+
+  #[inline]
+  fn cos(self) -> Self {
+    self.chain(self.value.cos(), -self.value.sin())
+  }
+
+  #[inline]
+  fn tan(self) -> Self {
+    let tan = self.value.tan();
+    self.chain(tan, S::ONE + tan * tan)
+  }
+
+  #[inline]
+  fn acos(self) -> Self {
+    let deriv = -(S::ONE - self.value * self.value).sqrt().recip();
+    self.chain(self.value.acos(), deriv)
+  }
+
+  #[inline]
+  fn atan2(self, other: Self) -> Self {
+    let (y, x) = (self.value, other.value);
+    let scale = (x * x + y * y).recip();
+    Self::new(y.atan2(x), (self.grad * x - other.grad * y) * scale)
+  }
+
+  /// The dual with the smaller value, ignoring `NaN`s
+  #[inline]
+  fn min(self, other: Self) -> Self {
+    if self.value.is_nan() || other.value < self.value {
+      other
+    } else {
+      self
+    }
+  }
+
+  /// The dual with the larger value, ignoring `NaN`s
+  #[inline]
+  fn max(self, other: Self) -> Self {
+    if self.value.is_nan() || other.value > self.value {
+      other
+    } else {
+      self
+    }
+  }
+
+  #[inline]
+  fn is_finite(self) -> bool {
+    self.value.is_finite()
+  }
+
+  #[inline]
+  fn is_nan(self) -> bool {
+    self.value.is_nan()
+  }
+}
+
+impl<const N: usize, S: Scalar> Dual<N, S> {
+  /// `constant` for use in `const` contexts
+  const fn constant_const(value: S) -> Self {
+    Self {
+      value,
+      grad: Vector([S::ZERO; N]),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn grad<const N: usize>(f: impl Fn(Vector<N, Dual<N>>) -> Dual<N>, pos: [f64; N]) -> Vector<N> {
+    f(Dual::variables(Vector(pos))).grad
+  }
+
+  #[test]
+  fn vector_ops() {
+    let pos = [1.0, -2.0, 2.0];
+    assert_eq!(grad(|p| p.mag(), pos), Vector(pos) / 3.0);
+    assert_eq!(grad(|p| p.dot(p), pos), Vector(pos) * 2.0);
+    assert_eq!(grad(|p| p.abs().max(), pos), Vector([0.0, -1.0, 0.0]));
+  }
+
+  #[test]
+  fn chain_rule() {
+    let x = 0.3;
+    let dual = Dual::<1>::variable(x, 0);
+    assert!(((dual.sin() * dual.cos()).grad[0] - (2.0 * x).cos()).abs() < 1e-12);
+    assert!((dual.acos().grad[0] + 1.0 / (1.0 - x * x).sqrt()).abs() < 1e-12);
+    assert!(((dual / (dual + Dual::ONE)).grad[0] - 1.0 / (1.0 + x).powi(2)).abs() < 1e-12);
+    let y = Dual::<2>::variable(2.0, 1);
+    let angle = Dual::<2>::variable(1.0, 0).atan2(y);
+    assert_eq!(angle.grad, Vector([0.4, -0.2]));
+  }
+}
@@ -0,0 +1,192 @@
+use super::{Scalar, Vector};
+
+impl<const N: usize, S: Scalar> Vector<N, S> {
+  /// Linear interpolation, from `self` at `t = 0` to `other` at `t = 1`
+  #[inline]
+  pub fn lerp(self, other: Self, t: S) -> Self {
+    self + (other - self) * t
+  }
+
+  /// Element-wise clamp between `min` and `max`
+  #[inline]
+  pub fn clamp(self, min: impl Into<Self>, max: impl Into<Self>) -> Self {
+    self.el_max(min).el_min(max)
+  }
+
+  /// Distance between two points
+  #[inline]
+  pub fn distance(self, other: Self) -> S {
+    (self - other).mag()
+  }
+
+  /// Whether every element is finite, i.e. not infinite or `NaN`
+  #[inline]
+  pub fn is_finite(self) -> bool {
+    self.into_iter().all(S::is_finite)
+  }
+
+  /// The component of `self` parallel to `other`
+  #[inline]
+  pub fn project_onto(self, other: Self) -> Self {
+    other * (self.dot(other) / other.mag2())
+  }
+
+  /// The component of `self` perpendicular to `other`
+  #[inline]
+  pub fn reject_from(self, other: Self) -> Self {
+    self - self.project_onto(other)
+  }
+
+  /// The angle between two vectors in `[0, π]` radians.
+  ///
+  /// Uses Kahan's formula, which stays accurate for nearly parallel vectors\
+  /// where `acos` of the normalised dot product loses precision.
+  #[inline]
+  pub fn angle_between(self, other: Self) -> S {
+    let a = self * other.mag();
+    let b = other * self.mag();
+    let two = S::from_f64(2.0);
+    two * (a - b).mag().atan2((a + b).mag())
+  }
+
+  /// Reflects a direction in the plane with unit `normal`
+  #[inline]
+  pub fn reflect(self, normal: Self) -> Self {
+    self - normal * (S::from_f64(2.0) * self.dot(normal))
+  }
+
+  /// Refracts a unit direction through a surface with unit `normal`,\
+  /// facing against the direction, where `eta` is the ratio of the\
+  /// refractive indices `outside / inside`.
+  ///
+  /// Returns `None` on total internal reflection.
+  #[inline]
+  pub fn refract(self, normal: Self, eta: S) -> Option<Self> {
+    let cos_in = -self.dot(normal);
+    let sin2_out = eta * eta * (S::ONE - cos_in * cos_in);
+    if sin2_out > S::ONE {
+      return None;
+    }
+    let cos_out = (S::ONE - sin2_out).sqrt();
+    Some(self * eta + normal * (eta * cos_in - cos_out))
+  }
+}
+
+impl<S: Scalar> Vector<2, S> {
+  /// Polar coordinates `(radius, angle)`,\
+  /// with the angle anticlockwise from the `x` axis in `(-π, π]`.
+  #[inline]
+  pub fn to_polar(self) -> (S, S) {
+    (self.mag(), self[1].atan2(self[0]))
+  }
+
+  #[inline]
+  pub fn from_polar(radius: S, angle: S) -> Self {
+    Vector([angle.cos(), angle.sin()]) * radius
+  }
+}
+
+impl<S: Scalar> Vector<3, S> {
+  /// Two unit vectors perpendicular to `self` and each other,\
+  /// such that `(tangent, bitangent, self)` is right handed.
+  ///
+  /// `self` must be a unit vector.\
+  /// see [Duff et al.](https://jcgt.org/published/0006/01/01/)
+  #[inline]
+  pub fn orthonormal_basis(self) -> (Self, Self) {
+    let Vector([x, y, z]) = self;
+    let sign = z.signum();
+    let a = -(sign + z).recip();
+    let b = x * y * a;
+    (
+      Vector([S::ONE + sign * x * x * a, sign * b, -sign * x]),
+      Vector([b, sign + y * y * a, -y]),
+    )
+  }
+
+  /// Spherical coordinates `(radius, polar, azimuth)`, where
+  /// - `polar` is the angle from the `z` axis, in `[0, π]`
+  /// - `azimuth` is the angle anticlockwise from the `x` axis\
+  ///   in the `xy` plane, in `(-π, π]`
+  #[inline]
+  pub fn to_spherical(self) -> (S, S, S) {
+    let radius = self.mag();
+    let Vector([x, y, z]) = self;
+    let planar = (x * x + y * y).sqrt();
+    (radius, planar.atan2(z), y.atan2(x))
+  }
+
+  #[inline]
+  pub fn from_spherical(radius: S, polar: S, azimuth: S) -> Self {
+    let planar = polar.sin();
+    Vector([planar * azimuth.cos(), planar * azimuth.sin(), polar.cos()]) * radius
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+  fn close<const N: usize>(a: Vector<N>, b: Vector<N>) -> bool {
+    a.distance(b) < 1e-12
+  }
+
+  #[test]
+  fn projections() {
+    let v = Vector([3.0, 4.0, 5.0]);
+    let axis = Vector([0.0, 2.0, 0.0]);
+    assert_eq!(v.project_onto(axis), Vector([0.0, 4.0, 0.0]));
+    assert_eq!(v.reject_from(axis), Vector([3.0, 0.0, 5.0]));
+    assert_eq!(v.clamp(3.5, [5.0, 5.0, 4.5]), Vector([3.5, 4.0, 4.5]));
+    assert_eq!(v.lerp(-v, 0.5), Vector::zeros());
+    assert!(!Vector([1.0, f64::NAN]).is_finite());
+  }
+
+  #[test]
+  fn angles() {
+    let x = Vector([1.0, 0.0, 0.0]);
+    let y = Vector([0.0, 2.0, 0.0]);
+    assert!((x.angle_between(y) - FRAC_PI_2).abs() < 1e-12);
+    assert!((x.angle_between(x + y * 0.5) - FRAC_PI_4).abs() < 1e-12);
+    assert_eq!(x.angle_between(x * 3.0), 0.0);
+  }
+
+  #[test]
+  fn reflect_refract() {
+    let normal = Vector([0.0, 1.0]);
+    let dir = Vector([1.0, -1.0]).normal();
+    assert!(close(dir.reflect(normal), Vector([1.0, 1.0]).normal()));
+    assert!(close(dir.refract(normal, 1.0).unwrap(), dir));
+
+    // Snell's law: sin(out) = eta * sin(in)
+    let out = dir.refract(normal, 1.0 / 1.5).unwrap();
+    assert!((out[0] - dir[0] / 1.5).abs() < 1e-12);
+    assert!((out.mag() - 1.0).abs() < 1e-12);
+    assert_eq!(dir.refract(normal, 1.5), None);
+  }
+
+  #[test]
+  fn bases() {
+    for normal in [[0.0, 0.0, 1.0], [0.0, 0.0, -1.0], [1.0, 2.0, -2.0]] {
+      let normal = Vector(normal).normal();
+      let (tangent, bitangent) = normal.orthonormal_basis();
+      assert!(tangent.dot(normal).abs() < 1e-12);
+      assert!(bitangent.dot(normal).abs() < 1e-12);
+      assert!(tangent.dot(bitangent).abs() < 1e-12);
+      assert!(close(tangent.cross(bitangent), normal));
+    }
+  }
+
+  #[test]
+  fn coordinates() {
+    let v = Vector([1.0, -2.0, 2.0]);
+    let (radius, polar, azimuth) = v.to_spherical();
+    assert!(close(Vector::from_spherical(radius, polar, azimuth), v));
+    let (radius, angle) = Vector([-1.0, 1.0]).to_polar();
+    assert!(close(
+      Vector::from_polar(radius, angle),
+      Vector([-1.0, 1.0])
+    ));
+  }
+}
@@ -0,0 +1,490 @@
+use super::{Aabb, Scalar, Vector};
+use std::{
+  cmp::Ordering,
+  f64::consts::{FRAC_PI_2, PI, TAU},
+  iter::Sum,
+  ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+/// A closed interval `[lo, hi]` of values.
+///
+/// Evaluating a function generic over `Scalar` with intervals\
+/// bounds the values it can take over a whole region at once,\
+/// ignoring floating point rounding.
+///
+/// Intervals only compare as ordered when they can't overlap,\
+/// and as equal when they're both the same single value,\
+/// so comparisons only hold when they'd hold for every value.
+#[derive(Clone, Copy, Debug)]
+pub struct Interval<S = f64> {
+  pub lo: S,
+  pub hi: S,
+}
+
+// Constructors
+
+impl<S: Scalar> Interval<S> {
+  /// The interval between `a` and `b`, in either order
+  #[inline]
+  pub fn new(a: S, b: S) -> Self {
+    if b < a {
+      Self { lo: b, hi: a }
+    } else {
+      Self { lo: a, hi: b }
+    }
+  }
+
+  /// The interval containing only `value`
+  #[inline]
+  pub const fn point(value: S) -> Self {
+    Self {
+      lo: value,
+      hi: value,
+    }
+  }
+
+  /// The interval containing every value
+  #[inline]
+  pub fn entire() -> Self {
+    Self {
+      lo: -S::INFINITY,
+      hi: S::INFINITY,
+    }
+  }
+
+  /// The smallest interval containing all of `values`
+  fn hull(values: impl IntoIterator<Item = S>) -> Self {
+    let mut values = values.into_iter();
+    let first = values.next().unwrap_or(S::NAN);
+    values.fold(Self::point(first), |acc, x| Self {
+      lo: acc.lo.min(x),
+      hi: acc.hi.max(x),
+    })
+  }
+}
+
+/// The intervals covering each axis of the box
+impl<const N: usize, S: Scalar> From<Aabb<N, S>> for Vector<N, Interval<S>> {
+  fn from(aabb: Aabb<N, S>) -> Self {
+    Vector(std::array::from_fn(|i| {
+      Interval::new(aabb.min[i], aabb.max[i])
+    }))
+  }
+}
+
+// Properties
+
+impl<S: Scalar> Interval<S> {
+  #[inline]
+  pub fn width(&self) -> S {
+    self.hi - self.lo
+  }
+
+  #[inline]
+  pub fn mid(&self) -> S {
+    (self.lo + self.hi) * S::from_f64(0.5)
+  }
+
+  #[inline]
+  pub fn contains(&self, value: S) -> bool {
+    self.lo <= value && value <= self.hi
+  }
+
+  /// The interval is unchanged by `f` when `f` is increasing
+  #[inline]
+  fn map_increasing(self, f: impl Fn(S) -> S) -> Self {
+    Self {
+      lo: f(self.lo),
+      hi: f(self.hi),
+    }
+  }
+
+  /// Whether `lo <= x + k * period <= hi` for some integer `k`
+  fn contains_periodic(&self, x: S, period: S) -> bool {
+    let k = ((self.lo - x) / period).floor() + S::ONE;
+    self.contains(x + k * period) || self.contains(x + (k - S::ONE) * period)
+  }
+}
+
+impl<S: Scalar> PartialEq for Interval<S> {
+  fn eq(&self, other: &Self) -> bool {
+    self.partial_cmp(other) == Some(Ordering::Equal)
+  }
+}
+
+impl<S: Scalar> PartialOrd for Interval<S> {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    if self.hi < other.lo {
+      Some(Ordering::Less)
+    } else if self.lo > other.hi {
+      Some(Ordering::Greater)
+    } else if self.lo == self.hi && other.lo == other.hi && self.lo == other.lo {
+      Some(Ordering::Equal)
+    } else {
+      None
+    }
+  }
+}
+
+// Operators
+
+impl<S: Scalar> Neg for Interval<S> {
+  type Output = Self;
+  fn neg(self) -> Self::Output {
+    Self {
+      lo: -self.hi,
+      hi: -self.lo,
+    }
+  }
+}
+
+impl<S: Scalar> Add for Interval<S> {
+  type Output = Self;
+  fn add(self, rhs: Self) -> Self::Output {
+    Self {
+      lo: self.lo + rhs.lo,
+      hi: self.hi + rhs.hi,
+    }
+  }
+}
+
+impl<S: Scalar> Sub for Interval<S> {
+  type Output = Self;
+  fn sub(self, rhs: Self) -> Self::Output {
+    Self {
+      lo: self.lo - rhs.hi,
+      hi: self.hi - rhs.lo,
+    }
+  }
+}
+
+impl<S: Scalar> Mul for Interval<S> {
+  type Output = Self;
+  fn mul(self, rhs: Self) -> Self::Output {
+    // `0 * inf` is `NaN`, but should be `0` for bounds
+    let mul = |a: S, b: S| {
+      if a == S::ZERO || b == S::ZERO {
+        S::ZERO
+      } else {
+        a * b
+      }
+    };
+    Self::hull([
+      mul(self.lo, rhs.lo),
+      mul(self.lo, rhs.hi),
+      mul(self.hi, rhs.lo),
+      mul(self.hi, rhs.hi),
+    ])
+  }
+}
+
+impl<S: Scalar> Div for Interval<S> {
+  type Output = Self;
+  #[allow(clippy::suspicious_arithmetic_impl)]
+  fn div(self, rhs: Self) -> Self::Output {
+    self * rhs.recip()
+  }
+}
+
+macro_rules! impl_assign {
+  ($($trait:ident::$fn:ident => $op:tt),*) => {$(
+    impl<S: Scalar> $trait for Interval<S> {
+      fn $fn(&mut self, rhs: Self) {
+        *self = *self $op rhs;
+      }
+    }
+  )*};
+}
+
+impl_assign!(
+  AddAssign::add_assign => +,
+  SubAssign::sub_assign => -,
+  MulAssign::mul_assign => *,
+  DivAssign::div_assign => /
+);
+
+impl<S: Scalar> Sum for Interval<S> {
+  fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+    iter.fold(Self::ZERO, Add::add)
+  }
+}
+
+impl<S: Scalar> Scalar for Interval<S> {
+  const ZERO: Self = Self::point(S::ZERO);
+  const ONE: Self = Self::point(S::ONE);
+  const EPSILON: Self = Self::point(S::EPSILON);
+  const MIN: Self = Self::point(S::MIN);
+  const MAX: Self = Self::point(S::MAX);
+  const INFINITY: Self = Self::point(S::INFINITY);
+  const NAN: Self = Self::point(S::NAN);
+
+  #[inline]
+  fn from_f64(value: f64) -> Self {
+    Self::point(S::from_f64(value))
+  }
+
+  /// The midpoint of the interval
+  #[inline]
+  fn to_f64(self) -> f64 {
+    self.mid().to_f64()
+  }
+
+  fn abs(self) -> Self {
+    if self.lo >= S::ZERO {
+      self
+    } else if self.hi <= S::ZERO {
+      -self
+    } else {
+      Self {
+        lo: S::ZERO,
+        hi: self.hi.max(-self.lo),
+      }
+    }
+  }
+
+  #[inline]
+  fn signum(self) -> Self {
+    self.map_increasing(S::signum)
+  }
+
+  /// Negative values are clamped to `0`
+  #[inline]
+  fn sqrt(self) -> Self {
+    self.map_increasing(|x| x.max(S::ZERO).sqrt())
+  }
+
+  fn recip(self) -> Self {
+    if self.lo > S::ZERO || self.hi < S::ZERO {
+      Self::new(self.hi.recip(), self.lo.recip())
+    } else if self.lo == S::ZERO && self.hi > S::ZERO {
+      Self::new(self.hi.recip(), S::INFINITY)
+    } else if self.hi == S::ZERO && self.lo < S::ZERO {
+      Self::new(-S::INFINITY, self.lo.recip())
+    } else {
+      Self::entire()
+    }
+  }
+
+  #[inline]
+  fn floor(self) -> Self {
+    self.map_increasing(S::floor)
+  }
+
+  fn sin(self) -> Self {
+    let tau = S::from_f64(TAU);
+    if self.width() >= tau {
+      return Self::new(-S::ONE, S::ONE);
+    }
+    let mut result = Self::new(self.lo.sin(), self.hi.sin());
+    if self.contains_periodic(S::from_f64(FRAC_PI_2), tau) {
+      result.hi = S::ONE;
+    }
+    if self.contains_periodic(S::from_f64(-FRAC_PI_2), tau) {
+      result.lo = -S::ONE;
+    }
+    result
+  }
+
+  fn cos(self) -> Self {
+    let tau = S::from_f64(TAU);
+    if self.width() >= tau {
+      return Self::new(-S::ONE, S::ONE);
+    }
+    let mut result = Self::new(self.lo.cos(), self.hi.cos());
+    if self.contains_periodic(S::ZERO, tau) {
+      result.hi = S::ONE;
+    }
+    if self.contains_periodic(S::from_f64(PI), tau) {
+      result.lo = -S::ONE;
+    }
+    result
+  }
+
+  fn tan(self) -> Self {
+    let pi = S::from_f64(PI);
+    if self.width() >= pi || self.contains_periodic(S::from_f64(FRAC_PI_2), pi) {
+      return Self::entire();
+    }
+    self.map_increasing(S::tan)
+  }
+
+  /// Values outside `[-1, 1]` are clamped
+  #[inline]
+  fn acos(self) -> Self {
+    let clamp = |x: S| x.clamp(-S::ONE, S::ONE).acos();
+    Self::new(clamp(self.hi), clamp(self.lo))
+  }
+
+  /// `atan2` only takes its extremes on the corners of a box,\
+  /// unless the box touches the branch cut along the negative `x` axis.
+  fn atan2(self, other: Self) -> Self {
+    if self.lo > S::ZERO || self.hi < S::ZERO || other.lo > S::ZERO {
+      Self::hull([
+        self.lo.atan2(other.lo),
+        self.lo.atan2(other.hi),
+        self.hi.atan2(other.lo),
+        self.hi.atan2(other.hi),
+      ])
+    } else {
+      let pi = S::from_f64(PI);
+      Self::new(-pi, pi)
+    }
+  }
+
+  /// The interval of minimums, ignoring `NaN`s
+  #[inline]
+  fn min(self, other: Self) -> Self {
+    if self.is_nan() {
+      return other;
+    }
+    if other.is_nan() {
+      return self;
+    }
+    Self {
+      lo: self.lo.min(other.lo),
+      hi: self.hi.min(other.hi),
+    }
+  }
+
+  /// The interval of maximums, ignoring `NaN`s
+  #[inline]
+  fn max(self, other: Self) -> Self {
+    if self.is_nan() {
+      return other;
+    }
+    if other.is_nan() {
+      return self;
+    }
+    Self {
+      lo: self.lo.max(other.lo),
+      hi: self.hi.max(other.hi),
+    }
+  }
+
+  /// Both arms are covered when the interval contains `0`,\
+  /// and every value when either arm is undefined.
+  fn if_positive(self, then: Self, otherwise: Self) -> Self {
+    if self.lo > S::ZERO {
+      then
+    } else if self.hi <= S::ZERO {
+      otherwise
+    } else if then.is_nan() || otherwise.is_nan() {
+      Self::entire()
+    } else {
+      Self {
+        lo: then.lo.min(otherwise.lo),
+        hi: then.hi.max(otherwise.hi),
+      }
+    }
+  }
+
+  #[inline]
+  fn is_point(self) -> bool {
+    self.lo == self.hi
+  }
+
+  #[inline]
+  fn is_finite(self) -> bool {
+    self.lo.is_finite() && self.hi.is_finite()
+  }
+
+  #[inline]
+  fn is_nan(self) -> bool {
+    self.lo.is_nan() || self.hi.is_nan()
+  }
+
+  /// Squares each element separately, as `x * x` for an interval\
+  /// containing `0` would otherwise include negative values.
+  #[inline]
+  fn lanes_mag2<const N: usize>(xs: [Self; N]) -> Self {
+    xs.into_iter()
+      .map(|x| {
+        let x = x.abs();
+        Self {
+          lo: x.lo * x.lo,
+          hi: x.hi * x.hi,
+        }
+      })
+      .sum()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sample(interval: Interval) -> impl Iterator<Item = f64> {
+    (0..=100).map(move |i| interval.lo + interval.width() * i as f64 / 100.0)
+  }
+
+  type Function = (fn(Interval) -> Interval, fn(f64) -> f64);
+
+  #[test]
+  fn bounds_functions() {
+    let functions: [Function; 5] = [
+      (|x| x.sin(), f64::sin),
+      (|x| x.cos(), f64::cos),
+      (|x| x * x - x, |x| x * x - x),
+      (|x| x.abs().sqrt(), |x| x.abs().sqrt()),
+      (|x| x.atan2(Interval::ONE - x), |x| x.atan2(1.0 - x)),
+    ];
+    for (lo, hi) in [(-0.5, 0.25), (1.0, 2.5), (-4.0, 3.0), (2.0, 2.0)] {
+      let interval = Interval::new(lo, hi);
+      for (f_interval, f) in functions {
+        let bounds = f_interval(interval);
+        for x in sample(interval) {
+          assert!(bounds.contains(f(x)), "{} outside {bounds:?}", f(x));
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn magnitude() {
+    let pos = Vector::from(Aabb::<2>::new([-1.0, 3.0], [2.0, 4.0]));
+    let mag = pos.mag();
+    assert_eq!((mag.lo, mag.hi), (3.0, 20f64.sqrt()));
+  }
+
+  #[test]
+  fn comparisons() {
+    let a = Interval::new(0.0, 1.0);
+    assert!(a < Interval::point(1.5));
+    assert!(a <= Interval::point(1.5));
+    assert_eq!(a.partial_cmp(&Interval::point(1.0)), None);
+    assert_eq!(a.partial_cmp(&Interval::point(0.5)), None);
+    assert!(a != Interval::new(0.0, 1.0));
+    assert!(Interval::point(1.0) == Interval::ONE);
+  }
+
+  #[test]
+  fn branches_cover_both_arms() {
+    let (then, otherwise) = (Interval::new(1.0, 2.0), Interval::point(-1.0));
+    let pick = |cond: (f64, f64), then: Interval| {
+      let picked = Interval::new(cond.0, cond.1).if_positive(then, otherwise);
+      (picked.lo, picked.hi)
+    };
+    assert_eq!(pick((0.5, 1.0), then), (1.0, 2.0));
+    assert_eq!(pick((-1.0, 0.0), then), (-1.0, -1.0));
+    assert_eq!(pick((-1.0, 1.0), then), (-1.0, 2.0));
+    let entire = (-f64::INFINITY, f64::INFINITY);
+    assert_eq!(pick((-1.0, 1.0), Interval::NAN), entire);
+  }
+
+  #[test]
+  fn points() {
+    assert!(Interval::point(1.5).is_point());
+    assert!(!Interval::new(1.0, 1.5).is_point());
+    assert!(!Interval::<f64>::entire().is_point());
+  }
+
+  #[test]
+  fn clamps_like_scalars() {
+    // Unlike `f64::clamp`, crossed or `NaN` bounds don't panic
+    for (x, min, max) in [(0.5, 1.0, 0.0), (2.0, f64::NAN, 1.0), (-1.0, 0.0, f64::NAN)] {
+      let clamped = Scalar::clamp(x, min, max);
+      let interval = Interval::point(x).clamp(Interval::point(min), Interval::point(max));
+      assert_eq!((interval.lo, interval.hi), (clamped, clamped));
+    }
+  }
+}
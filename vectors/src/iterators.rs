@@ -1,30 +1,39 @@
-use super::Vector;
-use marchrs_iterators::{MultiDims, MultiRangeIter, ParIter, Splittable};
+use super::{Scalar, Vector};
+use marchrs_iterators::{MultiDims, MultiRegion, MultiRegionIter, ParIter, Splittable};
 use rayon::iter::IntoParallelIterator;
 use std::ops::RangeInclusive;
 
-pub struct GridIter<const N: usize> {
-  iter: MultiRangeIter<N>,
-  start: Vector<N>,
-  steps: Vector<N>,
+pub struct GridIter<const N: usize, S = f64> {
+  iter: MultiRegionIter<N>,
+  start: Vector<N, S>,
+  steps: Vector<N, S>,
 }
 
-impl<const N: usize> GridIter<N> {
-  pub fn new(range: RangeInclusive<Vector<N>>, dims: impl Into<MultiDims<N>>) -> Self {
+impl<const N: usize, S: Scalar> GridIter<N, S> {
+  pub fn new(range: RangeInclusive<Vector<N, S>>, dims: impl Into<MultiDims<N>>) -> Self {
     let dims: MultiDims<N> = dims.into();
-    let iter: MultiRangeIter<N> = dims.into();
+    Self::in_region(range, dims.into())
+  }
+
+  /// Only the points of the grid within `region`, where `region.parent`\
+  /// gives the number of points in the whole grid.
+  pub fn in_region(range: RangeInclusive<Vector<N, S>>, region: MultiRegion<N>) -> Self {
     let start = *range.start();
     let end = *range.end();
-    let steps = (end - start) / (Vector::from(dims.0) - 1.0);
-    Self { iter, start, steps }
+    let steps = (end - start) / (Vector::from(region.parent.0) - S::ONE);
+    Self {
+      iter: region.into(),
+      start,
+      steps,
+    }
   }
 }
 
-impl<const N: usize> Iterator for GridIter<N> {
-  type Item = Vector<N>;
+impl<const N: usize, S: Scalar> Iterator for GridIter<N, S> {
+  type Item = Vector<N, S>;
   fn next(&mut self) -> Option<Self::Item> {
     let loc = self.iter.next()?;
-    let pos: Vector<N> = loc.into();
+    let pos: Vector<N, S> = loc.into();
     Some(self.start + self.steps * pos)
   }
 
@@ -33,17 +42,17 @@ impl<const N: usize> Iterator for GridIter<N> {
   }
 }
 
-impl<const N: usize> ExactSizeIterator for GridIter<N> {}
+impl<const N: usize, S: Scalar> ExactSizeIterator for GridIter<N, S> {}
 
-impl<const N: usize> DoubleEndedIterator for GridIter<N> {
+impl<const N: usize, S: Scalar> DoubleEndedIterator for GridIter<N, S> {
   fn next_back(&mut self) -> Option<Self::Item> {
     let loc = self.iter.next_back()?;
-    let pos: Vector<N> = loc.into();
+    let pos: Vector<N, S> = loc.into();
     Some(self.start + self.steps * pos)
   }
 }
 
-impl<const N: usize> Splittable for GridIter<N> {
+impl<const N: usize, S: Scalar> Splittable for GridIter<N, S> {
   fn split_at(self, index: usize) -> (Self, Self) {
     let (iter0, iter1) = self.iter.split_at(index);
     (
@@ -59,7 +68,7 @@ impl<const N: usize> Splittable for GridIter<N> {
   }
 }
 
-impl<const N: usize> IntoParallelIterator for GridIter<N> {
+impl<const N: usize, S: Scalar> IntoParallelIterator for GridIter<N, S> {
   type Item = <Self as Iterator>::Item;
   type Iter = ParIter<Self>;
   fn into_par_iter(self) -> Self::Iter {
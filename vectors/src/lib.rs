@@ -1,29 +1,33 @@
-use core::f64;
 use std::{
   array,
   ops::{Deref, DerefMut, Mul},
 };
 
+mod scalar;
+pub use scalar::Scalar;
+#[cfg(feature = "simd")]
+mod simd;
+
 /// A very simple mathematical vector implementation
 #[derive(Clone, Copy, Debug, PartialEq)]
-pub struct Vector<const N: usize>(pub [f64; N]);
+pub struct Vector<const N: usize, S = f64>(pub [S; N]);
 
 mod constructors;
 
-impl<const N: usize> Deref for Vector<N> {
-  type Target = [f64; N];
+impl<const N: usize, S> Deref for Vector<N, S> {
+  type Target = [S; N];
   fn deref(&self) -> &Self::Target {
     &self.0
   }
 }
-impl<const N: usize> DerefMut for Vector<N> {
+impl<const N: usize, S> DerefMut for Vector<N, S> {
   fn deref_mut(&mut self) -> &mut Self::Target {
     &mut self.0
   }
 }
-impl<const N: usize> IntoIterator for Vector<N> {
-  type Item = f64;
-  type IntoIter = array::IntoIter<f64, N>;
+impl<const N: usize, S> IntoIterator for Vector<N, S> {
+  type Item = S;
+  type IntoIter = array::IntoIter<S, N>;
   fn into_iter(self) -> Self::IntoIter {
     self.0.into_iter()
   }
@@ -31,11 +35,11 @@ impl<const N: usize> IntoIterator for Vector<N> {
 
 mod operators;
 
-impl<const N: usize> Vector<N> {
+impl<const N: usize, S: Scalar> Vector<N, S> {
   /// Minimum of a vector
   #[inline]
-  pub fn min(self) -> f64 {
-    let mut result = f64::NAN;
+  pub fn min(self) -> S {
+    let mut result = S::NAN;
     for x in self {
       result = result.min(x);
     }
@@ -44,8 +48,8 @@ impl<const N: usize> Vector<N> {
 
   /// Maximum of a vector
   #[inline]
-  pub fn max(self) -> f64 {
-    let mut result = f64::NAN;
+  pub fn max(self) -> S {
+    let mut result = S::NAN;
     for x in self {
       result = result.max(x);
     }
@@ -55,34 +59,32 @@ impl<const N: usize> Vector<N> {
   /// Element-wise minimum with value
   #[inline]
   pub fn el_min(self, rhs: impl Into<Self>) -> Self {
-    let rhs = rhs.into();
-    Self(array::from_fn(move |i| self[i].min(rhs[i])))
+    Self(S::lanes_min(self.0, rhs.into().0))
   }
 
   /// Element-wise maximum with value
   #[inline]
   pub fn el_max(self, rhs: impl Into<Self>) -> Self {
-    let rhs = rhs.into();
-    Self(array::from_fn(move |i| self[i].max(rhs[i])))
+    Self(S::lanes_max(self.0, rhs.into().0))
   }
 
   /// Element-wise absolute value
   #[inline]
   pub fn abs(self) -> Self {
-    Self(self.0.map(f64::abs))
+    Self(self.0.map(S::abs))
   }
 
   /// Magnitude squared of a vector.\
   /// Using this method allows us to avoid a `sqrt`, which are among the
   /// slowest CPU operations ([source](https://stackoverflow.com/a/12304868/14054238))
   #[inline]
-  pub fn mag2(self) -> f64 {
-    self.into_iter().map(|x| x * x).sum()
+  pub fn mag2(self) -> S {
+    S::lanes_mag2(self.0)
   }
 
   /// Magnitude of a vector
   #[inline]
-  pub fn mag(self) -> f64 {
+  pub fn mag(self) -> S {
     self.mag2().sqrt()
   }
 
@@ -94,27 +96,47 @@ impl<const N: usize> Vector<N> {
 
   /// Dot Product
   #[inline]
-  pub fn dot(self, rhs: Self) -> f64 {
-    self.into_iter().zip(rhs).map(|(l, r)| l * r).sum()
+  pub fn dot(self, rhs: Self) -> S {
+    S::lanes_dot(self.0, rhs.0)
   }
 
   /// Matrix Product
   #[inline]
-  pub fn matmul(self, mat: [[f64; N]; N]) -> Self {
-    Self(mat.map(|row| Vector(row).dot(self)))
+  pub fn matmul(self, mat: impl Into<Matrix<N>>) -> Self {
+    mat.into() * self
+  }
+
+  /// Converts to a vector of a different scalar type
+  #[inline]
+  pub fn cast<T: Scalar>(self) -> Vector<N, T> {
+    Vector(self.0.map(|x| T::from_f64(x.to_f64())))
   }
 }
 
 pub fn transpose<const N: usize>(mat: [[f64; N]; N]) -> [[f64; N]; N] {
-  array::from_fn(|i| array::from_fn(|j| mat[j][i]))
+  Matrix(mat).transpose().0
 }
 
 impl<const N: usize> Mul<Vector<N>> for [[f64; N]; N] {
   type Output = Vector<N>;
   fn mul(self, rhs: Vector<N>) -> Self::Output {
-    rhs.matmul(self)
+    Matrix(self) * rhs
   }
 }
 
+mod aabb;
+pub use aabb::Aabb;
+mod affine;
+pub use affine::Affine;
+mod dual;
+pub use dual::Dual;
+mod geometry;
+mod interval;
+pub use interval::Interval;
 mod iterators;
 pub use iterators::GridIter;
+mod matrix;
+pub use matrix::Matrix;
+mod quaternion;
+pub use quaternion::Quaternion;
+pub mod sampling;
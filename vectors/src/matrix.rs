@@ -0,0 +1,434 @@
+use super::{Scalar, Vector};
+use std::{
+  array,
+  ops::{Add, Deref, DerefMut, Mul, Neg, Sub},
+};
+
+/// Tolerance used when checking matrix properties, i.e. orthonormality.
+const EPSILON: f64 = 1e-9;
+/// Maximum number of sweeps for the Jacobi eigenvalue method.
+const JACOBI_SWEEPS: usize = 32;
+
+/// A square `N x N` matrix, stored as an array of rows.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Matrix<const N: usize>(pub [[f64; N]; N]);
+
+impl<const N: usize> Deref for Matrix<N> {
+  type Target = [[f64; N]; N];
+  fn deref(&self) -> &Self::Target {
+    &self.0
+  }
+}
+
+impl<const N: usize> DerefMut for Matrix<N> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.0
+  }
+}
+
+impl<const N: usize> From<[[f64; N]; N]> for Matrix<N> {
+  fn from(value: [[f64; N]; N]) -> Self {
+    Self(value)
+  }
+}
+
+impl<const N: usize> From<Matrix<N>> for [[f64; N]; N] {
+  fn from(value: Matrix<N>) -> Self {
+    value.0
+  }
+}
+
+impl<const N: usize> Default for Matrix<N> {
+  fn default() -> Self {
+    Self::identity()
+  }
+}
+
+// Constructors
+
+impl<const N: usize> Matrix<N> {
+  #[inline]
+  pub fn zeros() -> Self {
+    Self([[0.0; N]; N])
+  }
+
+  #[inline]
+  pub fn identity() -> Self {
+    Self::diagonal(Vector::ones())
+  }
+
+  /// A matrix with `diag` along the leading diagonal and zeros elsewhere.
+  #[inline]
+  pub fn diagonal(diag: impl Into<Vector<N>>) -> Self {
+    let diag = diag.into();
+    Self(array::from_fn(|i| {
+      array::from_fn(|j| if i == j { diag[i] } else { 0.0 })
+    }))
+  }
+
+  /// Constructs a matrix from its row vectors.
+  #[inline]
+  pub fn from_rows(rows: [Vector<N>; N]) -> Self {
+    Self(rows.map(|row| row.0))
+  }
+
+  /// Constructs a matrix from its column vectors.
+  #[inline]
+  pub fn from_cols(cols: [Vector<N>; N]) -> Self {
+    Self::from_rows(cols).transpose()
+  }
+}
+
+impl Matrix<2> {
+  /// Anti-clockwise rotation by `angle` radians.
+  pub fn rotation(angle: f64) -> Self {
+    let (s, c) = angle.sin_cos();
+    Self([[c, -s], [s, c]])
+  }
+}
+
+impl Matrix<3> {
+  /// Rotation by `angle` radians around `axis`.\
+  /// we just use the matrix transformation from [wikipedia](http://en.wikipedia.org/wiki/Rotation_matrix#Rotation_matrix_from_axis_and_angle)
+  pub fn rotation(axis: impl Into<Vector<3>>, angle: f64) -> Self {
+    let (s, c) = angle.sin_cos();
+    let Vector([x, y, z]) = axis.into().normal();
+    Self([
+      [
+        c + x * x * (1.0 - c),
+        x * y * (1.0 - c) - z * s,
+        x * z * (1.0 - c) + y * s,
+      ],
+      [
+        x * y * (1.0 - c) + z * s,
+        c + y * y * (1.0 - c),
+        y * z * (1.0 - c) - x * s,
+      ],
+      [
+        x * z * (1.0 - c) - y * s,
+        y * z * (1.0 - c) + x * s,
+        c + z * z * (1.0 - c),
+      ],
+    ])
+  }
+}
+
+// Accessors & Properties
+
+impl<const N: usize> Matrix<N> {
+  /// The `i`th row of the matrix
+  #[inline]
+  pub fn row(&self, i: usize) -> Vector<N> {
+    Vector(self[i])
+  }
+
+  /// The `j`th column of the matrix
+  #[inline]
+  pub fn col(&self, j: usize) -> Vector<N> {
+    Vector(array::from_fn(|i| self[i][j]))
+  }
+
+  #[inline]
+  pub fn transpose(self) -> Self {
+    Self(array::from_fn(|i| array::from_fn(|j| self[j][i])))
+  }
+
+  /// Sum of the leading diagonal
+  #[inline]
+  pub fn trace(&self) -> f64 {
+    (0..N).map(|i| self[i][i]).sum()
+  }
+
+  /// Reduces the matrix to upper triangular form via gaussian elimination,\
+  /// applying every row operation to `aug` as well.
+  ///
+  /// Returns the determinant of the original matrix.
+  fn eliminate(mut self, aug: &mut Self) -> (Self, f64) {
+    let mut det = 1.0;
+    for i in 0..N {
+      // partial pivoting, for numeric stability
+      let pivot = (i..N)
+        .max_by(|&a, &b| self[a][i].abs().total_cmp(&self[b][i].abs()))
+        .unwrap_or(i);
+      if self[pivot][i] == 0.0 {
+        return (self, 0.0);
+      }
+      if pivot != i {
+        self.swap(i, pivot);
+        aug.swap(i, pivot);
+        det = -det;
+      }
+
+      let diag = self[i][i];
+      det *= diag;
+      for k in i + 1..N {
+        let f = self[k][i] / diag;
+        for j in 0..N {
+          self[k][j] -= f * self[i][j];
+          aug[k][j] -= f * aug[i][j];
+        }
+      }
+    }
+    (self, det)
+  }
+
+  /// The determinant of the matrix
+  pub fn det(self) -> f64 {
+    self.eliminate(&mut Self::zeros()).1
+  }
+
+  /// The inverse of the matrix, if it exists.
+  ///
+  /// Uses Gauss-Jordan elimination, so is `O(N^3)`.\
+  /// If you know the matrix is orthonormal, prefer `transpose`.
+  pub fn inverse(self) -> Option<Self> {
+    let mut inv = Self::identity();
+    let (mut upper, det) = self.eliminate(&mut inv);
+    if det == 0.0 || !det.is_finite() {
+      return None;
+    }
+
+    // back substitution, clearing the upper triangle
+    for i in (0..N).rev() {
+      let diag = upper[i][i];
+      for j in 0..N {
+        upper[i][j] /= diag;
+        inv[i][j] /= diag;
+      }
+      for k in 0..i {
+        let f = upper[k][i];
+        for j in 0..N {
+          upper[k][j] -= f * upper[i][j];
+          inv[k][j] -= f * inv[i][j];
+        }
+      }
+    }
+    Some(inv)
+  }
+
+  /// Eigenvalues of a symmetric matrix, in no particular order.
+  ///
+  /// Uses the cyclic [Jacobi method](https://en.wikipedia.org/wiki/Jacobi_eigenvalue_algorithm),
+  /// which is slow but very accurate for the small matrices we deal with.
+  pub fn sym_eigenvalues(self) -> Vector<N> {
+    self.sym_eigen().0
+  }
+
+  /// Eigenvalues and eigenvectors of a symmetric matrix, i.e. `(D, V)`\
+  /// where column `i` of `V` has the eigenvalue `D[i]`, and\
+  /// `V * Matrix::diagonal(D) * V.transpose() == self`.
+  ///
+  /// Also uses the cyclic Jacobi method, accumulating the rotations.
+  pub fn sym_eigen(self) -> (Vector<N>, Self) {
+    let mut mat = self;
+    let mut vecs = Self::identity();
+    for _ in 0..JACOBI_SWEEPS {
+      let off_diag: f64 = (0..N)
+        .flat_map(|i| (0..N).filter(move |&j| i != j).map(move |j| (i, j)))
+        .map(|(i, j)| mat[i][j] * mat[i][j])
+        .sum();
+      if off_diag <= f64::MIN_POSITIVE {
+        break;
+      }
+
+      for p in 0..N {
+        for q in p + 1..N {
+          if mat[p][q] == 0.0 {
+            continue;
+          }
+          // rotation that zeroes `mat[p][q]`
+          let theta = (mat[q][q] - mat[p][p]) / (2.0 * mat[p][q]);
+          let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+          let c = (t * t + 1.0).sqrt().recip();
+          let s = t * c;
+
+          for k in 0..N {
+            let (kp, kq) = (mat[k][p], mat[k][q]);
+            mat[k][p] = c * kp - s * kq;
+            mat[k][q] = s * kp + c * kq;
+            let (kp, kq) = (vecs[k][p], vecs[k][q]);
+            vecs[k][p] = c * kp - s * kq;
+            vecs[k][q] = s * kp + c * kq;
+          }
+          for k in 0..N {
+            let (pk, qk) = (mat[p][k], mat[q][k]);
+            mat[p][k] = c * pk - s * qk;
+            mat[q][k] = s * pk + c * qk;
+          }
+        }
+      }
+    }
+    (Vector(array::from_fn(|i| mat[i][i])), vecs)
+  }
+
+  /// Singular values of the matrix, in no particular order.\
+  /// These are the factors the matrix stretches space by along each axis.
+  pub fn singular_values(self) -> Vector<N> {
+    (self.transpose() * self)
+      .sym_eigenvalues()
+      .el_max(0.0)
+      .0
+      .map(f64::sqrt)
+      .into()
+  }
+
+  /// The operator (spectral) norm,\
+  /// i.e. the most a vector's length can be scaled by this matrix.
+  pub fn norm(self) -> f64 {
+    self.singular_values().max()
+  }
+
+  /// Whether every element is within `tol` of `rhs`
+  pub fn approx_eq(&self, rhs: &Self, tol: f64) -> bool {
+    self
+      .iter()
+      .flatten()
+      .zip(rhs.iter().flatten())
+      .all(|(l, r)| (l - r).abs() <= tol)
+  }
+
+  /// Whether the rows (and so columns) are orthogonal unit vectors.\
+  /// i.e. `M * M^T == I`, up to floating point error.
+  pub fn is_orthonormal(&self) -> bool {
+    (*self * self.transpose()).approx_eq(&Self::identity(), EPSILON)
+  }
+
+  /// Whether the matrix is a pure rotation,\
+  /// i.e. orthonormal without any reflection.
+  pub fn is_rotation(&self) -> bool {
+    self.is_orthonormal() && self.det() > 0.0
+  }
+}
+
+// Operators
+
+impl<const N: usize> Neg for Matrix<N> {
+  type Output = Matrix<N>;
+  fn neg(self) -> Self::Output {
+    Self(self.0.map(|row| row.map(|x| -x)))
+  }
+}
+
+impl<const N: usize> Add<Matrix<N>> for Matrix<N> {
+  type Output = Matrix<N>;
+  fn add(self, rhs: Matrix<N>) -> Self::Output {
+    Self(array::from_fn(|i| {
+      array::from_fn(|j| self[i][j] + rhs[i][j])
+    }))
+  }
+}
+
+impl<const N: usize> Sub<Matrix<N>> for Matrix<N> {
+  type Output = Matrix<N>;
+  fn sub(self, rhs: Matrix<N>) -> Self::Output {
+    Self(array::from_fn(|i| {
+      array::from_fn(|j| self[i][j] - rhs[i][j])
+    }))
+  }
+}
+
+impl<const N: usize> Mul<f64> for Matrix<N> {
+  type Output = Matrix<N>;
+  fn mul(self, rhs: f64) -> Self::Output {
+    Self(self.0.map(|row| row.map(|x| x * rhs)))
+  }
+}
+
+impl<const N: usize> Mul<Matrix<N>> for f64 {
+  type Output = Matrix<N>;
+  fn mul(self, rhs: Matrix<N>) -> Self::Output {
+    rhs * self
+  }
+}
+
+impl<const N: usize, S: Scalar> Mul<Vector<N, S>> for Matrix<N> {
+  type Output = Vector<N, S>;
+  fn mul(self, rhs: Vector<N, S>) -> Self::Output {
+    Vector(self.0.map(|row| Vector(row).cast().dot(rhs)))
+  }
+}
+
+/// Matrix composition, `(A * B) * v == A * (B * v)`
+impl<const N: usize> Mul<Matrix<N>> for Matrix<N> {
+  type Output = Matrix<N>;
+  fn mul(self, rhs: Matrix<N>) -> Self::Output {
+    Self(array::from_fn(|i| {
+      array::from_fn(|j| (0..N).map(|k| self[i][k] * rhs[k][j]).sum())
+    }))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::f64::consts::PI;
+
+  #[test]
+  fn inverse_identity() {
+    let mat = Matrix([[2.0, 1.0, 0.0], [0.0, 1.0, 3.0], [1.0, 0.0, 1.0]]);
+    let inv = mat.inverse().expect("matrix should be invertible");
+    assert!((mat * inv).approx_eq(&Matrix::identity(), EPSILON));
+    assert!((inv * mat).approx_eq(&Matrix::identity(), EPSILON));
+  }
+
+  #[test]
+  fn singular_inverse() {
+    let mat = Matrix([[1.0, 2.0], [2.0, 4.0]]);
+    assert_eq!(mat.det(), 0.0);
+    assert_eq!(mat.inverse(), None);
+  }
+
+  #[test]
+  fn det_product() {
+    let a = Matrix([[2.0, 1.0, 0.0], [0.0, 1.0, 3.0], [1.0, 0.0, 1.0]]);
+    let b = Matrix([[0.0, 1.0, 4.0], [1.0, 0.0, 0.0], [2.0, 1.0, 1.0]]);
+    assert!(((a * b).det() - a.det() * b.det()).abs() < EPSILON);
+  }
+
+  #[test]
+  fn singular_values() {
+    let rot = Matrix::<3>::rotation([1.0, 1.0, 0.0], PI / 3.0);
+    let mat = rot * Matrix::diagonal([2.0, 0.5, 3.0]) * rot.transpose();
+    let mut values = mat.singular_values().0;
+    values.sort_by(f64::total_cmp);
+    for (value, expected) in values.into_iter().zip([0.5, 2.0, 3.0]) {
+      assert!((value - expected).abs() < EPSILON);
+    }
+  }
+
+  #[test]
+  fn sym_eigen() {
+    let rot = Matrix::<3>::rotation([1.0, -2.0, 0.5], PI / 5.0);
+    let mat = rot * Matrix::diagonal([2.0, -0.5, 3.0]) * rot.transpose();
+    let (values, vecs) = mat.sym_eigen();
+    assert!(vecs.is_orthonormal());
+    let rebuilt = vecs * Matrix::diagonal(values) * vecs.transpose();
+    assert!(rebuilt.approx_eq(&mat, EPSILON));
+    for i in 0..3 {
+      assert!((mat * vecs.col(i) - vecs.col(i) * values[i]).mag() < EPSILON);
+    }
+  }
+
+  #[test]
+  fn rotations_orthonormal() {
+    let rot2 = Matrix::<2>::rotation(PI / 3.0);
+    assert!(rot2.is_rotation());
+    let rot3 = Matrix::<3>::rotation([1.0, 2.0, 3.0], PI / 5.0);
+    assert!(rot3.is_rotation());
+    assert!(rot3
+      .transpose()
+      .approx_eq(&rot3.inverse().unwrap(), EPSILON));
+    assert!(!Matrix::diagonal([1.0, 1.0, -1.0]).is_rotation());
+  }
+
+  #[test]
+  fn rotations_invert() {
+    for angle in [0.3, PI / 2.0, 2.0] {
+      let there = Matrix::<2>::rotation(angle);
+      let back = Matrix::<2>::rotation(-angle);
+      assert!((there * back).approx_eq(&Matrix::identity(), EPSILON));
+    }
+    let quarter = Matrix::<2>::rotation(PI / 2.0);
+    assert!((quarter * Vector([1.0, 0.0]) - Vector([0.0, 1.0])).mag() < EPSILON);
+  }
+}
@@ -1,110 +1,106 @@
-use super::Vector;
+use super::{Scalar, Vector};
 use std::ops::{Add, Div, Mul, Neg, Sub};
 
-impl<const N: usize> Neg for Vector<N> {
-  type Output = Vector<N>;
+impl<const N: usize, S: Scalar> Neg for Vector<N, S> {
+  type Output = Vector<N, S>;
   fn neg(self) -> Self::Output {
     Self(self.0.map(|x| -x))
   }
 }
 
-impl<const N: usize> Add<f64> for Vector<N> {
-  type Output = Vector<N>;
-  fn add(self, rhs: f64) -> Self::Output {
-    Self(self.0.map(|l| l + rhs))
+impl<const N: usize, S: Scalar> Add<S> for Vector<N, S> {
+  type Output = Vector<N, S>;
+  fn add(self, rhs: S) -> Self::Output {
+    Self(S::lanes_add(self.0, [rhs; N]))
   }
 }
 
-impl<const N: usize> Sub<f64> for Vector<N> {
-  type Output = Vector<N>;
-  fn sub(self, rhs: f64) -> Self::Output {
-    Self(self.0.map(|l| l - rhs))
+impl<const N: usize, S: Scalar> Sub<S> for Vector<N, S> {
+  type Output = Vector<N, S>;
+  fn sub(self, rhs: S) -> Self::Output {
+    Self(S::lanes_sub(self.0, [rhs; N]))
   }
 }
 
-impl<const N: usize> Mul<f64> for Vector<N> {
-  type Output = Vector<N>;
-  fn mul(self, rhs: f64) -> Self::Output {
-    Self(self.0.map(|l| l * rhs))
+impl<const N: usize, S: Scalar> Mul<S> for Vector<N, S> {
+  type Output = Vector<N, S>;
+  fn mul(self, rhs: S) -> Self::Output {
+    Self(S::lanes_mul(self.0, [rhs; N]))
   }
 }
 
-impl<const N: usize> Div<f64> for Vector<N> {
-  type Output = Vector<N>;
-  fn div(self, rhs: f64) -> Self::Output {
-    Self(self.0.map(|l| l / rhs))
+impl<const N: usize, S: Scalar> Div<S> for Vector<N, S> {
+  type Output = Vector<N, S>;
+  fn div(self, rhs: S) -> Self::Output {
+    Self(S::lanes_div(self.0, [rhs; N]))
   }
 }
 
-impl<const N: usize> Add<Vector<N>> for f64 {
-  type Output = Vector<N>;
-  fn add(self, rhs: Vector<N>) -> Self::Output {
-    Vector(rhs.0.map(|r| self + r))
-  }
-}
+/// Scalars on the left hand side have to be implemented per type,\
+/// as `impl<S: Scalar> Add<Vector<N, S>> for S` breaks the orphan rule.
+macro_rules! impl_scalar_lhs {
+  ($($float:ident),*) => {$(
+    impl<const N: usize> Add<Vector<N, $float>> for $float {
+      type Output = Vector<N, $float>;
+      fn add(self, rhs: Vector<N, $float>) -> Self::Output {
+        Vector($float::lanes_add([self; N], rhs.0))
+      }
+    }
 
-impl<const N: usize> Sub<Vector<N>> for f64 {
-  type Output = Vector<N>;
-  fn sub(self, rhs: Vector<N>) -> Self::Output {
-    Vector(rhs.0.map(|r| self - r))
-  }
-}
+    impl<const N: usize> Sub<Vector<N, $float>> for $float {
+      type Output = Vector<N, $float>;
+      fn sub(self, rhs: Vector<N, $float>) -> Self::Output {
+        Vector($float::lanes_sub([self; N], rhs.0))
+      }
+    }
 
-impl<const N: usize> Mul<Vector<N>> for f64 {
-  type Output = Vector<N>;
-  fn mul(self, rhs: Vector<N>) -> Self::Output {
-    Vector(rhs.0.map(|r| self * r))
-  }
-}
+    impl<const N: usize> Mul<Vector<N, $float>> for $float {
+      type Output = Vector<N, $float>;
+      fn mul(self, rhs: Vector<N, $float>) -> Self::Output {
+        Vector($float::lanes_mul([self; N], rhs.0))
+      }
+    }
 
-impl<const N: usize> Div<Vector<N>> for f64 {
-  type Output = Vector<N>;
-  fn div(self, rhs: Vector<N>) -> Self::Output {
-    Vector(rhs.0.map(|r| self / r))
-  }
+    impl<const N: usize> Div<Vector<N, $float>> for $float {
+      type Output = Vector<N, $float>;
+      fn div(self, rhs: Vector<N, $float>) -> Self::Output {
+        Vector($float::lanes_div([self; N], rhs.0))
+      }
+    }
+  )*};
 }
 
-impl<const N: usize> Add<Vector<N>> for Vector<N> {
-  type Output = Vector<N>;
-  fn add(mut self, rhs: Vector<N>) -> Self::Output {
-    for (mut_l, r) in self.iter_mut().zip(rhs) {
-      *mut_l += r;
-    }
-    self
+impl_scalar_lhs!(f32, f64);
+
+impl<const N: usize, S: Scalar> Add<Vector<N, S>> for Vector<N, S> {
+  type Output = Vector<N, S>;
+  fn add(self, rhs: Vector<N, S>) -> Self::Output {
+    Self(S::lanes_add(self.0, rhs.0))
   }
 }
 
-impl<const N: usize> Sub<Vector<N>> for Vector<N> {
-  type Output = Vector<N>;
-  fn sub(mut self, rhs: Vector<N>) -> Self::Output {
-    for (mut_l, r) in self.iter_mut().zip(rhs) {
-      *mut_l -= r;
-    }
-    self
+impl<const N: usize, S: Scalar> Sub<Vector<N, S>> for Vector<N, S> {
+  type Output = Vector<N, S>;
+  fn sub(self, rhs: Vector<N, S>) -> Self::Output {
+    Self(S::lanes_sub(self.0, rhs.0))
   }
 }
 
-impl<const N: usize> Mul<Vector<N>> for Vector<N> {
-  type Output = Vector<N>;
-  fn mul(mut self, rhs: Vector<N>) -> Self::Output {
-    for (mut_l, r) in self.iter_mut().zip(rhs) {
-      *mut_l *= r;
-    }
-    self
+impl<const N: usize, S: Scalar> Mul<Vector<N, S>> for Vector<N, S> {
+  type Output = Vector<N, S>;
+  fn mul(self, rhs: Vector<N, S>) -> Self::Output {
+    Self(S::lanes_mul(self.0, rhs.0))
   }
 }
 
-impl<const N: usize> Div<Vector<N>> for Vector<N> {
-  type Output = Vector<N>;
-  fn div(mut self, rhs: Vector<N>) -> Self::Output {
-    for (mut_l, r) in self.iter_mut().zip(rhs) {
-      *mut_l /= r;
-    }
-    self
+impl<const N: usize, S: Scalar> Div<Vector<N, S>> for Vector<N, S> {
+  type Output = Vector<N, S>;
+  fn div(self, rhs: Vector<N, S>) -> Self::Output {
+    Self(S::lanes_div(self.0, rhs.0))
   }
 }
 
-impl Vector<3> {
+impl<S: Scalar> Vector<3, S> {
   pub fn cross(self, rhs: Self) -> Self {
     let Vector([a0, a1, a2]) = self;
     let Vector([b0, b1, b2]) = rhs;
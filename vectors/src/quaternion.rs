@@ -0,0 +1,325 @@
+use super::{Matrix, Scalar, Vector};
+use std::ops::{Mul, Neg};
+
+/// A quaternion `w + xi + yj + zk`, split into scalar and vector parts.
+///
+/// Unit quaternions are used to represent 3D rotations, as they:
+///
+/// 1. Compose cheaply and without accumulating skew.
+/// 2. Interpolate smoothly, via `slerp`.
+/// 3. Don't suffer from gimbal lock, unlike euler angles.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quaternion {
+  /// The scalar (real) part
+  pub w: f64,
+  /// The vector (imaginary) part
+  pub v: Vector<3>,
+}
+
+impl Default for Quaternion {
+  fn default() -> Self {
+    Self::identity()
+  }
+}
+
+impl From<[f64; 4]> for Quaternion {
+  /// Converts from `[w, x, y, z]` components
+  fn from([w, x, y, z]: [f64; 4]) -> Self {
+    Self::new(w, [x, y, z])
+  }
+}
+
+impl From<Quaternion> for [f64; 4] {
+  fn from(
+    Quaternion {
+      w,
+      v: Vector([x, y, z]),
+    }: Quaternion,
+  ) -> Self {
+    [w, x, y, z]
+  }
+}
+
+// Constructors
+
+impl Quaternion {
+  #[inline]
+  pub fn new(w: f64, v: impl Into<Vector<3>>) -> Self {
+    Self { w, v: v.into() }
+  }
+
+  /// The quaternion representing no rotation
+  #[inline]
+  pub fn identity() -> Self {
+    Self::new(1.0, Vector::zeros())
+  }
+
+  /// Rotation by `angle` radians around `axis`.
+  pub fn from_axis_angle(axis: impl Into<Vector<3>>, angle: f64) -> Self {
+    let (s, c) = (angle * 0.5).sin_cos();
+    Self::new(c, axis.into().normal() * s)
+  }
+
+  /// Rotation from euler angles, in radians:
+  /// - `roll` around the x axis
+  /// - `pitch` around the y axis
+  /// - `yaw` around the z axis
+  ///
+  /// These are applied in order: roll, then pitch, then yaw.
+  pub fn from_euler(roll: f64, pitch: f64, yaw: f64) -> Self {
+    Self::from_axis_angle(Vector::axis(2), yaw)
+      * Self::from_axis_angle(Vector::axis(1), pitch)
+      * Self::from_axis_angle(Vector::axis(0), roll)
+  }
+
+  /// The rotation that points the x axis along `forward`\
+  /// and the z axis as close as possible to `upwards`.
+  ///
+  /// This matches the default camera orientation, which looks along x.
+  pub fn look_rotation(forward: impl Into<Vector<3>>, upwards: impl Into<Vector<3>>) -> Self {
+    let forward = forward.into().normal();
+    let side = upwards.into().cross(forward).normal();
+    let upwards = forward.cross(side);
+    Self::from_matrix(Matrix::from_cols([forward, side, upwards]))
+  }
+
+  /// Converts a rotation matrix to a quaternion.\
+  /// Uses [Shepperd's method](https://doi.org/10.2514/3.55767b)
+  /// to pick the most numerically stable component to divide by.
+  pub fn from_matrix(mat: Matrix<3>) -> Self {
+    debug_assert!(
+      mat.is_rotation(),
+      "Can only convert rotation matrices to quaternions\nGot {mat:?}"
+    );
+    let [[m00, m01, m02], [m10, m11, m12], [m20, m21, m22]] = mat.0;
+    let trace = mat.trace();
+    let quat = if trace > 0.0 {
+      let s = 2.0 * (trace + 1.0).sqrt();
+      Self::new(
+        0.25 * s,
+        [(m21 - m12) / s, (m02 - m20) / s, (m10 - m01) / s],
+      )
+    } else if m00 > m11 && m00 > m22 {
+      let s = 2.0 * (1.0 + m00 - m11 - m22).sqrt();
+      Self::new(
+        (m21 - m12) / s,
+        [0.25 * s, (m01 + m10) / s, (m02 + m20) / s],
+      )
+    } else if m11 > m22 {
+      let s = 2.0 * (1.0 + m11 - m00 - m22).sqrt();
+      Self::new(
+        (m02 - m20) / s,
+        [(m01 + m10) / s, 0.25 * s, (m12 + m21) / s],
+      )
+    } else {
+      let s = 2.0 * (1.0 + m22 - m00 - m11).sqrt();
+      Self::new(
+        (m10 - m01) / s,
+        [(m02 + m20) / s, (m12 + m21) / s, 0.25 * s],
+      )
+    };
+    quat.normal()
+  }
+}
+
+// Properties
+
+impl Quaternion {
+  #[inline]
+  pub fn dot(self, rhs: Self) -> f64 {
+    self.w * rhs.w + self.v.dot(rhs.v)
+  }
+
+  #[inline]
+  pub fn mag2(self) -> f64 {
+    self.dot(self)
+  }
+
+  #[inline]
+  pub fn mag(self) -> f64 {
+    self.mag2().sqrt()
+  }
+
+  /// Normalises a quaternion, so that it represents a rotation
+  #[inline]
+  pub fn normal(self) -> Self {
+    let f = self.mag().recip();
+    Self::new(self.w * f, self.v * f)
+  }
+
+  #[inline]
+  pub fn conjugate(self) -> Self {
+    Self::new(self.w, -self.v)
+  }
+
+  /// The multiplicative inverse.\
+  /// For unit quaternions, prefer `conjugate`.
+  #[inline]
+  pub fn inverse(self) -> Self {
+    let f = self.mag2().recip();
+    Self::new(self.w * f, -self.v * f)
+  }
+
+  /// The axis and angle of rotation for a unit quaternion.
+  ///
+  /// The identity rotation has no well-defined axis,\
+  /// so we return the x axis in that case.
+  pub fn to_axis_angle(self) -> (Vector<3>, f64) {
+    let sin = self.v.mag();
+    let angle = 2.0 * sin.atan2(self.w);
+    if sin == 0.0 {
+      return (Vector::axis(0), angle);
+    }
+    (self.v / sin, angle)
+  }
+
+  /// Rotates a vector by this (unit) quaternion.
+  #[inline]
+  pub fn rotate<S: Scalar>(self, vec: Vector<3, S>) -> Vector<3, S> {
+    let (w, v) = (S::from_f64(self.w), self.v.cast());
+    // optimised form of `q * vec * q^-1`
+    let t = v.cross(vec) * S::from_f64(2.0);
+    vec + t * w + v.cross(t)
+  }
+
+  /// Spherical linear interpolation between two rotations.
+  ///
+  /// Always takes the shortest path, and falls back to\
+  /// normalised linear interpolation for nearly equal rotations.
+  pub fn slerp(self, rhs: Self, f: f64) -> Self {
+    let mut rhs = rhs;
+    let mut cos = self.dot(rhs);
+    if cos < 0.0 {
+      rhs = -rhs;
+      cos = -cos;
+    }
+
+    let (f0, f1) = if cos > 1.0 - 1e-6 {
+      (1.0 - f, f)
+    } else {
+      let angle = cos.acos();
+      let sin = angle.sin();
+      (((1.0 - f) * angle).sin() / sin, (f * angle).sin() / sin)
+    };
+    Self::new(f0 * self.w + f1 * rhs.w, f0 * self.v + f1 * rhs.v).normal()
+  }
+}
+
+// Operators
+
+impl Neg for Quaternion {
+  type Output = Quaternion;
+  fn neg(self) -> Self::Output {
+    Self::new(-self.w, -self.v)
+  }
+}
+
+/// The Hamilton product, composing rotations such that\
+/// `(a * b).rotate(v) == a.rotate(b.rotate(v))`
+impl Mul<Quaternion> for Quaternion {
+  type Output = Quaternion;
+  fn mul(self, rhs: Quaternion) -> Self::Output {
+    Self::new(
+      self.w * rhs.w - self.v.dot(rhs.v),
+      self.w * rhs.v + rhs.w * self.v + self.v.cross(rhs.v),
+    )
+  }
+}
+
+impl<S: Scalar> Mul<Vector<3, S>> for Quaternion {
+  type Output = Vector<3, S>;
+  fn mul(self, rhs: Vector<3, S>) -> Self::Output {
+    self.rotate(rhs)
+  }
+}
+
+impl From<Quaternion> for Matrix<3> {
+  /// The rotation matrix for a unit quaternion
+  fn from(
+    Quaternion {
+      w,
+      v: Vector([x, y, z]),
+    }: Quaternion,
+  ) -> Self {
+    Matrix([
+      [
+        1.0 - 2.0 * (y * y + z * z),
+        2.0 * (x * y - w * z),
+        2.0 * (x * z + w * y),
+      ],
+      [
+        2.0 * (x * y + w * z),
+        1.0 - 2.0 * (x * x + z * z),
+        2.0 * (y * z - w * x),
+      ],
+      [
+        2.0 * (x * z - w * y),
+        2.0 * (y * z + w * x),
+        1.0 - 2.0 * (x * x + y * y),
+      ],
+    ])
+  }
+}
+
+impl From<Matrix<3>> for Quaternion {
+  fn from(value: Matrix<3>) -> Self {
+    Self::from_matrix(value)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::f64::consts::PI;
+
+  const EPSILON: f64 = 1e-9;
+
+  fn approx_eq(lhs: Vector<3>, rhs: Vector<3>) -> bool {
+    (lhs - rhs).mag() < EPSILON
+  }
+
+  #[test]
+  fn matches_matrix() {
+    let axis = Vector([1.0, -2.0, 0.5]);
+    let quat = Quaternion::from_axis_angle(axis, PI / 3.0);
+    let mat = Matrix::<3>::rotation(axis, PI / 3.0);
+    assert!(Matrix::from(quat).approx_eq(&mat, EPSILON));
+
+    let vec = Vector([0.3, 0.2, -0.7]);
+    assert!(approx_eq(quat * vec, mat * vec));
+    let back = Quaternion::from_matrix(mat);
+    assert!((back.dot(quat).abs() - 1.0).abs() < EPSILON);
+  }
+
+  #[test]
+  fn composition() {
+    let q0 = Quaternion::from_axis_angle([0.0, 0.0, 1.0], PI / 4.0);
+    let q1 = Quaternion::from_axis_angle([1.0, 0.0, 0.0], PI / 3.0);
+    let vec = Vector([1.0, 2.0, 3.0]);
+    assert!(approx_eq((q1 * q0) * vec, q1 * (q0 * vec)));
+  }
+
+  #[test]
+  fn euler_angles() {
+    let quat = Quaternion::from_euler(0.0, 0.0, PI / 2.0);
+    assert!(approx_eq(quat * Vector::axis(0), Vector::axis(1)));
+  }
+
+  #[test]
+  fn look_rotation() {
+    let forward = Vector([1.0, 1.0, 0.0]);
+    let quat = Quaternion::look_rotation(forward, [0.0, 0.0, 1.0]);
+    assert!(approx_eq(quat * Vector::axis(0), forward.normal()));
+    assert!(approx_eq(quat * Vector::axis(2), Vector::axis(2)));
+  }
+
+  #[test]
+  fn slerp_midpoint() {
+    let q0 = Quaternion::identity();
+    let q1 = Quaternion::from_axis_angle([0.0, 0.0, 1.0], PI / 2.0);
+    let mid = q0.slerp(q1, 0.5);
+    let (axis, angle) = mid.to_axis_angle();
+    assert!(approx_eq(axis, Vector::axis(2)));
+    assert!((angle - PI / 4.0).abs() < EPSILON);
+  }
+}
@@ -0,0 +1,29 @@
+//! Sampling utilities for stochastic rendering,\
+//! e.g. anti-aliasing, soft shadows, ambient occlusion or depth of field.
+//!
+//! - [`shapes`]: maps uniform samples in `[0, 1)²` onto disks, spheres,\
+//!   hemispheres and cones, along with their probability densities,\
+//!   and samples in `[0, 1)ᴺ` onto spheres and balls in `N` dimensions.
+//! - [`sequences`]: the Halton, Sobol and R2 low-discrepancy sequences.
+//! - [`Sampler`]: deterministic per-pixel streams of samples,\
+//!   with the same results no matter how pixels are split between threads.
+pub mod sequences;
+pub mod shapes;
+
+mod samplers;
+pub use samplers::*;
+
+/// Mixes the bits of a value, as in the `SplitMix64` generator.\
+/// see [Steele et al.](https://doi.org/10.1145/2714064.2660195)
+#[inline]
+pub(crate) fn mix(mut x: u64) -> u64 {
+  x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+  x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+  x ^ (x >> 31)
+}
+
+/// Converts the top 53 bits of a value to a float in `[0, 1)`
+#[inline]
+pub(crate) fn to_unit(x: u64) -> f64 {
+  (x >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+}
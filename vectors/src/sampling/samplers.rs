@@ -0,0 +1,273 @@
+use super::{
+  mix,
+  sequences::{bits_to_unit, r2, radical_inverse, sobol_bits, HALTON_DIMS, PRIMES, SOBOL_DIMS},
+  to_unit,
+};
+use crate::{Scalar, Vector};
+use std::array;
+
+/// A deterministic stream of samples in `[0, 1)` for each pixel.
+///
+/// A sampler is positioned with `start`, after which each call to\
+/// `next_1d` returns the next dimension of that sample.\
+/// The values only depend on the seed, the pixel, the sample index\
+/// and the dimension, so renders are identical no matter how pixels\
+/// are split between threads, e.g. with rayon:
+/// ```
+/// # use marchrs_vectors::sampling::{RandomSampler, Sampler};
+/// # use rayon::prelude::*;
+/// let sampler = RandomSampler::new(42);
+/// let pixels: Vec<f64> = (0..64usize)
+///   .into_par_iter()
+///   .map_init(
+///     || sampler.clone(),
+///     |sampler, pixel| {
+///       sampler.start(pixel, 0);
+///       sampler.next_1d()
+///     },
+///   )
+///   .collect();
+/// ```
+pub trait Sampler: Clone + Send + Sync {
+  /// Moves to the first dimension of sample `index` of `pixel`.
+  fn start(&mut self, pixel: usize, index: usize);
+
+  /// The next dimension of the current sample.
+  fn next_1d(&mut self) -> f64;
+
+  /// The next two dimensions of the current sample,\
+  /// e.g. for mapping onto shapes via [`shapes`](super::shapes).
+  #[inline]
+  fn next_2d(&mut self) -> [f64; 2] {
+    [self.next_1d(), self.next_1d()]
+  }
+
+  /// The next `N` dimensions of the current sample.
+  #[inline]
+  fn next_vector<const N: usize, S: Scalar>(&mut self) -> Vector<N, S> {
+    Vector(array::from_fn(|_| S::from_f64(self.next_1d())))
+  }
+}
+
+/// Hashes the position of a sample in the stream
+#[inline]
+fn hash(seed: u64, pixel: usize, index: usize, dim: usize) -> u64 {
+  mix(mix(mix(mix(seed) ^ pixel as u64) ^ index as u64) ^ dim as u64)
+}
+
+/// Independent uniform random samples.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RandomSampler {
+  seed: u64,
+  state: u64,
+}
+
+impl RandomSampler {
+  pub fn new(seed: u64) -> Self {
+    Self { seed, state: 0 }
+  }
+}
+
+impl Sampler for RandomSampler {
+  #[inline]
+  fn start(&mut self, pixel: usize, index: usize) {
+    self.state = hash(self.seed, pixel, index, 0);
+  }
+
+  #[inline]
+  fn next_1d(&mut self) -> f64 {
+    self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+    to_unit(mix(self.state))
+  }
+}
+
+/// Samples from the Halton sequence, with the samples of each pixel\
+/// randomly shifted (a Cranley-Patterson rotation) to decorrelate pixels.
+///
+/// Dimensions past `HALTON_DIMS` fall back to random samples.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HaltonSampler {
+  seed: u64,
+  pixel: usize,
+  index: usize,
+  dim: usize,
+}
+
+impl HaltonSampler {
+  pub fn new(seed: u64) -> Self {
+    Self {
+      seed,
+      pixel: 0,
+      index: 0,
+      dim: 0,
+    }
+  }
+}
+
+impl Sampler for HaltonSampler {
+  #[inline]
+  fn start(&mut self, pixel: usize, index: usize) {
+    (self.pixel, self.index, self.dim) = (pixel, index, 0);
+  }
+
+  fn next_1d(&mut self) -> f64 {
+    let dim = self.dim;
+    self.dim += 1;
+    if dim >= HALTON_DIMS {
+      return to_unit(hash(self.seed, self.pixel, self.index, dim));
+    }
+    let shift = to_unit(hash(self.seed, self.pixel, 0, dim));
+    let value = radical_inverse(PRIMES[dim], self.index as u64) + shift;
+    value - value.floor()
+  }
+}
+
+/// Samples from the Sobol sequence, with the samples of each pixel\
+/// randomly XORed (a digital shift) to decorrelate pixels.
+///
+/// Dimensions past `SOBOL_DIMS` fall back to random samples.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SobolSampler {
+  seed: u64,
+  pixel: usize,
+  index: usize,
+  dim: usize,
+}
+
+impl SobolSampler {
+  pub fn new(seed: u64) -> Self {
+    Self {
+      seed,
+      pixel: 0,
+      index: 0,
+      dim: 0,
+    }
+  }
+}
+
+impl Sampler for SobolSampler {
+  #[inline]
+  fn start(&mut self, pixel: usize, index: usize) {
+    (self.pixel, self.index, self.dim) = (pixel, index, 0);
+  }
+
+  fn next_1d(&mut self) -> f64 {
+    let dim = self.dim;
+    self.dim += 1;
+    if dim >= SOBOL_DIMS {
+      return to_unit(hash(self.seed, self.pixel, self.index, dim));
+    }
+    let shift = hash(self.seed, self.pixel, 0, dim) as u32;
+    bits_to_unit(sobol_bits(self.index as u32, dim) ^ shift)
+  }
+}
+
+/// Samples from the `R2` sequence, with the samples of each pixel\
+/// randomly shifted (a Cranley-Patterson rotation) to decorrelate pixels.
+///
+/// Dimensions past the first 2 fall back to random samples, as reusing\
+/// the sequence would repeat the first 2 dimensions with a shift.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct R2Sampler {
+  seed: u64,
+  pixel: usize,
+  index: usize,
+  dim: usize,
+}
+
+impl R2Sampler {
+  pub fn new(seed: u64) -> Self {
+    Self {
+      seed,
+      pixel: 0,
+      index: 0,
+      dim: 0,
+    }
+  }
+}
+
+impl Sampler for R2Sampler {
+  #[inline]
+  fn start(&mut self, pixel: usize, index: usize) {
+    (self.pixel, self.index, self.dim) = (pixel, index, 0);
+  }
+
+  fn next_1d(&mut self) -> f64 {
+    let dim = self.dim;
+    self.dim += 1;
+    if dim >= 2 {
+      return to_unit(hash(self.seed, self.pixel, self.index, dim));
+    }
+    let shift = to_unit(hash(self.seed, self.pixel, 0, dim));
+    let value = r2(self.index)[dim] + shift;
+    value - value.floor()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use rayon::prelude::*;
+
+  fn render<T: Sampler>(sampler: &T) -> Vec<[f64; 3]> {
+    (0..256)
+      .into_par_iter()
+      .map_init(
+        || sampler.clone(),
+        |sampler, pixel| {
+          sampler.start(pixel / 4, pixel % 4);
+          sampler.next_vector::<3, f64>().0
+        },
+      )
+      .collect()
+  }
+
+  fn deterministic<T: Sampler>(sampler: T) {
+    let first = render(&sampler);
+    assert_eq!(first, render(&sampler));
+
+    // the same as sampling each pixel in reverse, on one thread
+    let mut sequential = sampler.clone();
+    for pixel in (0..256).rev() {
+      sequential.start(pixel / 4, pixel % 4);
+      assert_eq!(sequential.next_vector::<3, f64>().0, first[pixel]);
+    }
+    assert!(first.iter().flatten().all(|x| (0.0..1.0).contains(x)));
+  }
+
+  #[test]
+  fn deterministic_under_rayon() {
+    deterministic(RandomSampler::new(1));
+    deterministic(HaltonSampler::new(2));
+    deterministic(SobolSampler::new(3));
+    deterministic(R2Sampler::new(4));
+  }
+
+  /// Samples `(dim 0, dim 2)` of a pixel, which should fill the square\
+  /// rather than lie along a few lines as they would if correlated.
+  fn independent<T: Sampler>(mut sampler: T) {
+    let mut bins = [[0; 8]; 8];
+    for index in 0..1024 {
+      sampler.start(7, index);
+      let [x, _, y] = sampler.next_vector::<3, f64>().0;
+      bins[(x * 8.0) as usize][(y * 8.0) as usize] += 1;
+    }
+    assert!(bins.iter().flatten().all(|&n| n > 0), "{bins:?}");
+  }
+
+  #[test]
+  fn higher_dims_independent() {
+    independent(RandomSampler::new(1));
+    independent(HaltonSampler::new(2));
+    independent(SobolSampler::new(3));
+    independent(R2Sampler::new(4));
+  }
+
+  #[test]
+  fn seeds_differ() {
+    let (mut a, mut b) = (SobolSampler::new(1), SobolSampler::new(2));
+    a.start(0, 0);
+    b.start(0, 0);
+    assert_ne!(a.next_2d(), b.next_2d());
+  }
+}
@@ -0,0 +1,208 @@
+//! Low-discrepancy sequences in `[0, 1)^N`.
+//!
+//! These fill space more evenly than random samples,\
+//! so estimates made with them converge faster.
+use crate::Vector;
+use std::array;
+
+/// The first primes, used as the bases of the Halton sequence
+pub(crate) const PRIMES: [u64; 32] = [
+  2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+  101, 103, 107, 109, 113, 127, 131,
+];
+
+/// The number of dimensions supported by the Halton sequence
+pub const HALTON_DIMS: usize = PRIMES.len();
+
+/// Reverses the digits of `index` in `base` around the decimal point.
+#[inline]
+pub fn radical_inverse(base: u64, mut index: u64) -> f64 {
+  let inv_base = 1.0 / base as f64;
+  let (mut reversed, mut scale) = (0u64, 1.0);
+  while index > 0 {
+    reversed = reversed * base + index % base;
+    scale *= inv_base;
+    index /= base;
+  }
+  // avoid rounding up to `1` for large indices
+  (reversed as f64 * scale).min(1.0 - f64::EPSILON / 2.0)
+}
+
+/// The `index`th point of the Halton sequence in `N <= HALTON_DIMS` dimensions.
+pub fn halton<const N: usize>(index: usize) -> Vector<N> {
+  debug_assert!(
+    N <= HALTON_DIMS,
+    "Halton sequences only support up to {HALTON_DIMS} dimensions\nGot {N}"
+  );
+  Vector(array::from_fn(|i| radical_inverse(PRIMES[i], index as u64)))
+}
+
+/// The `(degree, coefficients, initial direction numbers)` of the\
+/// primitive polynomials for each Sobol dimension after the first.\
+/// see [Joe and Kuo](https://web.maths.unsw.edu.au/~fkuo/sobol/)
+const SOBOL_POLYNOMIALS: [(usize, u32, [u32; 5]); 7] = [
+  (1, 0, [1, 0, 0, 0, 0]),
+  (2, 1, [1, 3, 0, 0, 0]),
+  (3, 1, [1, 3, 1, 0, 0]),
+  (3, 2, [1, 1, 1, 0, 0]),
+  (4, 1, [1, 1, 3, 3, 0]),
+  (4, 4, [1, 3, 5, 13, 0]),
+  (5, 2, [1, 1, 5, 5, 17]),
+];
+
+/// The number of dimensions supported by the Sobol sequence
+pub const SOBOL_DIMS: usize = SOBOL_POLYNOMIALS.len() + 1;
+
+/// The direction numbers for each bit of the index, in each dimension
+const SOBOL_DIRECTIONS: [[u32; 32]; SOBOL_DIMS] = sobol_directions();
+
+const fn sobol_directions() -> [[u32; 32]; SOBOL_DIMS] {
+  let mut directions = [[0; 32]; SOBOL_DIMS];
+  // the first dimension is the base 2 radical inverse
+  let mut k = 0;
+  while k < 32 {
+    directions[0][k] = 1 << (31 - k);
+    k += 1;
+  }
+
+  let mut dim = 1;
+  while dim < SOBOL_DIMS {
+    let (degree, coeffs, initial) = SOBOL_POLYNOMIALS[dim - 1];
+    let v = &mut directions[dim];
+    let mut k = 0;
+    while k < 32 {
+      v[k] = if k < degree {
+        initial[k] << (31 - k)
+      } else {
+        let mut next = v[k - degree] ^ (v[k - degree] >> degree);
+        let mut j = 1;
+        while j < degree {
+          if (coeffs >> (degree - 1 - j)) & 1 == 1 {
+            next ^= v[k - j];
+          }
+          j += 1;
+        }
+        next
+      };
+      k += 1;
+    }
+    dim += 1;
+  }
+  directions
+}
+
+/// The `index`th value of dimension `dim < SOBOL_DIMS` of the Sobol sequence,\
+/// as bits of a fixed point value in `[0, 1)`.
+///
+/// XORing the result with a constant (a digital shift) randomises the\
+/// sequence whilst keeping its stratification.
+#[inline]
+pub fn sobol_bits(index: u32, dim: usize) -> u32 {
+  let directions = &SOBOL_DIRECTIONS[dim];
+  let (mut bits, mut index, mut k) = (0, index, 0);
+  while index > 0 {
+    if index & 1 == 1 {
+      bits ^= directions[k];
+    }
+    index >>= 1;
+    k += 1;
+  }
+  bits
+}
+
+/// Converts fixed point bits to a float in `[0, 1)`
+#[inline]
+pub(crate) fn bits_to_unit(bits: u32) -> f64 {
+  bits as f64 * (1.0 / (1u64 << 32) as f64)
+}
+
+/// The `index`th point of the Sobol sequence in `N <= SOBOL_DIMS` dimensions.
+pub fn sobol<const N: usize>(index: u32) -> Vector<N> {
+  debug_assert!(
+    N <= SOBOL_DIMS,
+    "Sobol sequences only support up to {SOBOL_DIMS} dimensions\nGot {N}"
+  );
+  Vector(array::from_fn(|dim| bits_to_unit(sobol_bits(index, dim))))
+}
+
+/// The unique positive root of `x^(N + 1) = x + 1`, via Newton's method.\
+/// This is the golden ratio for `N = 1` and the plastic number for `N = 2`.
+fn generalised_golden_ratio<const N: usize>() -> f64 {
+  let mut x = 2.0f64;
+  for _ in 0..32 {
+    let power = x.powi(N as i32);
+    x -= (power * x - x - 1.0) / ((N + 1) as f64 * power - 1.0);
+  }
+  x
+}
+
+/// The `index`th point of Roberts' `R` sequence in `N` dimensions,\
+/// i.e. `(0.5 + index * alpha) mod 1` with `alpha[i] = 1 / phi^(i + 1)`.\
+/// see [Roberts](https://extremelearning.com.au/unreasonable-effectiveness-of-quasirandom-sequences/)
+pub fn r_sequence<const N: usize>(index: usize) -> Vector<N> {
+  let phi = generalised_golden_ratio::<N>();
+  Vector(array::from_fn(|i| {
+    let alpha = phi.powi(-(i as i32 + 1));
+    (0.5 + alpha * index as f64).fract()
+  }))
+}
+
+/// The `index`th point of the `R2` sequence, see [`r_sequence`].
+pub fn r2(index: usize) -> Vector<2> {
+  r_sequence::<2>(index)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Whether each of the `count` bins of width `1 / count` has one value
+  fn stratified(values: impl Iterator<Item = f64>, count: usize) -> bool {
+    let mut bins = vec![0; count];
+    for x in values {
+      // values are multiples of `1 / count`, so nudge past rounding errors
+      bins[(x * count as f64 + 1e-9) as usize] += 1;
+    }
+    bins.iter().all(|&n| n == 1)
+  }
+
+  #[test]
+  fn halton_stratified() {
+    for (dim, &base) in PRIMES.iter().enumerate().take(4) {
+      let count = (base * base) as usize;
+      assert!(stratified((0..count).map(|i| halton::<4>(i)[dim]), count));
+    }
+  }
+
+  #[test]
+  fn sobol_nets() {
+    for dim in 0..SOBOL_DIMS {
+      assert!(stratified(
+        (0..256).map(|i| bits_to_unit(sobol_bits(i, dim))),
+        256
+      ));
+    }
+    // the first two dimensions form a (0, m, 2)-net,
+    // so every elementary interval of area 1 / 64 has one point
+    for log_width in 0..=6 {
+      let (w, h) = (1 << log_width, 1 << (6 - log_width));
+      let mut bins = vec![0; 64];
+      for point in (0..64).map(sobol::<2>) {
+        let (x, y) = (
+          (point[0] * w as f64) as usize,
+          (point[1] * h as f64) as usize,
+        );
+        bins[y * w + x] += 1;
+      }
+      assert!(bins.iter().all(|&n| n == 1), "{w}x{h}: {bins:?}");
+    }
+  }
+
+  #[test]
+  fn r2_plastic() {
+    let phi = generalised_golden_ratio::<2>();
+    assert!((phi - 1.324717957244746).abs() < 1e-12);
+    assert!((generalised_golden_ratio::<1>() - (1.0 + 5f64.sqrt()) / 2.0).abs() < 1e-12);
+    assert_eq!(r2(0), Vector([0.5, 0.5]));
+  }
+}
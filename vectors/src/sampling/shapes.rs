@@ -0,0 +1,255 @@
+//! Maps uniform samples in `[0, 1)²` onto shapes.
+//!
+//! Each mapping preserves the stratification of its input,\
+//! so low-discrepancy samples stay well spread out on the shape.
+//!
+//! The hemisphere and cone mappings are specific to 3D, and [`disk`]\
+//! to 2D. [`sphere_n`] and [`ball`] work in any dimension, but take\
+//! a sample in `[0, 1)ᴺ` instead.
+use crate::{Scalar, Vector};
+use std::f64::consts::{FRAC_1_PI, FRAC_PI_2, FRAC_PI_4, TAU};
+
+/// Converts a direction relative to `normal` (as the `z` axis) to world space.
+#[inline]
+fn to_world<S: Scalar>(local: Vector<3, S>, normal: Vector<3, S>) -> Vector<3, S> {
+  let (tangent, bitangent) = normal.orthonormal_basis();
+  tangent * local[0] + bitangent * local[1] + normal * local[2]
+}
+
+/// A direction at angle `acos(cos_theta)` from the `z` axis,\
+/// rotated by `phi` around it.
+#[inline]
+fn spherical<S: Scalar>(cos_theta: S, phi: S) -> Vector<3, S> {
+  let sin_theta = (S::ONE - cos_theta * cos_theta).max(S::ZERO).sqrt();
+  Vector([sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta])
+}
+
+/// A uniform point in the unit disk, via Shirley's concentric mapping.
+pub fn disk<S: Scalar>([u, v]: [S; 2]) -> Vector<2, S> {
+  let two = S::from_f64(2.0);
+  let (a, b) = (two * u - S::ONE, two * v - S::ONE);
+  if a == S::ZERO && b == S::ZERO {
+    return Vector::zeros();
+  }
+  let (radius, theta) = if a.abs() > b.abs() {
+    (a, S::from_f64(FRAC_PI_4) * (b / a))
+  } else {
+    (b, S::from_f64(FRAC_PI_2) - S::from_f64(FRAC_PI_4) * (a / b))
+  };
+  Vector([theta.cos(), theta.sin()]) * radius
+}
+
+/// A uniform direction on the unit sphere.
+pub fn sphere<S: Scalar>([u, v]: [S; 2]) -> Vector<3, S> {
+  let cos_theta = S::ONE - S::from_f64(2.0) * u;
+  spherical(cos_theta, S::from_f64(TAU) * v)
+}
+
+/// The density of [`sphere`] with respect to solid angle.
+pub fn sphere_pdf() -> f64 {
+  0.25 * FRAC_1_PI
+}
+
+/// A uniform direction in the hemisphere around the unit `normal`.
+pub fn hemisphere<S: Scalar>([u, v]: [S; 2], normal: Vector<3, S>) -> Vector<3, S> {
+  to_world(spherical(u, S::from_f64(TAU) * v), normal)
+}
+
+/// The density of [`hemisphere`] with respect to solid angle.
+pub fn hemisphere_pdf() -> f64 {
+  0.5 * FRAC_1_PI
+}
+
+/// A direction in the hemisphere around the unit `normal`,\
+/// distributed proportional to the cosine of its angle to `normal`,\
+/// via Malley's method of projecting up from the unit disk.
+pub fn cosine_hemisphere<S: Scalar>(uv: [S; 2], normal: Vector<3, S>) -> Vector<3, S> {
+  let Vector([x, y]) = disk(uv);
+  let z = (S::ONE - x * x - y * y).max(S::ZERO).sqrt();
+  to_world(Vector([x, y, z]), normal)
+}
+
+/// The density of [`cosine_hemisphere`] with respect to solid angle,\
+/// for a direction with cosine `cos_theta` to the normal.
+pub fn cosine_hemisphere_pdf(cos_theta: f64) -> f64 {
+  cos_theta.max(0.0) * FRAC_1_PI
+}
+
+/// A uniform direction within `half_angle` radians of the unit `axis`.
+pub fn cone<S: Scalar>([u, v]: [S; 2], axis: Vector<3, S>, half_angle: f64) -> Vector<3, S> {
+  let cos_max = S::from_f64(half_angle.cos());
+  let cos_theta = S::ONE - u * (S::ONE - cos_max);
+  to_world(spherical(cos_theta, S::from_f64(TAU) * v), axis)
+}
+
+/// The density of [`cone`] with respect to solid angle.
+pub fn cone_pdf(half_angle: f64) -> f64 {
+  1.0 / (TAU * (1.0 - half_angle.cos()))
+}
+
+/// The inverse of the standard normal CDF, via Acklam's rational\
+/// approximation, which has a relative error below `1.15e-9`.
+fn normal_quantile(p: f64) -> f64 {
+  const A: [f64; 6] = [
+    -3.969683028665376e1,
+    2.209460984245205e2,
+    -2.759285104469687e2,
+    1.38357751867269e2,
+    -3.066479806614716e1,
+    2.506628277459239,
+  ];
+  const B: [f64; 5] = [
+    -5.447609879822406e1,
+    1.615858368580409e2,
+    -1.556989798598866e2,
+    6.680131188771972e1,
+    -1.328068155288572e1,
+  ];
+  const C: [f64; 6] = [
+    -7.784894002430293e-3,
+    -3.223964580411365e-1,
+    -2.400758277161838,
+    -2.549732539343734,
+    4.374664141464968,
+    2.938163982698783,
+  ];
+  const D: [f64; 4] = [
+    7.784695709041462e-3,
+    3.224671290700398e-1,
+    2.445134137142996,
+    3.754408661907416,
+  ];
+  let poly = |coeffs: &[f64], x: f64| coeffs.iter().fold(0.0, |acc, c| acc * x + c);
+  // the tails, where the central approximation breaks down
+  let tail = |p: f64| {
+    let q = (-2.0 * p.ln()).sqrt();
+    poly(&C, q) / (poly(&D, q) * q + 1.0)
+  };
+
+  // `0` would map to an infinite sample
+  let p = p.clamp(f64::EPSILON, 1.0 - f64::EPSILON);
+  if p < 0.02425 {
+    tail(p)
+  } else if p > 1.0 - 0.02425 {
+    -tail(1.0 - p)
+  } else {
+    let q = p - 0.5;
+    let r = q * q;
+    poly(&A, r) * q / (poly(&B, r) * r + 1.0)
+  }
+}
+
+/// The surface area of the unit sphere in `N` dimensions
+fn sphere_area<const N: usize>() -> f64 {
+  // each dimension adds `2π / (n - 2)` times the area two dimensions down
+  let start = match N % 2 {
+    1 => (2.0, 1),
+    _ => (TAU, 2),
+  };
+  (start.1 + 2..=N)
+    .step_by(2)
+    .fold(start.0, |area, n| area * TAU / (n - 2) as f64)
+}
+
+/// A uniform direction on the unit sphere in `N` dimensions,\
+/// by normalising a sample from the standard normal distribution.
+///
+/// Each coordinate is mapped through the inverse normal CDF,\
+/// so this takes `N` dimensions, rather than the `N - 1` of [`sphere`].
+pub fn sphere_n<const N: usize, S: Scalar>(us: [S; N]) -> Vector<N, S> {
+  let normal = Vector(us.map(|u| normal_quantile(u.to_f64())));
+  match normal.mag2() > 0.0 {
+    true => normal.normal().cast(),
+    false => Vector::axis(0),
+  }
+}
+
+/// The density of [`sphere_n`], with respect to the area of the sphere.
+pub fn sphere_n_pdf<const N: usize>() -> f64 {
+  sphere_area::<N>().recip()
+}
+
+/// A uniform point in the unit ball in `N` dimensions,\
+/// scaling a direction from [`sphere_n`] by `w^(1 / N)`.
+pub fn ball<const N: usize, S: Scalar>(us: [S; N], w: S) -> Vector<N, S> {
+  sphere_n(us) * S::from_f64(w.to_f64().powf((N as f64).recip()))
+}
+
+/// The density of [`ball`], with respect to the volume of the ball.
+pub fn ball_pdf<const N: usize>() -> f64 {
+  N as f64 / sphere_area::<N>()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::sampling::sequences::halton;
+  use std::f64::consts::PI;
+
+  fn samples() -> impl Iterator<Item = [f64; 2]> {
+    (0..256).map(|i| halton::<2>(i).0)
+  }
+
+  #[test]
+  fn on_shapes() {
+    let normal = Vector([1.0, -2.0, 2.0]) / 3.0;
+    for uv in samples() {
+      assert!(disk(uv).mag() <= 1.0 + 1e-12);
+      assert!((sphere(uv).mag() - 1.0).abs() < 1e-12);
+      for dir in [hemisphere(uv, normal), cosine_hemisphere(uv, normal)] {
+        assert!((dir.mag() - 1.0).abs() < 1e-12);
+        assert!(dir.dot(normal) >= -1e-12);
+      }
+      let dir = cone(uv, normal, 0.3);
+      assert!(dir.dot(normal) >= 0.3f64.cos() - 1e-12);
+    }
+  }
+
+  #[test]
+  fn on_n_spheres() {
+    let mut mean = Vector::<4>::zeros();
+    for i in 0..1024 {
+      let Vector([a, b, c, d, w]) = halton::<5>(i);
+      let dir = sphere_n([a, b, c, d]);
+      assert!((dir.mag() - 1.0).abs() < 1e-12);
+      assert!(ball([a, b, c, d], w).mag() <= 1.0 + 1e-12);
+      mean = mean + dir / 1024.0;
+    }
+    assert!(mean.mag() < 1e-2, "mean direction {mean:?}");
+
+    // the heights on the 2-sphere are uniform in `[-1, 1]`
+    let height = (0..1024)
+      .map(|i| sphere_n(halton::<3>(i).0)[2].abs())
+      .sum::<f64>()
+      / 1024.0;
+    assert!((height - 0.5).abs() < 1e-2, "mean height {height}");
+  }
+
+  #[test]
+  fn n_sphere_densities() {
+    assert!((sphere_n_pdf::<3>() - sphere_pdf()).abs() < 1e-12);
+    assert!((sphere_n_pdf::<2>() - 1.0 / TAU).abs() < 1e-12);
+    assert!((ball_pdf::<2>() - FRAC_1_PI).abs() < 1e-12);
+    assert!((ball_pdf::<3>() - 3.0 / (4.0 * PI)).abs() < 1e-12);
+    assert!((ball_pdf::<4>() - 2.0 / (PI * PI)).abs() < 1e-12);
+  }
+
+  #[test]
+  fn normal_quantiles() {
+    assert_eq!(normal_quantile(0.5), 0.0);
+    assert!((normal_quantile(0.975) - 1.959963985).abs() < 1e-8);
+    assert!((normal_quantile(0.001) + 3.090232306).abs() < 1e-8);
+    assert!(normal_quantile(0.0).is_finite());
+  }
+
+  #[test]
+  fn cosine_weighted() {
+    // the mean cosine of a cosine weighted hemisphere is `2 / 3`
+    let normal = Vector([0.0, 0.0, -1.0]);
+    let mean = samples()
+      .map(|uv| cosine_hemisphere(uv, normal).dot(normal))
+      .sum::<f64>()
+      / 256.0;
+    assert!((mean - 2.0 / 3.0).abs() < 1e-2, "mean cosine {mean}");
+  }
+}
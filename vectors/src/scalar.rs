@@ -0,0 +1,355 @@
+#[cfg(feature = "simd")]
+use super::simd::{self, Wide};
+use std::{
+  array,
+  fmt::Debug,
+  iter::Sum,
+  ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+/// A floating point type that vectors and `SDF`s can be evaluated with.
+///
+/// This lets us choose between `f64` for precision and `f32` for speed,\
+/// whilst all the parameters of a model are still stored as `f64`s\
+/// and converted to the scalar type on evaluation.
+pub trait Scalar:
+  Copy
+  + Debug
+  + PartialEq
+  + PartialOrd
+  + Send
+  + Sync
+  + 'static
+  + Add<Output = Self>
+  + Sub<Output = Self>
+  + Mul<Output = Self>
+  + Div<Output = Self>
+  + Neg<Output = Self>
+  + AddAssign
+  + SubAssign
+  + MulAssign
+  + DivAssign
+  + Sum
+{
+  const ZERO: Self;
+  const ONE: Self;
+  /// The difference between `1.0` and the next representable value
+  const EPSILON: Self;
+  /// The smallest finite value
+  const MIN: Self;
+  /// The largest finite value
+  const MAX: Self;
+  const INFINITY: Self;
+  const NAN: Self;
+
+  /// Converts from a double, rounding if needed
+  fn from_f64(value: f64) -> Self;
+  /// Converts to a double
+  fn to_f64(self) -> f64;
+
+  #[inline]
+  fn from_usize(value: usize) -> Self {
+    Self::from_f64(value as f64)
+  }
+
+  fn abs(self) -> Self;
+  fn signum(self) -> Self;
+  fn sqrt(self) -> Self;
+  fn recip(self) -> Self;
+  fn floor(self) -> Self;
+  fn sin(self) -> Self;
+  fn cos(self) -> Self;
+  fn tan(self) -> Self;
+  fn acos(self) -> Self;
+  fn atan2(self, other: Self) -> Self;
+  /// Minimum of two values, ignoring `NaN`s
+  fn min(self, other: Self) -> Self;
+  /// Maximum of two values, ignoring `NaN`s
+  fn max(self, other: Self) -> Self;
+  fn is_finite(self) -> bool;
+  fn is_nan(self) -> bool;
+
+  #[inline]
+  fn clamp(self, min: Self, max: Self) -> Self {
+    self.max(min).min(max)
+  }
+
+  /// Picks `then` when `self > 0`, and `otherwise` when not.
+  ///
+  /// Scalars that can't always tell, i.e. intervals containing `0`,\
+  /// cover both arms, so this is safe to branch on positions with.
+  #[inline]
+  fn if_positive(self, then: Self, otherwise: Self) -> Self {
+    match self > Self::ZERO {
+      true => then,
+      false => otherwise,
+    }
+  }
+
+  /// Whether this is a single value, rather than a range of them.
+  ///
+  /// Intervals cover a range unless their bounds meet, and code that only\
+  /// follows one value, e.g. a nearest neighbour search, can't bound them.
+  #[inline]
+  fn is_point(self) -> bool {
+    true
+  }
+
+  // Element-wise kernels for `Vector` arithmetic.
+  // These are plain loops by default, but are overridden\
+  // with `SIMD` lanes for `f32` vectors under the `simd` feature.
+
+  #[inline]
+  fn lanes_add<const N: usize>(lhs: [Self; N], rhs: [Self; N]) -> [Self; N] {
+    array::from_fn(|i| lhs[i] + rhs[i])
+  }
+
+  #[inline]
+  fn lanes_sub<const N: usize>(lhs: [Self; N], rhs: [Self; N]) -> [Self; N] {
+    array::from_fn(|i| lhs[i] - rhs[i])
+  }
+
+  #[inline]
+  fn lanes_mul<const N: usize>(lhs: [Self; N], rhs: [Self; N]) -> [Self; N] {
+    array::from_fn(|i| lhs[i] * rhs[i])
+  }
+
+  #[inline]
+  fn lanes_div<const N: usize>(lhs: [Self; N], rhs: [Self; N]) -> [Self; N] {
+    array::from_fn(|i| lhs[i] / rhs[i])
+  }
+
+  #[inline]
+  fn lanes_min<const N: usize>(lhs: [Self; N], rhs: [Self; N]) -> [Self; N] {
+    array::from_fn(|i| lhs[i].min(rhs[i]))
+  }
+
+  #[inline]
+  fn lanes_max<const N: usize>(lhs: [Self; N], rhs: [Self; N]) -> [Self; N] {
+    array::from_fn(|i| lhs[i].max(rhs[i]))
+  }
+
+  #[inline]
+  fn lanes_dot<const N: usize>(lhs: [Self; N], rhs: [Self; N]) -> Self {
+    (0..N).fold(Self::ZERO, |acc, i| acc + lhs[i] * rhs[i])
+  }
+
+  #[inline]
+  fn lanes_mag2<const N: usize>(xs: [Self; N]) -> Self {
+    Self::lanes_dot(xs, xs)
+  }
+}
+
+macro_rules! impl_scalar {
+  ($($float:ident),*) => {$(
+    impl Scalar for $float {
+      const ZERO: Self = 0.0;
+      const ONE: Self = 1.0;
+      const EPSILON: Self = $float::EPSILON;
+      const MIN: Self = $float::MIN;
+      const MAX: Self = $float::MAX;
+      const INFINITY: Self = $float::INFINITY;
+      const NAN: Self = $float::NAN;
+
+      #[inline]
+      fn from_f64(value: f64) -> Self {
+        value as $float
+      }
+
+      #[inline]
+      fn to_f64(self) -> f64 {
+        self as f64
+      }
+
+      #[inline]
+      fn abs(self) -> Self {
+        $float::abs(self)
+      }
+
+      #[inline]
+      fn signum(self) -> Self {
+        $float::signum(self)
+      }
+
+      #[inline]
+      fn sqrt(self) -> Self {
+        $float::sqrt(self)
+      }
+
+      #[inline]
+      fn recip(self) -> Self {
+        $float::recip(self)
+      }
+
+      #[inline]
+      fn floor(self) -> Self {
+        $float::floor(self)
+      }
+
+      #[inline]
+      fn sin(self) -> Self {
+        $float::sin(self)
+      }
+
+      #[inline]
+      fn cos(self) -> Self {
+        $float::cos(self)
+      }
+
+      #[inline]
+      fn tan(self) -> Self {
+        $float::tan(self)
+      }
+
+      #[inline]
+      fn acos(self) -> Self {
+        $float::acos(self)
+      }
+
+      #[inline]
+      fn atan2(self, other: Self) -> Self {
+        $float::atan2(self, other)
+      }
+
+      #[inline]
+      fn min(self, other: Self) -> Self {
+        $float::min(self, other)
+      }
+
+      #[inline]
+      fn max(self, other: Self) -> Self {
+        $float::max(self, other)
+      }
+
+      #[inline]
+      fn is_finite(self) -> bool {
+        $float::is_finite(self)
+      }
+
+      #[inline]
+      fn is_nan(self) -> bool {
+        $float::is_nan(self)
+      }
+
+      impl_lanes!($float);
+    }
+  )*};
+}
+
+/// Overrides the element-wise kernels with `SIMD` lanes,\
+/// only for the scalars where the lanes beat the plain loops.
+macro_rules! impl_lanes {
+  (f32) => {
+    #[cfg(feature = "simd")]
+    #[inline]
+    fn lanes_add<const N: usize>(lhs: [Self; N], rhs: [Self; N]) -> [Self; N] {
+      simd::zip_with(lhs, rhs, |l, r| l + r, |l, r| l + r)
+    }
+
+    #[cfg(feature = "simd")]
+    #[inline]
+    fn lanes_sub<const N: usize>(lhs: [Self; N], rhs: [Self; N]) -> [Self; N] {
+      simd::zip_with(lhs, rhs, |l, r| l - r, |l, r| l - r)
+    }
+
+    #[cfg(feature = "simd")]
+    #[inline]
+    fn lanes_mul<const N: usize>(lhs: [Self; N], rhs: [Self; N]) -> [Self; N] {
+      simd::zip_with(lhs, rhs, |l, r| l * r, |l, r| l * r)
+    }
+
+    #[cfg(feature = "simd")]
+    #[inline]
+    fn lanes_div<const N: usize>(lhs: [Self; N], rhs: [Self; N]) -> [Self; N] {
+      simd::zip_with(lhs, rhs, |l, r| l / r, |l, r| l / r)
+    }
+
+    #[cfg(feature = "simd")]
+    #[inline]
+    fn lanes_min<const N: usize>(lhs: [Self; N], rhs: [Self; N]) -> [Self; N] {
+      simd::zip_with(lhs, rhs, <Self as Wide>::lanes_min, f32::min)
+    }
+
+    #[cfg(feature = "simd")]
+    #[inline]
+    fn lanes_max<const N: usize>(lhs: [Self; N], rhs: [Self; N]) -> [Self; N] {
+      simd::zip_with(lhs, rhs, <Self as Wide>::lanes_max, f32::max)
+    }
+
+    #[cfg(feature = "simd")]
+    #[inline]
+    fn lanes_dot<const N: usize>(lhs: [Self; N], rhs: [Self; N]) -> Self {
+      simd::dot(lhs, rhs)
+    }
+  };
+  ($float:ident) => {};
+}
+
+impl_scalar!(f32, f64);
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn conversions<S: Scalar>() {
+    for value in [0.0, 1.0, -2.5, 0.125, 1e6] {
+      assert_eq!(S::from_f64(value).to_f64(), value);
+    }
+    assert_eq!(S::from_usize(7), S::from_f64(7.0));
+    assert!(S::from_f64(f64::NAN).is_nan());
+    assert_eq!(S::from_f64(f64::INFINITY), S::INFINITY);
+    assert_eq!(S::ZERO.to_f64(), 0.0);
+    assert_eq!(S::ONE.to_f64(), 1.0);
+  }
+
+  fn min_max<S: Scalar>() {
+    let (one, two) = (S::ONE, S::from_f64(2.0));
+    assert_eq!(one.min(two), one);
+    assert_eq!(one.max(two), two);
+    // `Vector::min` and `Vector::max` seed their folds with `NAN`
+    assert_eq!(S::NAN.min(two), two);
+    assert_eq!(two.min(S::NAN), two);
+    assert_eq!(S::NAN.max(one), one);
+    assert_eq!(one.max(S::NAN), one);
+    assert!(S::NAN.min(S::NAN).is_nan());
+    assert_eq!(S::from_f64(3.0).clamp(one, two), two);
+    assert_eq!(S::from_f64(-3.0).clamp(one, two), one);
+  }
+
+  fn branches<S: Scalar>() {
+    let (then, otherwise) = (S::ONE, -S::ONE);
+    assert_eq!(S::from_f64(0.5).if_positive(then, otherwise), then);
+    assert_eq!(S::ZERO.if_positive(then, otherwise), otherwise);
+    assert_eq!((-S::ZERO).if_positive(then, otherwise), otherwise);
+    assert_eq!(S::from_f64(-0.5).if_positive(then, otherwise), otherwise);
+    assert_eq!(S::NAN.if_positive(then, otherwise), otherwise);
+    assert!(S::NAN.is_point() && S::INFINITY.is_point());
+  }
+
+  fn signs<S: Scalar>() {
+    // Zeros keep their sign, rather than giving `0`
+    assert_eq!(S::ZERO.signum(), S::ONE);
+    assert_eq!((-S::ZERO).signum(), -S::ONE);
+    assert_eq!(S::from_f64(-3.0).signum(), -S::ONE);
+    assert_eq!(S::from_f64(-3.0).abs(), S::from_f64(3.0));
+    assert!(S::NAN.signum().is_nan());
+    assert!(!S::INFINITY.is_finite());
+    assert!(S::MAX.is_finite() && S::MIN.is_finite());
+  }
+
+  #[test]
+  fn f32_semantics() {
+    conversions::<f32>();
+    min_max::<f32>();
+    branches::<f32>();
+    signs::<f32>();
+  }
+
+  #[test]
+  fn f64_semantics() {
+    conversions::<f64>();
+    min_max::<f64>();
+    branches::<f64>();
+    signs::<f64>();
+  }
+}
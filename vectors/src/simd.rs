@@ -0,0 +1,162 @@
+//! `SIMD` kernels for small vectors, enabled with the `simd` feature.
+//!
+//! Only `f32` vectors of 4 elements are packed, filling an `f32x4` exactly.\
+//! In the `vector_ops` bench, padding every other width (and all the `f64`\
+//! ones, including the default `Vector<3>`) into lanes ran slower than\
+//! the plain loops, which the compiler already vectorises, as packing and\
+//! unpacking each op costs more than the lanes save. So those keep the loops.
+use super::Scalar;
+use std::{
+  array,
+  ops::{Add, Div, Mul, Sub},
+};
+use wide::f32x4;
+
+/// The number of lanes in each `SIMD` register we use
+pub(crate) const LANES: usize = 4;
+
+/// Whether a vector of `N` elements fills the lanes exactly
+#[inline(always)]
+pub(crate) const fn fits_lanes<const N: usize>() -> bool {
+  N == LANES
+}
+
+/// A scalar with a `SIMD` type of `LANES` lanes
+pub(crate) trait Wide: Scalar {
+  type Lanes: Copy
+    + Add<Output = Self::Lanes>
+    + Sub<Output = Self::Lanes>
+    + Mul<Output = Self::Lanes>
+    + Div<Output = Self::Lanes>;
+
+  fn pack(lanes: [Self; LANES]) -> Self::Lanes;
+  fn unpack(lanes: Self::Lanes) -> [Self; LANES];
+  fn reduce_add(lanes: Self::Lanes) -> Self;
+  /// Lanewise minimum, ignoring `NaN`s
+  fn lanes_min(lhs: Self::Lanes, rhs: Self::Lanes) -> Self::Lanes;
+  /// Lanewise maximum, ignoring `NaN`s
+  fn lanes_max(lhs: Self::Lanes, rhs: Self::Lanes) -> Self::Lanes;
+}
+
+impl Wide for f32 {
+  type Lanes = f32x4;
+
+  #[inline(always)]
+  fn pack(lanes: [Self; LANES]) -> Self::Lanes {
+    f32x4::new(lanes)
+  }
+
+  #[inline(always)]
+  fn unpack(lanes: Self::Lanes) -> [Self; LANES] {
+    lanes.to_array()
+  }
+
+  #[inline(always)]
+  fn reduce_add(lanes: Self::Lanes) -> Self {
+    lanes.reduce_add()
+  }
+
+  #[inline(always)]
+  fn lanes_min(lhs: Self::Lanes, rhs: Self::Lanes) -> Self::Lanes {
+    lhs.min(rhs)
+  }
+
+  #[inline(always)]
+  fn lanes_max(lhs: Self::Lanes, rhs: Self::Lanes) -> Self::Lanes {
+    lhs.max(rhs)
+  }
+}
+
+/// Packs an array that fills the lanes
+#[inline(always)]
+fn pack<S: Wide, const N: usize>(xs: [S; N]) -> S::Lanes {
+  S::pack(array::from_fn(|i| xs[i]))
+}
+
+/// Applies `op` lanewise if the arrays fill the lanes,\
+/// otherwise applies `fallback` element-wise.
+#[inline(always)]
+pub(crate) fn zip_with<S: Wide, const N: usize>(
+  lhs: [S; N],
+  rhs: [S; N],
+  op: impl Fn(S::Lanes, S::Lanes) -> S::Lanes,
+  fallback: impl Fn(S, S) -> S,
+) -> [S; N] {
+  if fits_lanes::<N>() {
+    let lanes = S::unpack(op(pack(lhs), pack(rhs)));
+    array::from_fn(|i| lanes[i])
+  } else {
+    array::from_fn(|i| fallback(lhs[i], rhs[i]))
+  }
+}
+
+/// The sum of the element-wise products.
+#[inline(always)]
+pub(crate) fn dot<S: Wide, const N: usize>(lhs: [S; N], rhs: [S; N]) -> S {
+  if fits_lanes::<N>() {
+    S::reduce_add(pack(lhs) * pack(rhs))
+  } else {
+    (0..N).fold(S::ZERO, |acc, i| acc + lhs[i] * rhs[i])
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::sampling::{RandomSampler, Sampler};
+  use crate::Vector;
+
+  fn check<const N: usize, S: Scalar>() {
+    let a = Vector::<N, S>(array::from_fn(|i| S::from_f64(i as f64 - 1.5)));
+    let b = Vector::<N, S>(array::from_fn(|i| S::from_f64(2.0 / (i as f64 + 1.0))));
+    for i in 0..N {
+      assert_eq!((a + b)[i], a[i] + b[i]);
+      assert_eq!((a - b)[i], a[i] - b[i]);
+      assert_eq!((a * b)[i], a[i] * b[i]);
+      assert_eq!((a / b)[i], a[i] / b[i]);
+      assert_eq!(a.el_min(b)[i], a[i].min(b[i]));
+      assert_eq!(a.el_max(b)[i], a[i].max(b[i]));
+    }
+    let dot = (0..N).fold(S::ZERO, |acc, i| acc + a[i] * b[i]);
+    assert!((a.dot(b) - dot).abs().to_f64() < 1e-6);
+  }
+
+  #[test]
+  fn lanes_match_scalar() {
+    check::<1, f32>();
+    check::<3, f32>();
+    check::<4, f32>();
+    check::<5, f32>();
+    check::<3, f64>();
+    check::<4, f64>();
+  }
+
+  /// The lanes should give the same results as the plain loops for\
+  /// every operation on `f32x4`, on inputs of any sign and size.
+  #[test]
+  fn random_f32x4_match_scalar() {
+    let mut sampler = RandomSampler::new(5);
+    let mut random = || array::from_fn(|_| (sampler.next_1d() * 20.0 - 10.0) as f32);
+    for _ in 0..1000 {
+      let (a, b): ([f32; 4], [f32; 4]) = (random(), random());
+      let dot = (0..4).fold(0.0, |acc, i| acc + a[i] * b[i]);
+      let mag = (0..4).fold(0.0, |acc, i| acc + a[i] * a[i]).sqrt();
+      let close = |x: f32, y: f32| (x - y).abs() <= 1e-5 * (1.0 + y.abs());
+      assert!(close(Vector(a).dot(Vector(b)), dot));
+      assert!(close(Vector(a).mag(), mag));
+      assert_eq!(
+        Vector(a).el_max(Vector(b)).0,
+        array::from_fn(|i| a[i].max(b[i]))
+      );
+      assert_eq!(
+        Vector(a).el_min(Vector(b)).0,
+        array::from_fn(|i| a[i].min(b[i]))
+      );
+
+      let [a0, a1, a2, _] = a;
+      let [b0, b1, b2, _] = b;
+      let cross = [a1 * b2 - a2 * b1, a2 * b0 - a0 * b2, a0 * b1 - a1 * b0];
+      assert_eq!(Vector([a0, a1, a2]).cross(Vector([b0, b1, b2])).0, cross);
+    }
+  }
+}
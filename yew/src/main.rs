@@ -1,4 +1,4 @@
-use marchrs_rays::{PerspectiveCamera3D, ScreenInfo, Solid, SphereMarcher, BLACK};
+use marchrs_rays::{Executor, PerspectiveCamera3D, ScreenInfo, Solid, SphereMarcher, BLACK};
 use yew::prelude::*;
 
 // mod configs;
@@ -16,6 +16,11 @@ pub fn app() -> Html {
   let camera = PerspectiveCamera3D::default();
   let screen = ScreenInfo::new([0.3, 0.2], [1200, 800]);
   let renderer = Solid { background: BLACK };
+  // Plain wasm has no threads to share the work between
+  let executor = match cfg!(all(target_arch = "wasm32", not(target_feature = "atomics"))) {
+    true => Executor::Sequential,
+    false => Executor::Global,
+  };
 
   html! {
       <main>
@@ -26,6 +31,7 @@ pub fn app() -> Html {
             {screen}
             model={both()}
             {renderer}
+            {executor}
           />
       </main>
   }
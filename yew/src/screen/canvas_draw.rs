@@ -1,4 +1,5 @@
 use super::Rgba;
+use marchrs_iterators::Grid;
 use std::rc::Rc;
 use wasm_bindgen::{Clamped, JsCast, UnwrapThrowExt};
 use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, ImageData};
@@ -8,13 +9,14 @@ use yew::prelude::*;
 ///
 /// - `node`: the canvas element to draw pixels on
 /// - `pixels`: the pixel buffer to draw to the canvas
-/// - `dims`: the width & height of the image data to draw
 ///
-/// This hook will redraw on changes to `pixels` or `dims`.
+/// This hook will redraw on changes to `pixels`.
 #[hook]
-pub fn use_canvas_draw(node: NodeRef, pixels: Rc<Vec<Rgba>>, dims: [usize; 2]) {
+pub fn use_canvas_draw(node: NodeRef, pixels: Rc<Grid<2, Rgba>>) {
+  let dims = pixels.dims();
   let pixel_data = use_memo(pixels, |pixels| -> Vec<_> {
-    pixels.iter().flat_map(|rgba| rgba.0.into_iter()).collect()
+    let pixels = pixels.as_slice().iter();
+    pixels.flat_map(|rgba| rgba.0.into_iter()).collect()
   });
 
   use_effect_with(
@@ -1,9 +1,14 @@
+use marchrs_iterators::{CancelToken, Grid};
 use marchrs_rays::{
-  PerspectiveCamera3D, RayMarcher, RaySource, Renderer, Rgba, ScreenInfo, Solid, SphereMarcher,
+  Executor, PerspectiveCamera3D, Progressive, RayMarcher, Renderer, Rgba, ScreenInfo, Solid,
+  SphereMarcher, BLACK,
 };
 use marchrs_sdf::traits::DynModel;
-use rayon::iter::IntoParallelIterator;
-use yew::prelude::*;
+use std::{rc::Rc, time::Duration};
+use yew::{
+  platform::{spawn_local, time::sleep},
+  prelude::*,
+};
 
 mod canvas_draw;
 use canvas_draw::use_canvas_draw;
@@ -15,6 +20,7 @@ pub struct ScreenProps {
   pub screen: ScreenInfo<2>,
   pub model: DynModel<3, Rgba>,
   pub renderer: Solid<Rgba>,
+  pub executor: Executor,
 }
 
 #[function_component]
@@ -25,26 +31,48 @@ pub fn Screen(props: &ScreenProps) -> Html {
     screen,
     model,
     renderer,
+    executor,
   } = props;
 
   let canvas_ref = use_node_ref();
+  let pixels = use_state(|| Rc::new(Grid::new(screen.res, BLACK)));
+  // the token of the render in flight, cancelled once it's stale
+  let cancel = use_mut_ref(CancelToken::new);
+
+  // run the rendering pipeline, whenever the props change
+  {
+    let (pixels, cancel, renderer) = (pixels.clone(), cancel.clone(), *renderer);
+    use_effect_with(
+      (*marcher, *source, *screen, model.clone(), executor.clone()),
+      move |(marcher, source, screen, model, executor)| {
+        let token = CancelToken::new();
+        cancel.replace(token.clone()).cancel();
+
+        let (marcher, source, screen) = (*marcher, *source, *screen);
+        let (model, executor) = (model.clone(), executor.clone());
+        spawn_local(async move {
+          let mut image = Progressive::new(screen.res, BLACK);
+          let render =
+            |idx| renderer.render(&model, marcher.march(&model, source.ray(&screen, idx)));
+          // yield before each pass, so the canvas can show the last one
+          // and props that change in the meantime can cancel this render
+          loop {
+            sleep(Duration::ZERO).await;
+            let Ok(more) = image.render_pass(&executor, render, Some(&token)) else {
+              break;
+            };
+            pixels.set(Rc::new(Grid::from_vec(screen.res, image.pixels.clone())));
+            if !more {
+              break;
+            }
+          }
+        });
+        move || cancel.borrow().cancel()
+      },
+    );
+  }
 
-  // run the rendering pipeline
-  let render = use_memo(
-    (*marcher, *source, *screen, model.clone()),
-    |(marcher, source, screen, model)| -> Vec<_> {
-      source
-        .rays(screen)
-        .into_par_iter()
-        .map(|ray| {
-          let hit = marcher.march(model, ray);
-          renderer.render(model, hit)
-        })
-        .collect()
-    },
-  );
-
-  use_canvas_draw(canvas_ref.clone(), render, screen.res.0);
+  use_canvas_draw(canvas_ref.clone(), (*pixels).clone());
 
   html! {
     <canvas